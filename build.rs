@@ -0,0 +1,466 @@
+use std::{collections::BTreeSet, env, fmt::Write, fs, path::Path};
+
+use serde_json::Value;
+
+// Generates Rust structs for FHIR resources from the StructureDefinitions in
+// `definitions/<version>/`. Only resources whose cargo feature is enabled are
+// generated: `r4-patient` reads `definitions/r4/Patient.json`. The output is
+// included by `src/resources/generated.rs`.
+//
+// The definitions are official StructureDefinitions trimmed to the elements
+// the crate models; the generator only reads the snapshot elements' path, min,
+// max, type, contentReference and binding, so an untrimmed one also works but
+// generates every element. The resource type constant comes from the
+// definition's type, and the Nostr kind from `definitions/kinds.json`.
+const VERSIONS: [&str; 2] = ["r4", "r5"];
+
+// Elements handled by the crate itself rather than generated as fields
+const SKIPPED_ROOT: [&str; 6] = [
+    "id",
+    "meta",
+    "implicitRules",
+    "language",
+    "text",
+    "contained",
+];
+const SKIPPED_ANY: [&str; 3] = ["id", "extension", "modifierExtension"];
+const RUST_KEYWORDS: [&str; 10] = [
+    "type", "use", "for", "ref", "match", "abstract", "final", "class", "in", "mod",
+];
+// Fields used for the narrative, first match wins
+const NARRATIVE_FIELDS: [&str; 4] = ["name", "title", "description", "code"];
+
+fn main() {
+    println!("cargo:rerun-if-changed=definitions");
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    let kinds: Value = fs::read_to_string(Path::new("definitions").join("kinds.json"))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .expect("definitions/kinds.json maps resource types to Nostr kinds");
+    for version in VERSIONS {
+        let dir = Path::new("definitions").join(version);
+        let mut paths: Vec<_> = fs::read_dir(&dir)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        paths.sort();
+        let mut code = String::from("// @generated by build.rs\n");
+        for path in paths {
+            let json = fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("Cannot read {}: {}", path.display(), e));
+            let definition: Value = serde_json::from_str(&json)
+                .unwrap_or_else(|e| panic!("Invalid JSON in {}: {}", path.display(), e));
+            let resource_type = definition["type"]
+                .as_str()
+                .unwrap_or_else(|| panic!("{} has no type", path.display()));
+            let feature = format!(
+                "CARGO_FEATURE_{}_{}",
+                version.to_uppercase(),
+                snake_case(resource_type).to_uppercase()
+            );
+            if env::var_os(feature).is_none() {
+                continue;
+            }
+            let kind = kinds[resource_type]
+                .as_u64()
+                .unwrap_or_else(|| panic!("No Nostr kind for {} in kinds.json", resource_type));
+            let generator = Generator::new(&definition, kind)
+                .unwrap_or_else(|e| panic!("Cannot generate {}: {}", path.display(), e));
+            code.push_str(&generator.generate());
+        }
+        fs::write(Path::new(&out_dir).join(format!("{}.rs", version)), code)
+            .expect("Cannot write generated resources");
+    }
+}
+
+struct Element {
+    path: String,
+    min: u32,
+    max: Option<u32>,
+    types: Vec<String>,
+    content_reference: Option<String>,
+    binding: Option<(String, String)>,
+}
+impl Element {
+    fn from_json(element: &Value) -> Result<Self, String> {
+        let path = element["path"]
+            .as_str()
+            .ok_or("Element without a path")?
+            .to_string();
+        let max = match element["max"].as_str().unwrap_or("1") {
+            "*" => None,
+            max => Some(max.parse().map_err(|_| format!("Bad max for {}", path))?),
+        };
+        let binding = element.get("binding").and_then(|binding| {
+            let value_set = binding["valueSet"].as_str()?;
+            let value_set = value_set.split('|').next().unwrap_or(value_set);
+            Some((
+                binding["strength"].as_str()?.to_string(),
+                value_set.to_string(),
+            ))
+        });
+        Ok(Self {
+            min: element["min"].as_u64().unwrap_or(0) as u32,
+            max,
+            types: element["type"]
+                .as_array()
+                .map(|types| {
+                    types
+                        .iter()
+                        .filter_map(|t| t["code"].as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            content_reference: element["contentReference"]
+                .as_str()
+                .map(|reference| reference.trim_start_matches('#').to_string()),
+            binding,
+            path,
+        })
+    }
+    fn name(&self) -> &str {
+        self.path.rsplit('.').next().unwrap_or(&self.path)
+    }
+    fn is_choice(&self) -> bool {
+        self.name().ends_with("[x]")
+    }
+    fn is_backbone(&self) -> bool {
+        self.content_reference.is_none()
+            && matches!(self.types.as_slice(), [t] if t == "BackboneElement" || t == "Element")
+    }
+}
+
+struct Generator {
+    resource_type: String,
+    kind: u64,
+    url: String,
+    fhir_version: String,
+    elements: Vec<Element>,
+}
+impl Generator {
+    fn new(definition: &Value, kind: u64) -> Result<Self, String> {
+        let elements = definition["snapshot"]["element"]
+            .as_array()
+            .ok_or("StructureDefinition has no snapshot")?
+            .iter()
+            .map(Element::from_json)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            resource_type: definition["type"].as_str().ok_or("No type")?.to_string(),
+            kind,
+            url: definition["url"].as_str().ok_or("No url")?.to_string(),
+            fhir_version: definition["fhirVersion"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            elements,
+        })
+    }
+    fn children(&self, path: &str) -> Vec<&Element> {
+        let depth = path.matches('.').count() + 1;
+        let root = path == self.resource_type;
+        self.elements
+            .iter()
+            .filter(|element| {
+                element.path.starts_with(&format!("{}.", path))
+                    && element.path.matches('.').count() == depth
+                    && !SKIPPED_ANY.contains(&element.name())
+                    && !(root && SKIPPED_ROOT.contains(&element.name()))
+            })
+            .collect()
+    }
+    fn generate(&self) -> String {
+        let mut items = vec![];
+        self.generate_struct(&self.resource_type, &mut items);
+        let name = struct_name(&self.resource_type);
+        let mut code = items.join("\n");
+        let elements: Vec<&Element> = self.all_fields(&self.resource_type);
+        let _ = write!(
+            code,
+            "\nimpl crate::resources::FhirStructureDefinition for {} {{\n    const URL: &'static str = {:?};\n    const FHIR_VERSION: &'static str = {:?};\n    const ELEMENTS: &'static [crate::resources::FhirElementDefinition] = &[\n",
+            name, self.url, self.fhir_version
+        );
+        for element in elements {
+            let binding = match &element.binding {
+                Some((strength, value_set)) => format!(
+                    "Some(crate::resources::FhirElementBinding {{ strength: crate::resources::FhirBindingStrength::{}, value_set: {:?} }})",
+                    upper_first(strength),
+                    value_set
+                ),
+                None => "None".to_string(),
+            };
+            let types = match &element.content_reference {
+                Some(reference) => vec![format!("#{}", reference)],
+                None => element.types.clone(),
+            };
+            let _ = writeln!(
+                code,
+                "        crate::resources::FhirElementDefinition {{ path: {:?}, min: {}, max: {:?}, types: &{:?}, binding: {} }},",
+                element.path, element.min, element.max, types, binding
+            );
+        }
+        code.push_str("    ];\n}\n");
+        let _ = write!(
+            code,
+            "impl crate::datatypes::FhirExtensible for {name} {{\n    fn extensions(&self) -> &Vec<crate::datatypes::FhirExtension> {{\n        &self.extension\n    }}\n    fn extensions_mut(&mut self) -> &mut Vec<crate::datatypes::FhirExtension> {{\n        &mut self.extension\n    }}\n}}\n"
+        );
+        if self
+            .children(&self.resource_type)
+            .iter()
+            .any(|element| element.name() == "identifier" && element.max.is_none())
+        {
+            let _ = write!(
+                code,
+                "impl crate::fhir_trait::FhirIdentifiable for {name} {{\n    fn identifiers(&self) -> &[crate::fhir_trait::FhirIdentifier] {{\n        &self.identifier\n    }}\n}}\n"
+            );
+        }
+        code
+    }
+    // Every generated element, depth first, in definition order
+    fn all_fields(&self, path: &str) -> Vec<&Element> {
+        let mut fields = vec![];
+        for element in self.children(path) {
+            fields.push(element);
+            if element.is_backbone() {
+                fields.extend(self.all_fields(&element.path));
+            }
+        }
+        fields
+    }
+    fn generate_struct(&self, path: &str, items: &mut Vec<String>) {
+        let root = path == self.resource_type;
+        let name = struct_name(path);
+        let mut fields = String::new();
+        let mut nested = vec![];
+        let mut narrative_field = None;
+        let mut choices = vec![];
+        if root {
+            fields.push_str("    #[serde(default, skip_serializing_if = \"Vec::is_empty\", deserialize_with = \"crate::datatypes::deserialize_extensions\")]\n    pub extension: Vec<crate::datatypes::FhirExtension>,\n");
+        }
+        for element in self.children(path) {
+            let json_name = element.name().trim_end_matches("[x]");
+            let field = field_name(json_name, path);
+            if element.is_choice() {
+                choices.push(format!("{:?}", json_name));
+                let choice = format!("{}{}", name, upper_first(json_name));
+                let module = snake_case(&choice);
+                let mut variants = BTreeSet::new();
+                let variants: Vec<String> = element
+                    .types
+                    .iter()
+                    .filter(|fhir_type| variants.insert(fhir_type.as_str()))
+                    .map(|fhir_type| {
+                        format!("{}({})", upper_first(fhir_type), rust_type(fhir_type))
+                    })
+                    .collect();
+                items.push(format!(
+                    "crate::fhir_choice!(\n    #[derive(Debug, Clone, PartialEq)]\n    pub enum {} {{\n        {},\n    }}\n);\ncrate::fhir_choice_field!({}, {:?}, {});\n",
+                    choice,
+                    variants.join(",\n        "),
+                    module,
+                    json_name,
+                    choice
+                ));
+                let _ = writeln!(
+                    fields,
+                    "    #[serde(flatten, with = \"{}\")]\n    pub {}: Option<{}>,",
+                    module, field, choice
+                );
+                continue;
+            }
+            let item_type = match (&element.content_reference, element.is_backbone()) {
+                (Some(reference), _) => struct_name(reference),
+                (None, true) => {
+                    nested.push(element.path.clone());
+                    struct_name(&element.path)
+                }
+                (None, false) => match element.types.as_slice() {
+                    [fhir_type] => rust_type(fhir_type).to_string(),
+                    _ => "serde_json::Value".to_string(),
+                },
+            };
+            let mut serde = vec![];
+            if field != json_name {
+                serde.push(format!("rename = \"{}\"", json_name));
+            }
+            let field_type = match (element.min, element.max) {
+                (_, None) | (_, Some(2..)) => {
+                    serde.push("default".to_string());
+                    serde.push("skip_serializing_if = \"Vec::is_empty\"".to_string());
+                    format!("Vec<{}>", item_type)
+                }
+                (1, _) => item_type.clone(),
+                _ => {
+                    serde.push("skip_serializing_if = \"Option::is_none\"".to_string());
+                    format!("Option<{}>", item_type)
+                }
+            };
+            if root
+                && narrative_field.is_none()
+                && NARRATIVE_FIELDS.contains(&json_name)
+                && has_text_value(&item_type)
+            {
+                narrative_field = Some(field.clone());
+            }
+            if !serde.is_empty() {
+                let _ = writeln!(fields, "    #[serde({})]", serde.join(", "));
+            }
+            if narrative_field.as_ref() == Some(&field) {
+                fields.push_str("    #[fhir(narrative)]\n");
+            }
+            let _ = writeln!(fields, "    pub {}: {},", field, field_type);
+        }
+        // Members of newer versions of the resource, kept when re-signing
+        if root {
+            let flatten = match choices.is_empty() {
+                true => "flatten".to_string(),
+                false => {
+                    let module = snake_case(&format!("{}Unknown", name));
+                    items.push(format!(
+                        "crate::fhir_unknown_field!({}, [{}]);\n",
+                        module,
+                        choices.join(", ")
+                    ));
+                    format!("flatten, with = \"{}\"", module)
+                }
+            };
+            let _ = writeln!(
+                fields,
+                "    #[serde({})]\n    #[fhir(unknown)]\n    pub unknown: crate::datatypes::FhirUnknownElements,",
+                flatten
+            );
+        }
+        let mut item = String::new();
+        match root {
+            true => {
+                let constant = snake_case(&self.resource_type).to_uppercase();
+                let narrative = match &narrative_field {
+                    Some(_) => String::new(),
+                    None => ", narrative_with = default_narrative".to_string(),
+                };
+                let _ = write!(
+                    item,
+                    "pub const FHIR_RESOURCE_{}: &str = {:?};\npub const NOSTR_KIND_{}: u32 = {};\n",
+                    constant, self.resource_type, constant, self.kind
+                );
+                let _ = write!(
+                    item,
+                    "// {}\n#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, crate::fhir_trait::FhirResource)]\n#[fhir(resource_type = FHIR_RESOURCE_{}, kind = NOSTR_KIND_{}{}, validate_with = validate_structure, structure)]\npub struct {} {{\n{}}}\n",
+                    self.url, constant, constant, narrative, name, fields
+                );
+                let _ = write!(
+                    item,
+                    "impl {} {{\n    fn validate_structure(&self) -> crate::resources::operation_outcome::FhirOperationOutcome {{\n        crate::resources::validate_structure(self)\n    }}\n}}\n",
+                    name
+                );
+                if narrative_field.is_none() {
+                    let _ = write!(
+                        item,
+                        "impl {} {{\n    fn default_narrative(&self) -> String {{\n        {:?}.to_string()\n    }}\n}}\n",
+                        name, self.resource_type
+                    );
+                }
+            }
+            false => {
+                let _ = write!(
+                    item,
+                    "#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]\npub struct {} {{\n{}}}\n",
+                    name, fields
+                );
+            }
+        }
+        items.push(item);
+        for path in nested {
+            self.generate_struct(&path, items);
+        }
+    }
+}
+
+// https://www.hl7.org/fhir/datatypes.html
+// Datatypes without a Rust model are kept as raw JSON
+fn rust_type(fhir_type: &str) -> &'static str {
+    match fhir_type {
+        "boolean" => "bool",
+        "integer" | "integer64" => "i64",
+        "positiveInt" | "unsignedInt" => "u32",
+        "decimal" => "crate::datatypes::FhirDecimal",
+        "string" | "code" | "uri" | "url" | "canonical" | "id" | "markdown" | "oid" | "uuid"
+        | "base64Binary" | "time" | "xhtml" => "String",
+        "date" => "crate::datatypes::FhirDate",
+        "dateTime" => "crate::datatypes::FhirDateTime",
+        "instant" => "crate::datatypes::FhirInstant",
+        "Address" => "crate::datatypes::FhirAddress",
+        "CodeableConcept" => "crate::datatypes::FhirCodeableConcept",
+        "Coding" => "crate::datatypes::FhirCoding",
+        "ContactPoint" => "crate::datatypes::FhirContactPoint",
+        "Duration" => "crate::datatypes::FhirDuration",
+        "HumanName" => "crate::datatypes::FhirHumanName",
+        "Identifier" => "crate::fhir_trait::FhirIdentifier",
+        "Money" => "crate::datatypes::FhirMoney",
+        "Period" => "crate::datatypes::FhirPeriod",
+        "Quantity" | "SimpleQuantity" | "Age" | "Count" | "Distance" => {
+            "crate::datatypes::FhirQuantity"
+        }
+        "Range" => "crate::datatypes::FhirRange",
+        "Ratio" => "crate::datatypes::FhirRatio",
+        "Reference" => "crate::fhir_trait::FhirReference<serde_json::Value>",
+        _ => "serde_json::Value",
+    }
+}
+
+// Types implementing FhirTextValue, usable as the narrative
+fn has_text_value(rust_type: &str) -> bool {
+    matches!(
+        rust_type,
+        "String" | "crate::datatypes::FhirHumanName" | "crate::datatypes::FhirCodeableConcept"
+    )
+}
+
+// "Observation.referenceRange" -> "FhirObservationReferenceRange"
+fn struct_name(path: &str) -> String {
+    format!(
+        "Fhir{}",
+        path.split('.').map(upper_first).collect::<String>()
+    )
+}
+
+// Keywords are prefixed with their parent element, as in `link_type`
+fn field_name(json_name: &str, parent: &str) -> String {
+    let field = snake_case(json_name);
+    match RUST_KEYWORDS.contains(&field.as_str()) {
+        true => format!(
+            "{}_{}",
+            snake_case(parent.rsplit('.').next().unwrap_or(parent)),
+            field
+        ),
+        false => field,
+    }
+}
+
+fn upper_first(value: &str) -> String {
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn snake_case(value: &str) -> String {
+    let mut snake = String::new();
+    for (index, c) in value.chars().enumerate() {
+        if c.is_uppercase() {
+            if index > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
@@ -0,0 +1,257 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, Expr, Fields, Ident, LitStr, Type,
+};
+
+// Implements `FhirResource` and `FhirResourceDefinition` for a struct and
+// registers it for `FhirResourceRegistry::with_defaults`:
+//
+//     #[derive(Clone, Serialize, Deserialize, FhirResource)]
+//     #[fhir(resource_type = "Observation", kind = 2010)]
+//     pub struct Observation {
+//         #[fhir(narrative)]
+//         title: String,
+//         #[fhir(tag = "c")]
+//         category: Option<String>,
+//     }
+//
+// Container attributes:
+//   resource_type = <expr>   FHIR resource type name
+//   kind = <expr>            Nostr event kind
+//   narrative_with = <ident> method returning the display text, instead of fields
+//   text_with = <ident>      method taking an FhirLocalization and a language and
+//                            returning the XHTML narrative, as an FhirNarrative or
+//                            FhirText; without it the display text is the narrative
+//   validate_with = <ident>  method returning the resource's FhirOperationOutcome
+//   structure                the type implements FhirStructureDefinition
+//   register = <type>        the instance of a generic struct to register; generic
+//                            structs without it are not registered
+// Field attributes:
+//   narrative                field values joined into the display text
+//   tag = "<name>"           one event tag per field value
+//   unknown                  the #[serde(flatten)] FhirUnknownElements field
+//                            keeping members this version does not model
+#[proc_macro_derive(FhirResource, attributes(fhir))]
+pub fn derive_fhir_resource(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(mut input: DeriveInput) -> syn::Result<TokenStream2> {
+    let krate = quote!(::ignis_nostr);
+    let mut resource_type: Option<Expr> = None;
+    let mut kind: Option<Expr> = None;
+    let mut narrative_with: Option<Ident> = None;
+    let mut text_with: Option<Ident> = None;
+    let mut validate_with: Option<Ident> = None;
+    let mut structure = false;
+    let mut register: Option<Type> = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("fhir"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("resource_type") {
+                resource_type = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("kind") {
+                kind = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("narrative_with") {
+                narrative_with = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("text_with") {
+                text_with = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("validate_with") {
+                validate_with = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("structure") {
+                structure = true;
+            } else if meta.path.is_ident("register") {
+                register = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("unknown fhir attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    let name = input.ident.clone();
+    let resource_type = resource_type
+        .ok_or_else(|| Error::new_spanned(&name, "missing #[fhir(resource_type = ...)]"))?;
+    let kind = kind.ok_or_else(|| Error::new_spanned(&name, "missing #[fhir(kind = ...)]"))?;
+
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &name,
+            "FhirResource can only be derived for structs",
+        ));
+    };
+    let mut narrative: Vec<Ident> = vec![];
+    let mut tags: Vec<(LitStr, Ident)> = vec![];
+    let mut unknown: Option<Ident> = None;
+    if let Fields::Named(fields) = &data.fields {
+        for field in &fields.named {
+            let Some(ident) = &field.ident else {
+                continue;
+            };
+            for attr in field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("fhir"))
+            {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("narrative") {
+                        narrative.push(ident.clone());
+                    } else if meta.path.is_ident("tag") {
+                        tags.push((meta.value()?.parse()?, ident.clone()));
+                    } else if meta.path.is_ident("unknown") {
+                        unknown = Some(ident.clone());
+                    } else {
+                        return Err(meta.error("unknown fhir field attribute"));
+                    }
+                    Ok(())
+                })?;
+            }
+        }
+    }
+
+    let display = match (narrative_with, narrative.is_empty()) {
+        (Some(method), true) => quote!(self.#method()),
+        (None, false) => quote! {
+            let parts: Vec<Vec<String>> = vec![
+                #(#krate::fhir_trait::FhirTextValue::text_values(&self.#narrative)),*
+            ];
+            parts.concat().join(" ")
+        },
+        (Some(method), false) => {
+            return Err(Error::new_spanned(
+                method,
+                "use either narrative_with or #[fhir(narrative)] fields, not both",
+            ))
+        }
+        (None, true) => {
+            return Err(Error::new_spanned(
+                &name,
+                "mark a field with #[fhir(narrative)] or set #[fhir(narrative_with = method)]",
+            ))
+        }
+    };
+    let tags = match tags.is_empty() {
+        true => quote!(),
+        false => {
+            let (tag_names, tag_fields): (Vec<_>, Vec<_>) = tags.into_iter().unzip();
+            quote! {
+                fn tags(&self) -> Vec<Vec<String>> {
+                    let mut tags = vec![];
+                    #(
+                        for value in #krate::fhir_trait::FhirTextValue::text_values(&self.#tag_fields) {
+                            tags.push(vec![#tag_names.to_string(), value]);
+                        }
+                    )*
+                    tags
+                }
+            }
+        }
+    };
+
+    let text = match text_with {
+        Some(method) => quote! {
+            fn localized_text(
+                &self,
+                localization: &#krate::locale::FhirLocalization,
+                language: &str,
+            ) -> #krate::fhir_trait::FhirText {
+                self.#method(localization, language).into()
+            }
+        },
+        None => quote!(),
+    };
+    let unknown = match unknown {
+        Some(field) => quote! {
+            fn unknown_elements(&self) -> Option<&#krate::datatypes::FhirUnknownElements> {
+                Some(&self.#field)
+            }
+        },
+        None => quote!(),
+    };
+    let validate = match validate_with {
+        Some(method) => quote! {
+            fn validate(&self) -> #krate::resources::operation_outcome::FhirOperationOutcome {
+                self.#method()
+            }
+        },
+        None => quote!(),
+    };
+    let structure = match structure {
+        true => quote! {
+            fn structure() -> &'static [#krate::resources::FhirElementDefinition] {
+                <Self as #krate::resources::FhirStructureDefinition>::ELEMENTS
+            }
+            fn fhir_version() -> &'static str {
+                <Self as #krate::resources::FhirStructureDefinition>::FHIR_VERSION
+            }
+        },
+        false => quote!(),
+    };
+
+    let register = match (register, input.generics.params.is_empty()) {
+        (Some(register), false) => Some(register),
+        (None, true) => Some(parse_quote!(#name)),
+        (None, false) => None,
+        (Some(register), true) => {
+            return Err(Error::new_spanned(
+                register,
+                "register is only needed on generic structs",
+            ))
+        }
+    };
+    let registration = register.map(|register| {
+        quote! {
+            #krate::inventory::submit! {
+                #krate::resources::FhirResourceRegistration::new(
+                    #krate::resources::FhirResourceDescriptor::of::<#register>
+                )
+            }
+        }
+    });
+
+    // Type parameters end up inside the serialized resource, so they need the
+    // same bounds as the resource itself
+    let type_params: Vec<Ident> = input
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let where_clause = input.generics.make_where_clause();
+    for param in type_params {
+        where_clause.predicates.push(parse_quote!(
+            #param: ::serde::Serialize + ::serde::de::DeserializeOwned + Clone
+        ));
+    }
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #krate::resources::FhirResourceDefinition for #name #type_generics #where_clause {
+            const RESOURCE_TYPE: &'static str = #resource_type;
+            const NOSTR_KIND: u32 = #kind;
+        }
+        impl #impl_generics #krate::fhir_trait::FhirResource for #name #type_generics #where_clause {
+            fn nostr_kind(&self) -> u32 {
+                <Self as #krate::resources::FhirResourceDefinition>::NOSTR_KIND
+            }
+            fn resource_type(&self) -> &str {
+                <Self as #krate::resources::FhirResourceDefinition>::RESOURCE_TYPE
+            }
+            fn display(&self) -> String {
+                #display
+            }
+            #text
+            #tags
+            #validate
+            #structure
+            #unknown
+        }
+        #registration
+    })
+}
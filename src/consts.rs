@@ -2,10 +2,51 @@
 pub const FHIR_RESOURCE_ORGANIZATION: &str = "Organization";
 pub const FHIR_RESOURCE_PRACTITIONER: &str = "Practitioner";
 pub const FHIR_RESOURCE_APPOINTMENT: &str = "Appointment";
-pub const FHIR_RESOURCE_APPOINTMENT_RESPONSE: &str = "Appointment";
+// The 0.1 release gave AppointmentResponse the Appointment type, so its FHIR
+// exports and references to it named the wrong resource. Notes are told apart
+// by kind and were not affected.
+pub const FHIR_RESOURCE_APPOINTMENT_RESPONSE: &str = "AppointmentResponse";
 
 // Nostr mapped kinds
 pub const NOSTR_KIND_ORGANIZATION: u32 = 2000;
 pub const NOSTR_KIND_PRACTITIONER: u32 = 2001;
 pub const NOSTR_KIND_APPOINTMENT: u32 = 2002;
 pub const NOSTR_KIND_APPOINTMENT_RESPONSE: u32 = 2003;
+
+// Code systems
+pub const SNOMED_CT_SYSTEM: &str = "http://snomed.info/sct";
+// Local codes published by this crate, for concepts that have no SNOMED CT code
+pub const IGNIS_CODESYSTEM_SPECIALTY: &str =
+    "https://github.com/illuminodes/ignis-nostr/fhir/CodeSystem/specialty";
+pub const IGNIS_CONCEPTMAP_SPECIALTY_TO_SNOMED: &str =
+    "https://github.com/illuminodes/ignis-nostr/fhir/ConceptMap/specialty-to-snomed";
+
+// Identifier systems
+pub const FHIR_IDENTIFIER_US_NPI: &str = "http://hl7.org/fhir/sid/us-npi";
+pub const FHIR_IDENTIFIER_URI: &str = "urn:ietf:rfc:3986";
+pub const FHIR_IDENTIFIER_EMAIL: &str = "urn:ietf:rfc:5322";
+// National ID schemes without a published FHIR system URI
+pub const IGNIS_IDENTIFIER_VE_CEDULA: &str =
+    "https://github.com/illuminodes/ignis-nostr/fhir/sid/ve-cedula";
+pub const IGNIS_IDENTIFIER_VE_RIF: &str =
+    "https://github.com/illuminodes/ignis-nostr/fhir/sid/ve-rif";
+pub const IGNIS_IDENTIFIER_CO_CC: &str =
+    "https://github.com/illuminodes/ignis-nostr/fhir/sid/co-cc";
+pub const IGNIS_IDENTIFIER_MX_CURP: &str =
+    "https://github.com/illuminodes/ignis-nostr/fhir/sid/mx-curp";
+pub const IGNIS_IDENTIFIER_MX_RFC: &str =
+    "https://github.com/illuminodes/ignis-nostr/fhir/sid/mx-rfc";
+
+// Extensions
+// Wraps extension content written before extensions were typed
+pub const IGNIS_EXTENSION_LEGACY: &str =
+    "https://github.com/illuminodes/ignis-nostr/fhir/StructureDefinition/legacy-extension";
+
+// Note payloads
+// Version of the note content format, raised whenever it changes in a way
+// older clients cannot read. Older content is upgraded by the migrations in
+// `resources::IgnisPayload`.
+pub const IGNIS_SCHEMA_VERSION: u32 = 3;
+// Member carrying the schema version of schema 1 content; content written
+// before it existed is schema 0
+pub const IGNIS_SCHEMA_VERSION_ELEMENT: &str = "ignisSchemaVersion";
@@ -0,0 +1,418 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    validate_country, FhirAddress, FhirDecimal, FhirExtensible, FhirExtension, FhirPeriod,
+    FhirTypedExtension, FhirValue,
+};
+use crate::valuesets::{FhirAddressType, FhirAddressUse};
+
+// https://www.hl7.org/fhir/extensions/StructureDefinition-geolocation.html
+pub const FHIR_EXTENSION_GEOLOCATION: &str = "http://hl7.org/fhir/StructureDefinition/geolocation";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirGeolocation {
+    pub latitude: FhirDecimal,
+    pub longitude: FhirDecimal,
+}
+impl FhirGeolocation {
+    pub fn new(latitude: f64, longitude: f64) -> Result<Self, String> {
+        if !(-90.0..=90.0).contains(&latitude) {
+            return Err(format!("Latitude out of range: {}", latitude));
+        }
+        if !(-180.0..=180.0).contains(&longitude) {
+            return Err(format!("Longitude out of range: {}", longitude));
+        }
+        Ok(Self {
+            latitude: FhirDecimal::new(latitude)?,
+            longitude: FhirDecimal::new(longitude)?,
+        })
+    }
+}
+impl FhirTypedExtension for FhirGeolocation {
+    const URL: &'static str = FHIR_EXTENSION_GEOLOCATION;
+    fn to_extension(&self) -> FhirExtension {
+        FhirExtension::new_complex(
+            Self::URL,
+            vec![
+                FhirExtension::new_value("latitude", FhirValue::Decimal(self.latitude)),
+                FhirExtension::new_value("longitude", FhirValue::Decimal(self.longitude)),
+            ],
+        )
+    }
+    fn from_extension(extension: &FhirExtension) -> Result<Self, String> {
+        let part = |url: &str| match extension
+            .get_extension(url)
+            .and_then(|part| part.get_value())
+        {
+            Some(FhirValue::Decimal(value)) => Ok(value.value()),
+            _ => Err(format!("Geolocation is missing a decimal {}", url)),
+        };
+        Self::new(part("latitude")?, part("longitude")?)
+    }
+}
+
+// How the locality line of a postal label is laid out
+#[derive(Clone, Copy)]
+enum LocalityLayout {
+    // "Springfield, IL 62704"
+    CityStatePostal,
+    // "06600 Ciudad de México, CDMX"
+    PostalCityState,
+    // "Caracas 1010, Distrito Capital"
+    CityPostalState,
+    // "28013 Madrid" followed by the province
+    PostalCityThenState,
+    // "São Paulo - SP" followed by the postal code
+    CityStateThenPostal,
+}
+
+struct CountryRules {
+    code: &'static str,
+    english_name: &'static str,
+    local_name: &'static str,
+    // Lowercase names recognised at the end of a free text address
+    aliases: &'static [&'static str],
+    layout: LocalityLayout,
+    postal_code: fn(&str) -> bool,
+}
+
+fn digits(value: &str, count: usize) -> bool {
+    value.len() == count && value.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+// Countries with postal label formatting and free text parsing support
+const COUNTRY_RULES: [CountryRules; 6] = [
+    CountryRules {
+        code: "VE",
+        english_name: "Venezuela",
+        local_name: "Venezuela",
+        aliases: &["venezuela", "ven"],
+        layout: LocalityLayout::CityPostalState,
+        postal_code: |code| digits(code, 4),
+    },
+    CountryRules {
+        code: "CO",
+        english_name: "Colombia",
+        local_name: "Colombia",
+        aliases: &["colombia", "col"],
+        layout: LocalityLayout::CityStatePostal,
+        postal_code: |code| digits(code, 6),
+    },
+    CountryRules {
+        code: "MX",
+        english_name: "Mexico",
+        local_name: "México",
+        aliases: &["mexico", "mex"],
+        layout: LocalityLayout::PostalCityState,
+        postal_code: |code| digits(code, 5),
+    },
+    CountryRules {
+        code: "US",
+        english_name: "United States",
+        local_name: "Estados Unidos",
+        aliases: &["united states", "usa", "estados unidos", "eeuu", "ee.uu."],
+        layout: LocalityLayout::CityStatePostal,
+        postal_code: |code| match code.split_once('-') {
+            Some((zip, plus_four)) => digits(zip, 5) && digits(plus_four, 4),
+            None => digits(code, 5),
+        },
+    },
+    CountryRules {
+        code: "ES",
+        english_name: "Spain",
+        local_name: "España",
+        aliases: &["spain", "espana", "esp"],
+        layout: LocalityLayout::PostalCityThenState,
+        postal_code: |code| digits(code, 5),
+    },
+    CountryRules {
+        code: "BR",
+        english_name: "Brazil",
+        local_name: "Brasil",
+        aliases: &["brazil", "brasil", "bra"],
+        layout: LocalityLayout::CityStateThenPostal,
+        postal_code: |code| match code.split_once('-') {
+            Some((prefix, suffix)) => digits(prefix, 5) && digits(suffix, 3),
+            None => digits(code, 8),
+        },
+    },
+];
+
+fn country_rules(code: &str) -> Option<&'static CountryRules> {
+    COUNTRY_RULES.iter().find(|rules| rules.code == code)
+}
+
+fn fold(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' => 'a',
+            'é' => 'e',
+            'í' => 'i',
+            'ó' => 'o',
+            'ú' | 'ü' => 'u',
+            'ñ' => 'n',
+            'ã' => 'a',
+            'ç' => 'c',
+            other => other,
+        })
+        .collect()
+}
+
+fn join_present(parts: &[Option<&str>], separator: &str) -> String {
+    parts
+        .iter()
+        .flatten()
+        .map(|part| part.trim())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+impl FhirAddress {
+    pub fn new(address_use: FhirAddressUse, address_type: FhirAddressType) -> Self {
+        Self {
+            address_use: Some(address_use),
+            address_type: Some(address_type),
+            text: String::new(),
+            line: vec![],
+            city: None,
+            district: None,
+            state: None,
+            postal_code: None,
+            country: None,
+            period: None,
+            extension: vec![],
+        }
+    }
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.text = text.to_string();
+        self
+    }
+    pub fn with_line(mut self, line: &str) -> Self {
+        self.line.push(line.to_string());
+        self
+    }
+    pub fn with_city(mut self, city: &str) -> Self {
+        self.city = Some(city.to_string());
+        self
+    }
+    pub fn with_district(mut self, district: &str) -> Self {
+        self.district = Some(district.to_string());
+        self
+    }
+    pub fn with_state(mut self, state: &str) -> Self {
+        self.state = Some(state.to_string());
+        self
+    }
+    pub fn with_postal_code(mut self, postal_code: &str) -> Self {
+        self.postal_code = Some(postal_code.to_string());
+        self
+    }
+    // Stored as the ISO 3166 alpha-2 code
+    pub fn with_country(mut self, country: &str) -> Result<Self, String> {
+        self.country = Some(validate_country(country)?.to_string());
+        Ok(self)
+    }
+    pub fn with_period(mut self, period: FhirPeriod) -> Self {
+        self.period = Some(period);
+        self
+    }
+    pub fn with_geolocation(mut self, geolocation: FhirGeolocation) -> Self {
+        self.set_geolocation(geolocation);
+        self
+    }
+    // Fills `text` from the parts, for addresses assembled with the builders
+    pub fn with_generated_text(mut self, language: &str) -> Self {
+        self.text = self.format_label(language).replace('\n', ", ");
+        self
+    }
+    pub fn get_geolocation(&self) -> Option<FhirGeolocation> {
+        self.get_typed_extension().ok().flatten()
+    }
+    pub fn set_geolocation(&mut self, geolocation: FhirGeolocation) {
+        self.set_typed_extension(&geolocation);
+    }
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(country) = &self.country {
+            let country = validate_country(country)?;
+            if let (Some(rules), Some(postal_code)) = (country_rules(country), &self.postal_code) {
+                if !(rules.postal_code)(postal_code.trim()) {
+                    return Err(format!(
+                        "Invalid postal code for {}: {}",
+                        rules.english_name, postal_code
+                    ));
+                }
+            }
+        }
+        if let Some(period) = &self.period {
+            period.validate()?;
+        }
+        Ok(())
+    }
+    pub fn country_name(&self, language: &str) -> Option<String> {
+        let country = self.country.as_deref()?;
+        let rules = validate_country(country).ok().and_then(country_rules);
+        Some(
+            match (rules, language.split('-').next().unwrap_or_default()) {
+                (Some(rules), "es" | "pt") => rules.local_name.to_string(),
+                (Some(rules), _) => rules.english_name.to_string(),
+                (None, _) => country.to_string(),
+            },
+        )
+    }
+    // Multi-line postal label laid out the way the destination country expects
+    pub fn format_label(&self, language: &str) -> String {
+        let city = self.city.as_deref();
+        let state = self.state.as_deref();
+        let postal_code = self.postal_code.as_deref();
+        let layout = self
+            .country
+            .as_deref()
+            .and_then(|country| validate_country(country).ok())
+            .and_then(country_rules)
+            .map(|rules| rules.layout)
+            .unwrap_or(LocalityLayout::CityStatePostal);
+        let locality = match layout {
+            LocalityLayout::CityStatePostal => vec![join_present(
+                &[Some(&join_present(&[city, state], ", ")), postal_code],
+                " ",
+            )],
+            LocalityLayout::PostalCityState => vec![join_present(
+                &[Some(&join_present(&[postal_code, city], " ")), state],
+                ", ",
+            )],
+            LocalityLayout::CityPostalState => vec![join_present(
+                &[Some(&join_present(&[city, postal_code], " ")), state],
+                ", ",
+            )],
+            LocalityLayout::PostalCityThenState => vec![
+                join_present(&[postal_code, city], " "),
+                match state == city {
+                    true => String::new(),
+                    false => join_present(&[state], ""),
+                },
+            ],
+            LocalityLayout::CityStateThenPostal => vec![
+                join_present(&[city, state], " - "),
+                join_present(&[postal_code], ""),
+            ],
+        };
+        let label: Vec<String> = self
+            .line
+            .iter()
+            .cloned()
+            .chain(self.district.clone())
+            .chain(locality)
+            .chain(self.country_name(language))
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        match label.is_empty() {
+            true => self.text.clone(),
+            false => label.join("\n"),
+        }
+    }
+    // Single line form, preferring the text the address was entered with
+    pub fn display(&self, language: &str) -> String {
+        match self.text.trim().is_empty() {
+            true => self.format_label(language).replace('\n', ", "),
+            false => self.text.clone(),
+        }
+    }
+    // Splits a free text address on commas and line breaks into its parts. A
+    // trailing country name overrides `default_country`; the postal code is
+    // found by the country's format, and the remaining trailing segments are
+    // read as state and city. Parsed addresses are work addresses of type both.
+    pub fn parse(text: &str, default_country: &str) -> Result<Self, String> {
+        let mut segments: Vec<String> = text
+            .split(['\n', ','])
+            .map(|segment| segment.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        if segments.is_empty() {
+            return Err("Address text is empty".to_string());
+        }
+        let mut country = validate_country(default_country)?;
+        if let Some(last) = segments.last() {
+            let folded = fold(last);
+            if let Some(rules) = COUNTRY_RULES
+                .iter()
+                .find(|rules| rules.aliases.contains(&folded.as_str()))
+            {
+                country = rules.code;
+                segments.pop();
+            }
+        }
+        let rules = country_rules(country)
+            .ok_or(format!("Address parsing is not supported for {}", country))?;
+        let mut address =
+            Self::new(FhirAddressUse::Work, FhirAddressType::Both).with_text(&segments.join(", "));
+        address.country = Some(country.to_string());
+
+        'search: for index in (0..segments.len()).rev() {
+            let words: Vec<&str> = segments[index].split(' ').collect();
+            for (position, word) in words.iter().enumerate() {
+                let candidate = word.trim_start_matches("C.P.").trim_start_matches("CP");
+                if (rules.postal_code)(candidate) && (index > 0 || words.len() > 1) {
+                    address.postal_code = Some(candidate.to_string());
+                    let rest: Vec<&str> = words
+                        .iter()
+                        .enumerate()
+                        .filter(|(other, _)| *other != position)
+                        .map(|(_, word)| *word)
+                        .filter(|word| !matches!(*word, "CP" | "C.P."))
+                        .collect();
+                    match rest.is_empty() {
+                        true => {
+                            segments.remove(index);
+                        }
+                        false => segments[index] = rest.join(" "),
+                    }
+                    break 'search;
+                }
+            }
+        }
+
+        // "Springfield IL" and "São Paulo - SP" keep the state in the city segment
+        if let Some(last) = segments.pop() {
+            let us_state = last.rsplit_once(' ').filter(|(_, state)| {
+                rules.code == "US"
+                    && state.len() == 2
+                    && state.chars().all(|c| c.is_ascii_uppercase())
+            });
+            match us_state.or(last.split_once(" - ")) {
+                Some((city, state)) => {
+                    segments.push(city.to_string());
+                    segments.push(state.to_string());
+                }
+                None => segments.push(last),
+            }
+        }
+        if segments.len() >= 3 {
+            address.state = segments.pop();
+        }
+        if segments.len() >= 2 {
+            address.city = segments.pop();
+        }
+        if let Some(index) = segments.iter().position(|segment| {
+            let folded = fold(segment);
+            ["col.", "colonia ", "urb.", "urbanizacion "]
+                .iter()
+                .any(|marker| folded.starts_with(marker))
+        }) {
+            address.district = Some(segments.remove(index));
+        }
+        address.line = segments;
+        Ok(address)
+    }
+}
+
+impl FhirExtensible for FhirAddress {
+    fn extensions(&self) -> &Vec<FhirExtension> {
+        &self.extension
+    }
+    fn extensions_mut(&mut self) -> &mut Vec<FhirExtension> {
+        &mut self.extension
+    }
+}
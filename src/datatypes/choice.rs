@@ -0,0 +1,194 @@
+use serde_json::{Map, Value};
+
+use super::{
+    FhirAddress, FhirCodeableConcept, FhirCoding, FhirContactPoint, FhirDate, FhirDateTime,
+    FhirDecimal, FhirDuration, FhirHumanName, FhirInstant, FhirMoney, FhirPeriod, FhirQuantity,
+    FhirRange, FhirRatio,
+};
+use crate::fhir_trait::FhirIdentifier;
+
+// https://www.hl7.org/fhir/formats.html#choice
+// A choice element such as value[x] is written under one key made of the
+// element name and the type name: "valueString", "effectivePeriod".
+pub trait FhirChoice: Sized {
+    // FHIR type name used as the key suffix, e.g. "CodeableConcept"
+    fn type_name(&self) -> &str;
+    fn to_json(&self) -> Result<Value, String>;
+    // None if the type is not one of the allowed choices
+    fn from_json(type_name: &str, value: Value) -> Option<Result<Self, String>>;
+}
+
+// Writes the choice under its suffixed key
+pub fn choice_to_map<C: FhirChoice>(
+    element: &str,
+    choice: Option<&C>,
+) -> Result<Map<String, Value>, String> {
+    let mut map = Map::new();
+    if let Some(choice) = choice {
+        map.insert(
+            format!("{}{}", element, choice.type_name()),
+            choice.to_json()?,
+        );
+    }
+    Ok(map)
+}
+
+// Picks the choice out of an object's entries. Keys for other elements are
+// ignored; a document with more than one `element[x]` key is rejected.
+pub fn choice_from_map<C: FhirChoice>(
+    element: &str,
+    map: &Map<String, Value>,
+) -> Result<Option<C>, String> {
+    let mut found: Option<(&str, C)> = None;
+    for (key, value) in map {
+        let Some(type_name) = key.strip_prefix(element) else {
+            continue;
+        };
+        if !type_name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            continue;
+        }
+        let choice = C::from_json(type_name, value.clone())
+            .ok_or(format!(
+                "{} is not an allowed type for {}[x]",
+                type_name, element
+            ))?
+            .map_err(|e| format!("Invalid {}: {}", key, e))?;
+        if let Some((previous, _)) = &found {
+            return Err(format!(
+                "{}[x] has more than one value: {} and {}",
+                element, previous, key
+            ));
+        }
+        found = Some((key, choice));
+    }
+    Ok(found.map(|(_, choice)| choice))
+}
+
+// Declares an enum of the types allowed for a choice element, one variant per
+// FHIR type name. An optional `other` variant keeps values of any other type
+// as raw JSON instead of rejecting them.
+//
+//     fhir_choice!(
+//         #[derive(Debug, Clone, PartialEq)]
+//         pub enum FhirOnset { DateTime(FhirDateTime), Period(FhirPeriod), String(String) }
+//     );
+#[macro_export]
+macro_rules! fhir_choice {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($variant:ident($ty:ty)),* $(,)?
+        }
+        $(other $other:ident)?
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($variant($ty),)*
+            $($other(String, serde_json::Value),)?
+        }
+        impl $crate::datatypes::FhirChoice for $name {
+            fn type_name(&self) -> &str {
+                match self {
+                    $(Self::$variant(_) => stringify!($variant),)*
+                    $(Self::$other(type_name, _) => type_name,)?
+                }
+            }
+            fn to_json(&self) -> Result<serde_json::Value, String> {
+                match self {
+                    $(Self::$variant(value) => {
+                        serde_json::to_value(value).map_err(|e| e.to_string())
+                    })*
+                    $(Self::$other(_, value) => Ok(value.clone()),)?
+                }
+            }
+            #[allow(unreachable_code)]
+            fn from_json(
+                type_name: &str,
+                value: serde_json::Value,
+            ) -> Option<Result<Self, String>> {
+                match type_name {
+                    $(stringify!($variant) => Some(
+                        serde_json::from_value(value)
+                            .map(Self::$variant)
+                            .map_err(|e| e.to_string()),
+                    ),)*
+                    _ => {
+                        $(return Some(Ok(Self::$other(type_name.to_string(), value)));)?
+                        None
+                    }
+                }
+            }
+        }
+    };
+}
+
+// Generates a serde `with` module for an optional choice element, used with
+// `#[serde(flatten, with = "module")]` on an `Option<Choice>` field:
+//
+//     fhir_choice_field!(onset_x, "onset", FhirOnset);
+#[macro_export]
+macro_rules! fhir_choice_field {
+    ($module:ident, $element:expr, $choice:ty) => {
+        pub mod $module {
+            #[allow(unused_imports)]
+            use super::*;
+            pub fn serialize<S: serde::Serializer>(
+                choice: &Option<$choice>,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                use serde::Serialize;
+                $crate::datatypes::choice_to_map($element, choice.as_ref())
+                    .map_err(serde::ser::Error::custom)?
+                    .serialize(serializer)
+            }
+            pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Option<$choice>, D::Error> {
+                use serde::Deserialize;
+                let map = serde_json::Map::<String, serde_json::Value>::deserialize(deserializer)?;
+                $crate::datatypes::choice_from_map($element, &map).map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+crate::fhir_choice!(
+    // https://www.hl7.org/fhir/extensibility.html#extension
+    // The types allowed for Extension.value[x]; values of types not listed are
+    // carried as raw JSON under their type name.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum FhirValue {
+        Base64Binary(String),
+        Boolean(bool),
+        Canonical(String),
+        Code(String),
+        Date(FhirDate),
+        DateTime(FhirDateTime),
+        Decimal(FhirDecimal),
+        Id(String),
+        Instant(FhirInstant),
+        Integer(i64),
+        Markdown(String),
+        Oid(String),
+        PositiveInt(u32),
+        String(String),
+        Time(String),
+        UnsignedInt(u32),
+        Uri(String),
+        Url(String),
+        Uuid(String),
+        Address(FhirAddress),
+        CodeableConcept(FhirCodeableConcept),
+        Coding(FhirCoding),
+        ContactPoint(FhirContactPoint),
+        Duration(FhirDuration),
+        HumanName(FhirHumanName),
+        Identifier(FhirIdentifier),
+        Money(FhirMoney),
+        Period(FhirPeriod),
+        Quantity(FhirQuantity),
+        Range(FhirRange),
+        Ratio(FhirRatio),
+    }
+    other Other
+);
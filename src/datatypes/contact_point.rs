@@ -0,0 +1,251 @@
+use super::{FhirContactPoint, FhirPeriod};
+use crate::{
+    identifiers::{validate_email, validate_uri},
+    valuesets::{FhirContactPointSystem, FhirContactPointUse},
+};
+
+// National numbering rules for the regions we normalize phone numbers for
+struct DialingPlan {
+    region: &'static str,
+    calling_code: &'static str,
+    trunk_prefix: Option<&'static str>,
+    national_lengths: &'static [usize],
+    is_mobile: fn(&str) -> bool,
+}
+
+// https://www.itu.int/rec/T-REC-E.164
+// US is listed after CA so +1 numbers are reported as US
+const DIALING_PLANS: [DialingPlan; 12] = [
+    DialingPlan {
+        region: "VE",
+        calling_code: "58",
+        trunk_prefix: Some("0"),
+        national_lengths: &[10],
+        is_mobile: |number| {
+            ["412", "414", "416", "422", "424", "426"]
+                .iter()
+                .any(|prefix| number.starts_with(prefix))
+        },
+    },
+    DialingPlan {
+        region: "CO",
+        calling_code: "57",
+        trunk_prefix: None,
+        national_lengths: &[10],
+        is_mobile: |number| number.starts_with('3'),
+    },
+    DialingPlan {
+        region: "MX",
+        calling_code: "52",
+        trunk_prefix: None,
+        national_lengths: &[10],
+        is_mobile: |_| false,
+    },
+    DialingPlan {
+        region: "CA",
+        calling_code: "1",
+        trunk_prefix: Some("1"),
+        national_lengths: &[10],
+        is_mobile: |_| false,
+    },
+    DialingPlan {
+        region: "US",
+        calling_code: "1",
+        trunk_prefix: Some("1"),
+        national_lengths: &[10],
+        is_mobile: |_| false,
+    },
+    DialingPlan {
+        region: "ES",
+        calling_code: "34",
+        trunk_prefix: None,
+        national_lengths: &[9],
+        is_mobile: |number| number.starts_with(['6', '7']),
+    },
+    DialingPlan {
+        region: "BR",
+        calling_code: "55",
+        trunk_prefix: Some("0"),
+        national_lengths: &[10, 11],
+        is_mobile: |number| number.len() == 11 && number[2..].starts_with('9'),
+    },
+    DialingPlan {
+        region: "AR",
+        calling_code: "54",
+        trunk_prefix: Some("0"),
+        national_lengths: &[10, 11],
+        is_mobile: |number| number.starts_with('9'),
+    },
+    DialingPlan {
+        region: "CL",
+        calling_code: "56",
+        trunk_prefix: None,
+        national_lengths: &[9],
+        is_mobile: |number| number.starts_with('9'),
+    },
+    DialingPlan {
+        region: "PE",
+        calling_code: "51",
+        trunk_prefix: Some("0"),
+        national_lengths: &[8, 9],
+        is_mobile: |number| number.len() == 9 && number.starts_with('9'),
+    },
+    DialingPlan {
+        region: "EC",
+        calling_code: "593",
+        trunk_prefix: Some("0"),
+        national_lengths: &[8, 9],
+        is_mobile: |number| number.len() == 9 && number.starts_with('9'),
+    },
+    DialingPlan {
+        region: "PA",
+        calling_code: "507",
+        trunk_prefix: None,
+        national_lengths: &[7, 8],
+        is_mobile: |number| number.len() == 8 && number.starts_with('6'),
+    },
+];
+
+// Returns the E.164 form ("+584125551234") and whether it is a mobile number.
+// Numbers without an international prefix are read as national numbers of
+// `default_region`.
+pub fn normalize_phone(value: &str, default_region: &str) -> Result<(String, bool), String> {
+    let trimmed = value.trim();
+    if !trimmed
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '+' | ' ' | '-' | '.' | '(' | ')'))
+        || trimmed.rfind('+').is_some_and(|position| position > 0)
+    {
+        return Err(format!("Invalid phone number: {}", value));
+    }
+    let digits: String = trimmed.chars().filter(char::is_ascii_digit).collect();
+    let international = match (trimmed.starts_with('+'), digits.strip_prefix("00")) {
+        (true, _) => Some(digits.clone()),
+        (false, Some(rest)) => Some(rest.to_string()),
+        (false, None) => None,
+    };
+    let (plan, national) = match international {
+        Some(number) => {
+            let plan = DIALING_PLANS
+                .iter()
+                .filter(|plan| number.starts_with(plan.calling_code))
+                .max_by_key(|plan| plan.calling_code.len());
+            match plan {
+                Some(plan) => (plan, number[plan.calling_code.len()..].to_string()),
+                None if (8..=15).contains(&number.len()) => {
+                    return Ok((format!("+{}", number), false));
+                }
+                None => return Err(format!("Invalid phone number length: {}", value)),
+            }
+        }
+        None => {
+            let region = default_region.trim().to_uppercase();
+            let plan = DIALING_PLANS
+                .iter()
+                .find(|plan| plan.region == region)
+                .ok_or(format!("No dialing plan for region {}", default_region))?;
+            let national = match plan.trunk_prefix {
+                Some(trunk)
+                    if digits.starts_with(trunk)
+                        && !plan.national_lengths.contains(&digits.len()) =>
+                {
+                    digits[trunk.len()..].to_string()
+                }
+                _ => digits,
+            };
+            (plan, national)
+        }
+    };
+    if !plan.national_lengths.contains(&national.len()) {
+        return Err(format!(
+            "Invalid phone number for region {}: {}",
+            plan.region, value
+        ));
+    }
+    Ok((
+        format!("+{}{}", plan.calling_code, national),
+        (plan.is_mobile)(&national),
+    ))
+}
+
+impl FhirContactPoint {
+    // Validated email address, a work contact unless changed with `with_use`
+    pub fn email(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        validate_email(value)?;
+        let (local, domain) = value.rsplit_once('@').unwrap_or_default();
+        Ok(Self {
+            system: Some(FhirContactPointSystem::Email),
+            value: format!("{}@{}", local, domain.to_lowercase()),
+            contact_use: Some(FhirContactPointUse::Work),
+            period: None,
+            rank: None,
+        })
+    }
+    // Phone number stored in E.164 form; mobile numbers are detected by prefix
+    // where the national numbering plan allows it
+    pub fn phone(value: &str, default_region: &str) -> Result<Self, String> {
+        let (number, mobile) = normalize_phone(value, default_region)?;
+        Ok(Self {
+            system: Some(FhirContactPointSystem::Phone),
+            value: number,
+            contact_use: Some(match mobile {
+                true => FhirContactPointUse::Mobile,
+                false => FhirContactPointUse::Work,
+            }),
+            period: None,
+            rank: None,
+        })
+    }
+    pub fn url(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        validate_uri(value)?;
+        let scheme = value.split(':').next().unwrap_or_default().to_lowercase();
+        if scheme != "http" && scheme != "https" {
+            return Err(format!("URL must use http or https: {}", value));
+        }
+        Ok(Self {
+            system: Some(FhirContactPointSystem::Url),
+            value: value.to_string(),
+            contact_use: Some(FhirContactPointUse::Work),
+            period: None,
+            rank: None,
+        })
+    }
+    pub fn with_use(mut self, contact_use: FhirContactPointUse) -> Self {
+        self.contact_use = Some(contact_use);
+        self
+    }
+    // 1 is the most preferred
+    pub fn with_rank(mut self, rank: u32) -> Self {
+        self.rank = Some(rank);
+        self
+    }
+    pub fn with_period(mut self, period: FhirPeriod) -> Self {
+        self.period = Some(period);
+        self
+    }
+    pub fn is_active(&self) -> bool {
+        self.contact_use != Some(FhirContactPointUse::Old)
+            && !self.period.as_ref().is_some_and(FhirPeriod::is_expired)
+    }
+    // Active contact points of a system, most preferred first. Unranked
+    // contact points come after ranked ones and keep their order.
+    pub fn ranked(
+        contacts: &[FhirContactPoint],
+        system: FhirContactPointSystem,
+    ) -> Vec<&FhirContactPoint> {
+        let mut ranked: Vec<&FhirContactPoint> = contacts
+            .iter()
+            .filter(|contact| contact.system.as_ref() == Some(&system) && contact.is_active())
+            .collect();
+        ranked.sort_by_key(|contact| contact.rank.unwrap_or(u32::MAX));
+        ranked
+    }
+    pub fn preferred(
+        contacts: &[FhirContactPoint],
+        system: FhirContactPointSystem,
+    ) -> Option<&FhirContactPoint> {
+        Self::ranked(contacts, system).into_iter().next()
+    }
+}
@@ -0,0 +1,349 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Add, Sub},
+    str::FromStr,
+};
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, SecondsFormat, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// Precision of a date or dateTime. Partial dates stand for the whole year,
+// month or day; comparisons use the start of that period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FhirDateTimePrecision {
+    Year,
+    Month,
+    Day,
+    Second,
+}
+
+// https://www.hl7.org/fhir/datatypes.html#date
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FhirDate {
+    value: NaiveDate,
+    precision: FhirDateTimePrecision,
+}
+impl FhirDate {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let (value, precision) = parse_partial_date(text)?;
+        Ok(Self { value, precision })
+    }
+    pub fn from_ymd(year: i32, month: u32, day: u32) -> Result<Self, String> {
+        let value = NaiveDate::from_ymd_opt(year, month, day)
+            .ok_or(format!("Invalid date: {}-{}-{}", year, month, day))?;
+        Ok(Self {
+            value,
+            precision: FhirDateTimePrecision::Day,
+        })
+    }
+    pub fn today() -> Self {
+        Self {
+            value: Utc::now().date_naive(),
+            precision: FhirDateTimePrecision::Day,
+        }
+    }
+    pub fn precision(&self) -> FhirDateTimePrecision {
+        self.precision
+    }
+    pub fn to_naive_date(&self) -> NaiveDate {
+        self.value
+    }
+}
+impl Display for FhirDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_partial_date(self.value, self.precision))
+    }
+}
+
+// https://www.hl7.org/fhir/datatypes.html#dateTime
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FhirDateTime {
+    value: DateTime<FixedOffset>,
+    precision: FhirDateTimePrecision,
+}
+impl FhirDateTime {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        if text.contains('T') {
+            let value = parse_full_date_time(text)?;
+            return Ok(Self {
+                value,
+                precision: FhirDateTimePrecision::Second,
+            });
+        }
+        let (date, precision) = parse_partial_date(text)?;
+        Ok(Self {
+            value: start_of_day_utc(date),
+            precision,
+        })
+    }
+    pub fn from_timestamp(seconds: i64) -> Result<Self, String> {
+        Ok(FhirInstant::from_timestamp(seconds)?.into())
+    }
+    pub fn now() -> Self {
+        FhirInstant::now().into()
+    }
+    pub fn timestamp(&self) -> i64 {
+        self.value.timestamp()
+    }
+    pub fn precision(&self) -> FhirDateTimePrecision {
+        self.precision
+    }
+    pub fn to_chrono(&self) -> DateTime<FixedOffset> {
+        self.value
+    }
+    // Last instant covered by a partial dateTime, the value itself otherwise
+    pub fn end_of_precision(&self) -> DateTime<FixedOffset> {
+        let date = self.value.date_naive();
+        let next = match self.precision {
+            FhirDateTimePrecision::Second => return self.value,
+            FhirDateTimePrecision::Day => date.succ_opt(),
+            FhirDateTimePrecision::Month => {
+                let (year, month) = match date.month() {
+                    12 => (date.year() + 1, 1),
+                    month => (date.year(), month + 1),
+                };
+                NaiveDate::from_ymd_opt(year, month, 1)
+            }
+            FhirDateTimePrecision::Year => NaiveDate::from_ymd_opt(date.year() + 1, 1, 1),
+        };
+        next.map(|next| start_of_day_utc(next) - Duration::nanoseconds(1))
+            .unwrap_or(self.value)
+    }
+    // None when the result falls outside the years FHIR can write
+    pub fn checked_add(&self, duration: Duration) -> Option<Self> {
+        let value = in_fhir_range(self.value.checked_add_signed(duration)?)?;
+        Some(self.with_value(value))
+    }
+    pub fn checked_sub(&self, duration: Duration) -> Option<Self> {
+        let value = in_fhir_range(self.value.checked_sub_signed(duration)?)?;
+        Some(self.with_value(value))
+    }
+    // Keeps the precision, so a partial date stays truncated to its period
+    fn with_value(&self, value: DateTime<FixedOffset>) -> Self {
+        match self.precision {
+            FhirDateTimePrecision::Second => Self {
+                value,
+                precision: self.precision,
+            },
+            precision => {
+                let date = truncate_date(value.date_naive(), precision);
+                Self {
+                    value: start_of_day_utc(date),
+                    precision,
+                }
+            }
+        }
+    }
+}
+impl Display for FhirDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.precision {
+            FhirDateTimePrecision::Second => {
+                f.write_str(&self.value.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+            }
+            precision => f.write_str(&format_partial_date(self.value.date_naive(), precision)),
+        }
+    }
+}
+impl From<FhirDate> for FhirDateTime {
+    fn from(date: FhirDate) -> Self {
+        Self {
+            value: start_of_day_utc(date.value),
+            precision: date.precision,
+        }
+    }
+}
+impl From<FhirInstant> for FhirDateTime {
+    fn from(instant: FhirInstant) -> Self {
+        Self {
+            value: instant.0,
+            precision: FhirDateTimePrecision::Second,
+        }
+    }
+}
+impl Add<Duration> for FhirDateTime {
+    type Output = Result<FhirDateTime, String>;
+    fn add(self, duration: Duration) -> Self::Output {
+        self.checked_add(duration)
+            .ok_or(format!("dateTime out of range: {} + {}", self, duration))
+    }
+}
+impl Sub<Duration> for FhirDateTime {
+    type Output = Result<FhirDateTime, String>;
+    fn sub(self, duration: Duration) -> Self::Output {
+        self.checked_sub(duration)
+            .ok_or(format!("dateTime out of range: {} - {}", self, duration))
+    }
+}
+impl Sub<FhirDateTime> for FhirDateTime {
+    type Output = Duration;
+    fn sub(self, other: FhirDateTime) -> Self::Output {
+        self.value - other.value
+    }
+}
+
+// https://www.hl7.org/fhir/datatypes.html#instant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FhirInstant(DateTime<FixedOffset>);
+impl FhirInstant {
+    // Instants are always full precision and must carry a timezone offset
+    pub fn parse(text: &str) -> Result<Self, String> {
+        Ok(Self(parse_full_date_time(text)?))
+    }
+    pub fn from_timestamp(seconds: i64) -> Result<Self, String> {
+        let value = DateTime::<Utc>::from_timestamp(seconds, 0)
+            .ok_or(format!("Timestamp out of range: {}", seconds))?;
+        Ok(Self(value.fixed_offset()))
+    }
+    // Nostr events carry `created_at` as unsigned seconds since the epoch
+    pub fn from_nostr_created_at(created_at: u64) -> Result<Self, String> {
+        let seconds = i64::try_from(created_at)
+            .map_err(|_| format!("Timestamp out of range: {}", created_at))?;
+        Self::from_timestamp(seconds)
+    }
+    pub fn from_chrono(value: DateTime<FixedOffset>) -> Self {
+        Self(value)
+    }
+    pub fn now() -> Self {
+        Self(Utc::now().fixed_offset())
+    }
+    pub fn timestamp(&self) -> i64 {
+        self.0.timestamp()
+    }
+    pub fn to_chrono(&self) -> DateTime<FixedOffset> {
+        self.0
+    }
+    pub fn checked_add(&self, duration: Duration) -> Option<Self> {
+        in_fhir_range(self.0.checked_add_signed(duration)?).map(Self)
+    }
+    pub fn checked_sub(&self, duration: Duration) -> Option<Self> {
+        in_fhir_range(self.0.checked_sub_signed(duration)?).map(Self)
+    }
+}
+impl Display for FhirInstant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+    }
+}
+impl Add<Duration> for FhirInstant {
+    type Output = Result<FhirInstant, String>;
+    fn add(self, duration: Duration) -> Self::Output {
+        self.checked_add(duration)
+            .ok_or(format!("instant out of range: {} + {}", self, duration))
+    }
+}
+impl Sub<Duration> for FhirInstant {
+    type Output = Result<FhirInstant, String>;
+    fn sub(self, duration: Duration) -> Self::Output {
+        self.checked_sub(duration)
+            .ok_or(format!("instant out of range: {} - {}", self, duration))
+    }
+}
+impl Sub<FhirInstant> for FhirInstant {
+    type Output = Duration;
+    fn sub(self, other: FhirInstant) -> Self::Output {
+        self.0 - other.0
+    }
+}
+
+macro_rules! impl_string_serde {
+    ($name:ident) => {
+        impl FromStr for $name {
+            type Err = String;
+            fn from_str(text: &str) -> Result<Self, Self::Err> {
+                Self::parse(text)
+            }
+        }
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.to_string())
+            }
+        }
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let text = String::deserialize(deserializer)?;
+                Self::parse(&text).map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+impl_string_serde!(FhirDate);
+impl_string_serde!(FhirDateTime);
+impl_string_serde!(FhirInstant);
+
+// YYYY, YYYY-MM or YYYY-MM-DD
+fn parse_partial_date(text: &str) -> Result<(NaiveDate, FhirDateTimePrecision), String> {
+    let invalid = || format!("Invalid FHIR date: {}", text);
+    let parts: Vec<&str> = text.split('-').collect();
+    let lengths: Vec<usize> = parts.iter().map(|part| part.len()).collect();
+    if !matches!(lengths.as_slice(), [4] | [4, 2] | [4, 2, 2])
+        || !parts
+            .iter()
+            .all(|part| part.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return Err(invalid());
+    }
+    let number = |index: usize| parts.get(index).map(|part| part.parse::<u32>());
+    let year = number(0).ok_or_else(invalid)?.map_err(|_| invalid())?;
+    if year == 0 {
+        return Err(invalid());
+    }
+    let month = number(1).transpose().map_err(|_| invalid())?;
+    let day = number(2).transpose().map_err(|_| invalid())?;
+    let precision = match (month, day) {
+        (None, _) => FhirDateTimePrecision::Year,
+        (Some(_), None) => FhirDateTimePrecision::Month,
+        _ => FhirDateTimePrecision::Day,
+    };
+    let date = NaiveDate::from_ymd_opt(year as i32, month.unwrap_or(1), day.unwrap_or(1))
+        .ok_or_else(invalid)?;
+    Ok((date, precision))
+}
+
+// YYYY-MM-DDThh:mm:ss(.sss)(Z|+hh:mm|-hh:mm), timezone required
+fn parse_full_date_time(text: &str) -> Result<DateTime<FixedOffset>, String> {
+    let invalid = || format!("Invalid FHIR instant: {}", text);
+    if !text.is_ascii() {
+        return Err(invalid());
+    }
+    let (date, time) = text.split_once('T').ok_or_else(invalid)?;
+    if date.len() != 10 || time.len() < 8 || time.contains(['t', 'z', ' ']) {
+        return Err(invalid());
+    }
+    let has_offset = time.ends_with('Z') || time[8..].contains(['+', '-']);
+    if !has_offset {
+        return Err(format!(
+            "FHIR dateTime with a time must include a timezone: {}",
+            text
+        ));
+    }
+    DateTime::parse_from_rfc3339(text).map_err(|_| invalid())
+}
+
+fn format_partial_date(date: NaiveDate, precision: FhirDateTimePrecision) -> String {
+    match precision {
+        FhirDateTimePrecision::Year => format!("{:04}", date.year()),
+        FhirDateTimePrecision::Month => format!("{:04}-{:02}", date.year(), date.month()),
+        _ => format!("{:04}-{:02}-{:02}", date.year(), date.month(), date.day()),
+    }
+}
+
+fn truncate_date(date: NaiveDate, precision: FhirDateTimePrecision) -> NaiveDate {
+    match precision {
+        FhirDateTimePrecision::Year => date.with_ordinal(1).unwrap_or(date),
+        FhirDateTimePrecision::Month => date.with_day(1).unwrap_or(date),
+        _ => date,
+    }
+}
+
+// FHIR writes years with four digits, from 0001 to 9999
+fn in_fhir_range(value: DateTime<FixedOffset>) -> Option<DateTime<FixedOffset>> {
+    (1..=9999).contains(&value.year()).then_some(value)
+}
+
+fn start_of_day_utc(date: NaiveDate) -> DateTime<FixedOffset> {
+    date.and_hms_opt(0, 0, 0)
+        .unwrap_or_default()
+        .and_utc()
+        .fixed_offset()
+}
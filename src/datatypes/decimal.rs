@@ -0,0 +1,49 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// https://www.hl7.org/fhir/datatypes.html#decimal
+// A rational number. NaN and infinities are not decimals and are rejected when
+// one is built, which keeps equality total for the types holding decimals.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct FhirDecimal(f64);
+impl FhirDecimal {
+    pub fn new(value: f64) -> Result<Self, String> {
+        match value.is_finite() {
+            true => Ok(Self(value)),
+            false => Err(format!("Decimal is not a finite number: {}", value)),
+        }
+    }
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+}
+impl Eq for FhirDecimal {}
+impl TryFrom<f64> for FhirDecimal {
+    type Error = String;
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+impl From<FhirDecimal> for f64 {
+    fn from(decimal: FhirDecimal) -> Self {
+        decimal.0
+    }
+}
+impl std::fmt::Display for FhirDecimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+// Whole numbers are written without a fraction, as FHIR JSON gives them
+impl Serialize for FhirDecimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0.fract() == 0.0 && self.0.abs() < i64::MAX as f64 {
+            true => serializer.serialize_i64(self.0 as i64),
+            false => serializer.serialize_f64(self.0),
+        }
+    }
+}
+impl<'de> Deserialize<'de> for FhirDecimal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::new(f64::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,260 @@
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+use super::{choice_from_map, choice_to_map, FhirChoice, FhirValue};
+use crate::consts::IGNIS_EXTENSION_LEGACY;
+
+// https://www.hl7.org/fhir/extensibility.html#Extension
+// An extension carries either a single value[x] or nested extensions (ext-1).
+// Extensions with unknown urls, and values of types FhirValue does not model,
+// survive a round trip unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FhirExtension {
+    id: Option<String>,
+    url: String,
+    value: Option<FhirValue>,
+    extension: Vec<FhirExtension>,
+}
+impl FhirExtension {
+    pub fn new_value(url: &str, value: FhirValue) -> Self {
+        Self {
+            id: None,
+            url: url.to_string(),
+            value: Some(value),
+            extension: vec![],
+        }
+    }
+    pub fn new_complex(url: &str, extension: Vec<FhirExtension>) -> Self {
+        Self {
+            id: None,
+            url: url.to_string(),
+            value: None,
+            extension,
+        }
+    }
+    pub fn get_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+    pub fn get_url(&self) -> &str {
+        &self.url
+    }
+    pub fn get_value(&self) -> Option<&FhirValue> {
+        self.value.as_ref()
+    }
+    // FHIR type name of the value, e.g. "String"
+    pub fn get_value_type(&self) -> Option<&str> {
+        self.value.as_ref().map(FhirChoice::type_name)
+    }
+    // Reads the value into a Rust type, checking its FHIR type name
+    pub fn get_value_as<T: DeserializeOwned>(&self, value_type: &str) -> Result<T, String> {
+        match &self.value {
+            Some(value) if value.type_name() == value_type => {
+                serde_json::from_value(value.to_json()?).map_err(|e| e.to_string())
+            }
+            Some(value) => Err(format!(
+                "Extension {} has value{} instead of value{}",
+                self.url,
+                value.type_name(),
+                value_type
+            )),
+            None => Err(format!("Extension {} has no value", self.url)),
+        }
+    }
+    pub fn get_extensions(&self) -> &Vec<FhirExtension> {
+        &self.extension
+    }
+    // Nested extensions use relative urls, e.g. "latitude"
+    pub fn get_extension(&self, url: &str) -> Option<&FhirExtension> {
+        self.extension.iter().find(|extension| extension.url == url)
+    }
+    // Content stored before extensions were typed, kept as an opaque JSON value
+    pub fn legacy(value: Value) -> Self {
+        Self::new_value(IGNIS_EXTENSION_LEGACY, FhirValue::String(value.to_string()))
+    }
+    pub fn get_legacy_value(&self) -> Option<Value> {
+        match self.url == IGNIS_EXTENSION_LEGACY {
+            true => match self.value.as_ref()? {
+                FhirValue::String(json) => serde_json::from_str(json).ok(),
+                _ => None,
+            },
+            false => None,
+        }
+    }
+}
+
+impl Serialize for FhirExtension {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = Map::new();
+        if let Some(id) = &self.id {
+            map.insert("id".to_string(), Value::String(id.clone()));
+        }
+        map.insert("url".to_string(), Value::String(self.url.clone()));
+        if !self.extension.is_empty() {
+            let extension =
+                serde_json::to_value(&self.extension).map_err(serde::ser::Error::custom)?;
+            map.insert("extension".to_string(), extension);
+        }
+        map.extend(choice_to_map("value", self.value.as_ref()).map_err(serde::ser::Error::custom)?);
+        map.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FhirExtension {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        Self::try_from(value).map_err(serde::de::Error::custom)
+    }
+}
+
+impl TryFrom<Value> for FhirExtension {
+    type Error = String;
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let Value::Object(map) = value else {
+            return Err("Extension must be a JSON object".to_string());
+        };
+        let url = map
+            .get("url")
+            .and_then(Value::as_str)
+            .ok_or("Extension is missing its url")?
+            .to_string();
+        let mut extension = Self::new_complex(&url, vec![]);
+        extension.value = choice_from_map("value", &map)?;
+        for (key, value) in map {
+            match key.as_str() {
+                "url" => {}
+                "id" => {
+                    extension.id = Some(
+                        value
+                            .as_str()
+                            .ok_or("Extension id must be a string")?
+                            .to_string(),
+                    )
+                }
+                "extension" => {
+                    extension.extension =
+                        serde_json::from_value(value).map_err(|e| e.to_string())?
+                }
+                key if key.starts_with("value") => {}
+                key => return Err(format!("Unknown element {} in extension {}", key, url)),
+            }
+        }
+        if extension.value.is_some() && !extension.extension.is_empty() {
+            return Err(format!(
+                "Extension {} cannot have both a value and extensions",
+                url
+            ));
+        }
+        Ok(extension)
+    }
+}
+
+// Reads a resource's `extension` element. Older notes stored an arbitrary JSON
+// value there; a value that is not a list is kept whole as a legacy extension
+// instead of failing the entire resource. Lists must be valid extensions.
+pub(crate) fn deserialize_extensions<'de, D>(
+    deserializer: D,
+) -> Result<Vec<FhirExtension>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<Value>::deserialize(deserializer)?;
+    match value {
+        None | Some(Value::Null) => Ok(vec![]),
+        Some(Value::Array(items)) => items
+            .into_iter()
+            .map(FhirExtension::try_from)
+            .collect::<Result<_, _>>()
+            .map_err(serde::de::Error::custom),
+        Some(value) => Ok(vec![FhirExtension::legacy(value)]),
+    }
+}
+
+// An extension with a fixed url whose content maps to a Rust type
+pub trait FhirTypedExtension: Sized {
+    const URL: &'static str;
+    fn to_extension(&self) -> FhirExtension;
+    fn from_extension(extension: &FhirExtension) -> Result<Self, String>;
+}
+
+// Get, set and remove extensions by url on resources and datatypes
+pub trait FhirExtensible {
+    fn extensions(&self) -> &Vec<FhirExtension>;
+    fn extensions_mut(&mut self) -> &mut Vec<FhirExtension>;
+    fn get_extension(&self, url: &str) -> Option<&FhirExtension> {
+        self.extensions()
+            .iter()
+            .find(|extension| extension.get_url() == url)
+    }
+    fn get_extensions_by_url(&self, url: &str) -> Vec<&FhirExtension> {
+        self.extensions()
+            .iter()
+            .filter(|extension| extension.get_url() == url)
+            .collect()
+    }
+    fn add_extension(&mut self, extension: FhirExtension) {
+        self.extensions_mut().push(extension);
+    }
+    // Replaces any extensions with the same url
+    fn set_extension(&mut self, extension: FhirExtension) {
+        let url = extension.get_url().to_string();
+        self.remove_extension(&url);
+        self.extensions_mut().push(extension);
+    }
+    fn remove_extension(&mut self, url: &str) -> Vec<FhirExtension> {
+        let (removed, kept) = std::mem::take(self.extensions_mut())
+            .into_iter()
+            .partition(|extension| extension.get_url() == url);
+        *self.extensions_mut() = kept;
+        removed
+    }
+    fn get_typed_extension<E: FhirTypedExtension>(&self) -> Result<Option<E>, String> {
+        self.get_extension(E::URL)
+            .map(E::from_extension)
+            .transpose()
+    }
+    fn set_typed_extension<E: FhirTypedExtension>(&mut self, extension: &E) {
+        self.set_extension(extension.to_extension());
+    }
+    fn remove_typed_extension<E: FhirTypedExtension>(&mut self) -> Vec<FhirExtension> {
+        self.remove_extension(E::URL)
+    }
+    fn get_legacy_extension<T: DeserializeOwned>(&self) -> Option<T> {
+        let value = self
+            .get_extension(IGNIS_EXTENSION_LEGACY)?
+            .get_legacy_value()?;
+        serde_json::from_value(value).ok()
+    }
+}
+
+// https://www.hl7.org/fhir/json.html#primitive
+// The id and extensions of a primitive, written next to it as "_element" in JSON
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirElement {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_extensions",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    extension: Vec<FhirExtension>,
+}
+impl FhirElement {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn get_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.extension.is_empty()
+    }
+}
+impl FhirExtensible for FhirElement {
+    fn extensions(&self) -> &Vec<FhirExtension> {
+        &self.extension
+    }
+    fn extensions_mut(&mut self) -> &mut Vec<FhirExtension> {
+        &mut self.extension
+    }
+}
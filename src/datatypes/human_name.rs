@@ -0,0 +1,209 @@
+use super::FhirHumanName;
+use crate::valuesets::FhirNameUse;
+
+// Honorifics and titles written before a name, compared without the trailing dot
+const NAME_PREFIXES: [&str; 26] = [
+    "dr", "dra", "lic", "licda", "ing", "inga", "arq", "abg", "abog", "prof", "profa", "sr", "sra",
+    "srta", "don", "doña", "mr", "mrs", "ms", "miss", "mx", "sir", "fr", "rev", "pbro", "mtro",
+];
+// Generational markers and credentials written after a name
+// "do" is left out: it is far more common as the Portuguese particle
+const NAME_SUFFIXES: [&str; 19] = [
+    "jr", "sr", "ii", "iii", "iv", "v", "hijo", "filho", "neto", "md", "phd", "dds", "dmd", "rn",
+    "np", "pa", "msc", "mba", "esq",
+];
+// Lowercase words that belong to the surname that follows them ("de la Cruz")
+const NAME_PARTICLES: [&str; 20] = [
+    "de", "del", "la", "las", "los", "da", "das", "do", "dos", "di", "du", "van", "von", "der",
+    "den", "le", "san", "santa", "dal", "al",
+];
+// Words joining two surnames into one ("Ortega y Gasset")
+const NAME_CONJUNCTIONS: [&str; 2] = ["y", "e"];
+
+fn bare(word: &str) -> String {
+    word.trim_end_matches([',', '.']).to_lowercase()
+}
+fn is_prefix(word: &str) -> bool {
+    NAME_PREFIXES.contains(&bare(word).as_str())
+}
+fn is_suffix(word: &str) -> bool {
+    NAME_SUFFIXES.contains(&bare(word).as_str())
+}
+fn is_particle(word: &str) -> bool {
+    NAME_PARTICLES.contains(&word)
+}
+
+// Spanish names carry the paternal then maternal surname, Portuguese names the
+// maternal then paternal one; elsewhere a single family name is assumed.
+fn has_two_surnames(language: &str) -> bool {
+    matches!(language.split('-').next().unwrap_or_default(), "es" | "pt")
+}
+
+// Groups words into name parts, keeping particles with the word that follows
+// and joining surnames linked by a conjunction.
+fn name_parts(words: &[&str], language: &str) -> Vec<String> {
+    let mut parts: Vec<String> = vec![];
+    let mut pending: Vec<&str> = vec![];
+    let mut conjunction: Option<&str> = None;
+    for (index, word) in words.iter().enumerate() {
+        let last = index + 1 == words.len();
+        if has_two_surnames(language)
+            && NAME_CONJUNCTIONS.contains(word)
+            && !parts.is_empty()
+            && pending.is_empty()
+            && !last
+        {
+            conjunction = Some(word);
+            continue;
+        }
+        pending.push(word);
+        if is_particle(word) && !last {
+            continue;
+        }
+        let part = pending.join(" ");
+        pending.clear();
+        match (parts.last_mut(), conjunction.take()) {
+            (Some(previous), Some(conjunction)) => {
+                *previous = format!("{} {} {}", previous, conjunction, part);
+            }
+            _ => parts.push(part),
+        }
+    }
+    parts
+}
+
+impl FhirHumanName {
+    // Splits a free text name into prefixes, given names, family name and
+    // suffixes. The language decides how many trailing parts form the family
+    // name: "Dra. Ana María Pérez Gómez" has two surnames in Spanish, while
+    // "John Fitzgerald Kennedy" has one in English. "Family, Given" order is
+    // recognised by the comma.
+    pub fn parse(text: &str, language: &str) -> Self {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let (main, after_comma) = match text.split_once(',') {
+            Some((main, rest)) => (main.trim().to_string(), Some(rest.trim().to_string())),
+            None => (text.clone(), None),
+        };
+        let mut words: Vec<&str> = main.split(' ').filter(|word| !word.is_empty()).collect();
+        let mut prefix = vec![];
+        while words.len() > 1 && is_prefix(words[0]) {
+            prefix.push(words.remove(0).to_string());
+        }
+        let mut suffix = vec![];
+        while words.len() > 1 && is_suffix(words[words.len() - 1]) {
+            suffix.insert(0, words.pop().unwrap_or_default().to_string());
+        }
+        let (given, family) = match after_comma {
+            // "John Smith, MD": the comma only separates credentials
+            Some(rest) if !rest.is_empty() && rest.split(' ').all(is_suffix) => {
+                suffix.extend(rest.split(' ').map(str::to_string));
+                Self::split_given_family(&words, language)
+            }
+            // "Pérez Gómez, Dra. Ana María"
+            Some(rest) => {
+                let mut given_words: Vec<&str> =
+                    rest.split(' ').filter(|w| !w.is_empty()).collect();
+                while given_words.len() > 1 && is_prefix(given_words[0]) {
+                    prefix.push(given_words.remove(0).to_string());
+                }
+                while given_words.len() > 1 && is_suffix(given_words[given_words.len() - 1]) {
+                    suffix.push(given_words.pop().unwrap_or_default().to_string());
+                }
+                let given = given_words.iter().map(|word| word.to_string()).collect();
+                (given, words.join(" "))
+            }
+            None => Self::split_given_family(&words, language),
+        };
+        Self {
+            name_use: Some(FhirNameUse::Official),
+            text,
+            family,
+            family_element: None,
+            given,
+            prefix,
+            suffix,
+            period: None,
+        }
+    }
+    fn split_given_family(words: &[&str], language: &str) -> (Vec<String>, String) {
+        let parts = name_parts(words, language);
+        let family_parts = match parts.len() {
+            0 | 1 => 0,
+            2 => 1,
+            _ if has_two_surnames(language) => 2,
+            _ => 1,
+        };
+        let split = parts.len() - family_parts;
+        let given = parts[..split]
+            .iter()
+            .flat_map(|part| part.split(' ').map(str::to_string))
+            .collect();
+        (given, parts[split..].join(" "))
+    }
+    // Family name broken into surnames, e.g. ["de la Cruz", "Pérez"]
+    pub fn surnames(&self, language: &str) -> Vec<String> {
+        let words: Vec<&str> = self.family.split_whitespace().collect();
+        name_parts(&words, language)
+    }
+    // The surname used on its own when addressing someone: the paternal one,
+    // which is first in Spanish and last in Portuguese
+    pub fn primary_surname(&self, language: &str) -> String {
+        let surnames = self.surnames(language);
+        let surname = match language.split('-').next().unwrap_or_default() {
+            "es" => surnames.first(),
+            "pt" => surnames.last(),
+            _ => None,
+        };
+        surname.cloned().unwrap_or(self.family.clone())
+    }
+    // "Dra. Ana María Pérez Gómez, PhD"
+    pub fn format_full(&self) -> String {
+        let name = self
+            .prefix
+            .iter()
+            .chain(self.given.iter())
+            .chain(std::iter::once(&self.family))
+            .filter(|part| !part.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ");
+        match (name.is_empty(), self.suffix.is_empty()) {
+            (true, _) => self.text.clone(),
+            (false, true) => name,
+            (false, false) => format!("{}, {}", name, self.suffix.join(" ")),
+        }
+    }
+    // Form of address: "Dra. Pérez", or "Ana Pérez" when there is no title
+    pub fn format_formal(&self, language: &str) -> String {
+        let surname = self.primary_surname(language);
+        let lead = match self.prefix.is_empty() {
+            true => self.given.first().cloned().unwrap_or_default(),
+            false => self.prefix.join(" "),
+        };
+        let formal = format!("{} {}", lead, surname).trim().to_string();
+        match formal.is_empty() {
+            true => self.text.clone(),
+            false => formal,
+        }
+    }
+    // First given name only: "Ana"
+    pub fn format_informal(&self) -> String {
+        self.given.first().cloned().unwrap_or(self.format_full())
+    }
+    // "Pérez Gómez, Ana María", for alphabetical listings
+    pub fn format_sortable(&self) -> String {
+        match (self.family.is_empty(), self.given.is_empty()) {
+            (true, true) => self.text.clone(),
+            (true, false) => self.given.join(" "),
+            (false, true) => self.family.clone(),
+            (false, false) => format!("{}, {}", self.family, self.given.join(" ")),
+        }
+    }
+    // Names stored before parsing existed only have their text filled in
+    pub fn display(&self) -> String {
+        match self.given.is_empty() && !self.text.trim().is_empty() {
+            true => self.text.clone(),
+            false => self.format_full(),
+        }
+    }
+}
@@ -1,39 +1,140 @@
+mod address;
+mod choice;
+mod contact_point;
+mod countries;
+mod datetime;
+mod decimal;
+mod extension;
+mod human_name;
+mod timezone;
+mod translation;
+mod unknown;
+mod units;
+pub use address::{FhirGeolocation, FHIR_EXTENSION_GEOLOCATION};
+pub use choice::{choice_from_map, choice_to_map, FhirChoice, FhirValue};
+pub use contact_point::normalize_phone;
+pub use countries::validate_country;
+pub use datetime::{FhirDate, FhirDateTime, FhirDateTimePrecision, FhirInstant};
+pub use decimal::FhirDecimal;
+pub(crate) use extension::deserialize_extensions;
+pub use extension::{FhirElement, FhirExtensible, FhirExtension, FhirTypedExtension};
+pub use timezone::parse_time_zone;
+pub use translation::{FhirTranslation, FHIR_EXTENSION_TRANSLATION};
+pub use unknown::FhirUnknownElements;
+pub use units::{validate_currency, validate_ucum, ISO_4217_SYSTEM, UCUM_SYSTEM};
+
+use super::terminology::language_matches;
 use super::valuesets::{
-    FhirAddressType, FhirAddressUse, FhirContactPointSystem, FhirContactPointUse, FhirNameUse,
+    FhirAddressType, FhirAddressUse, FhirCodedValue, FhirContactPointSystem, FhirContactPointUse,
+    FhirNameUse, FhirQuantityComparator,
 };
 use serde::{Deserialize, Serialize};
 
 // https://www.hl7.org/fhir/datatypes.html#Period
+// Either bound may be missing: no end means the period is ongoing.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FhirPeriod {
-    pub start: FhirDateTime,
-    pub end: FhirDateTime,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<FhirDateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<FhirDateTime>,
+}
+impl FhirPeriod {
+    pub fn new(start: Option<FhirDateTime>, end: Option<FhirDateTime>) -> Result<Self, String> {
+        let period = Self { start, end };
+        period.validate()?;
+        Ok(period)
+    }
+    pub fn starting(start: FhirDateTime) -> Self {
+        Self {
+            start: Some(start),
+            end: None,
+        }
+    }
+    // per-1: if present, start SHALL have a lower or equal value than end
+    pub fn validate(&self) -> Result<(), String> {
+        match (&self.start, &self.end) {
+            (Some(start), Some(end)) if start.to_chrono() > end.end_of_precision() => {
+                Err(format!("Period start {} is after end {}", start, end))
+            }
+            _ => Ok(()),
+        }
+    }
+    // A partial end date covers its whole day, month or year
+    pub fn contains(&self, moment: &FhirDateTime) -> bool {
+        let after_start = self
+            .start
+            .is_none_or(|start| start.to_chrono() <= moment.end_of_precision());
+        let before_end = self
+            .end
+            .is_none_or(|end| moment.to_chrono() <= end.end_of_precision());
+        after_start && before_end
+    }
+    pub fn is_active_now(&self) -> bool {
+        self.contains(&FhirDateTime::now())
+    }
+    pub fn is_expired(&self) -> bool {
+        self.end
+            .is_some_and(|end| end.end_of_precision() < FhirDateTime::now().to_chrono())
+    }
+    pub fn overlaps(&self, other: &FhirPeriod) -> bool {
+        let starts_before_other_ends = match (&self.start, &other.end) {
+            (Some(start), Some(end)) => start.to_chrono() <= end.end_of_precision(),
+            _ => true,
+        };
+        let other_starts_before_end = match (&other.start, &self.end) {
+            (Some(start), Some(end)) => start.to_chrono() <= end.end_of_precision(),
+            _ => true,
+        };
+        starts_before_other_ends && other_starts_before_end
+    }
+    pub fn contains_period(&self, other: &FhirPeriod) -> bool {
+        let start_inside = match (&self.start, &other.start) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(start), Some(other_start)) => start.to_chrono() <= other_start.to_chrono(),
+        };
+        let end_inside = match (&self.end, &other.end) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(end), Some(other_end)) => other_end.end_of_precision() <= end.end_of_precision(),
+        };
+        start_inside && end_inside
+    }
+    pub fn duration(&self) -> Option<chrono::Duration> {
+        Some(self.end? - self.start?)
+    }
 }
-// https://www.hl7.org/fhir/datatypes.html#instant
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct FhirInstant(pub String);
-// https://www.hl7.org/fhir/datatypes.html#dateTime
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct FhirDateTime(String);
 // https://www.hl7.org/fhir/datatypes.html#HumanName
+// Every element is optional; empty text and parts are left out of the JSON.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FhirHumanName {
-    #[serde(rename = "use")]
-    pub name_use: FhirNameUse,
+    #[serde(rename = "use", default, skip_serializing_if = "Option::is_none")]
+    pub name_use: Option<FhirNameUse>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub text: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub family: String,
+    // Extensions of the family name, such as its own-name and prefix parts
+    #[serde(rename = "_family", default, skip_serializing_if = "Option::is_none")]
+    pub family_element: Option<FhirElement>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub given: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub prefix: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub suffix: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub period: Option<FhirPeriod>,
 }
 impl FhirHumanName {
+    // A name known only by its text; use `parse` to split it into parts
     pub fn new_simple(text: String) -> Self {
-        let family = text.clone().split(' ').collect();
         Self {
-            name_use: FhirNameUse::Official,
+            name_use: Some(FhirNameUse::Official),
             text,
-            family,
+            family: String::new(),
+            family_element: None,
             given: vec![],
             prefix: vec![],
             suffix: vec![],
@@ -42,49 +143,434 @@ impl FhirHumanName {
     }
 }
 // https://www.hl7.org/fhir/datatypes.html#ContactPoint
+// The system is required whenever there is a value (cpt-2)
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FhirContactPoint {
-    pub system: FhirContactPointSystem,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system: Option<FhirContactPointSystem>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub value: String,
-    #[serde(rename = "use")]
-    pub contact_use: FhirContactPointUse,
-    pub period: Option<FhirPeriod>,
+    #[serde(rename = "use", default, skip_serializing_if = "Option::is_none")]
+    pub contact_use: Option<FhirContactPointUse>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rank: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub period: Option<FhirPeriod>,
 }
 impl FhirContactPoint {
+    // Unvalidated constructors; `email` and `phone` check and normalize the value
     pub fn new_email(value: String) -> Self {
         Self {
-            system: FhirContactPointSystem::Email,
+            system: Some(FhirContactPointSystem::Email),
             value,
-            contact_use: FhirContactPointUse::Mobile,
+            contact_use: Some(FhirContactPointUse::Work),
             period: None,
             rank: None,
         }
     }
     pub fn new_phone(value: String) -> Self {
         Self {
-            system: FhirContactPointSystem::Phone,
+            system: Some(FhirContactPointSystem::Phone),
             value,
-            contact_use: FhirContactPointUse::Mobile,
+            contact_use: Some(FhirContactPointUse::Mobile),
             period: None,
             rank: None,
         }
     }
 }
 // https://www.hl7.org/fhir/datatypes.html#Address
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FhirAddress {
-    #[serde(rename = "use")]
-    pub address_use: FhirAddressUse,
-    #[serde(rename = "type")]
-    pub address_type: FhirAddressType,
+    #[serde(rename = "use", default, skip_serializing_if = "Option::is_none")]
+    pub address_use: Option<FhirAddressUse>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub address_type: Option<FhirAddressType>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub text: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub line: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub city: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub district: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub state: Option<String>,
-    #[serde(rename = "postalCode")]
+    #[serde(
+        rename = "postalCode",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     pub postal_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub country: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub period: Option<FhirPeriod>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_extensions",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub extension: Vec<FhirExtension>,
+}
+// https://www.hl7.org/fhir/datatypes.html#Coding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirCoding {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub code: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<String>,
+    // Extensions of the display, such as its translations
+    #[serde(rename = "_display", default, skip_serializing_if = "Option::is_none")]
+    pub display_element: Option<FhirElement>,
+    #[serde(
+        rename = "userSelected",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub user_selected: Option<bool>,
+}
+impl FhirCoding {
+    pub fn new(system: String, code: String, display: Option<String>) -> Self {
+        Self {
+            system: Some(system),
+            version: None,
+            code,
+            display,
+            display_element: None,
+            user_selected: None,
+        }
+    }
+    pub fn with_translation(mut self, language: &str, content: &str) -> Self {
+        self.add_translation(FhirTranslation::new(language, content));
+        self
+    }
+    // Replaces any translation into the same language
+    pub fn add_translation(&mut self, translation: FhirTranslation) {
+        let element = self.display_element.get_or_insert_with(FhirElement::new);
+        let kept: Vec<FhirExtension> = element
+            .remove_extension(FHIR_EXTENSION_TRANSLATION)
+            .into_iter()
+            .filter(|extension| {
+                FhirTranslation::from_extension(extension)
+                    .map_or(true, |own| own.get_language() != translation.get_language())
+            })
+            .collect();
+        for extension in kept {
+            element.add_extension(extension);
+        }
+        element.add_extension(translation.to_extension());
+    }
+    // Translations of the display; malformed ones are skipped
+    pub fn get_translations(&self) -> Vec<FhirTranslation> {
+        self.display_element
+            .iter()
+            .flat_map(|element| element.get_extensions_by_url(FHIR_EXTENSION_TRANSLATION))
+            .filter_map(|extension| FhirTranslation::from_extension(extension).ok())
+            .collect()
+    }
+    // The translation into `language`, else one into a related tag, so
+    // "es-VE" finds "es"
+    pub fn get_translation(&self, language: &str) -> Option<String> {
+        let translations = self.get_translations();
+        translations
+            .iter()
+            .find(|translation| translation.get_language().eq_ignore_ascii_case(language))
+            .or_else(|| {
+                translations
+                    .iter()
+                    .find(|translation| language_matches(translation.get_language(), language))
+            })
+            .map(|translation| translation.get_content().to_string())
+    }
+    // The display in `language` when translated, else the display itself
+    pub fn display_in(&self, language: &str) -> Option<String> {
+        self.get_translation(language).or_else(|| self.display.clone())
+    }
+    pub fn is(&self, system: &str, code: &str) -> bool {
+        self.system.as_deref() == Some(system) && self.code == code
+    }
+    pub fn decode<T: FhirCodedValue>(&self) -> Option<T> {
+        let value = T::from_fhir_code(&self.code)?;
+        match &self.system {
+            Some(system) if system != value.fhir_system() => None,
+            _ => Some(value),
+        }
+    }
+}
+impl<T: FhirCodedValue> From<T> for FhirCoding {
+    fn from(value: T) -> Self {
+        Self::new(
+            value.fhir_system().to_string(),
+            value.fhir_code(),
+            Some(value.fhir_display().to_string()),
+        )
+    }
+}
+// https://www.hl7.org/fhir/datatypes.html#CodeableConcept
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirCodeableConcept {
+    // A concept may be given by its text alone
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub coding: Vec<FhirCoding>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+impl FhirCodeableConcept {
+    pub fn new(coding: Vec<FhirCoding>, text: Option<String>) -> Self {
+        Self { coding, text }
+    }
+    pub fn from_text(text: String) -> Self {
+        Self {
+            coding: vec![],
+            text: Some(text),
+        }
+    }
+    pub fn has_coding(&self, system: &str, code: &str) -> bool {
+        self.coding.iter().any(|coding| coding.is(system, code))
+    }
+    pub fn decode<T: FhirCodedValue>(&self) -> Option<T> {
+        self.coding.iter().find_map(|coding| coding.decode())
+    }
+    pub fn decode_all<T: FhirCodedValue>(&self) -> Vec<T> {
+        self.coding
+            .iter()
+            .filter_map(|coding| coding.decode())
+            .collect()
+    }
+    // The text, else the first coding's display or code
+    pub fn display(&self) -> String {
+        self.text
+            .clone()
+            .filter(|text| !text.trim().is_empty())
+            .or_else(|| {
+                self.coding
+                    .first()
+                    .map(|coding| coding.display.clone().unwrap_or(coding.code.clone()))
+            })
+            .unwrap_or_default()
+    }
+    // The first coding translated into `language`, else `display`
+    pub fn display_in(&self, language: &str) -> String {
+        self.coding
+            .iter()
+            .find_map(|coding| coding.get_translation(language))
+            .unwrap_or_else(|| self.display())
+    }
+}
+impl<T: FhirCodedValue> From<T> for FhirCodeableConcept {
+    fn from(value: T) -> Self {
+        let text = Some(value.fhir_display().to_string());
+        Self {
+            coding: vec![value.into()],
+            text,
+        }
+    }
+}
+// https://www.hl7.org/fhir/datatypes.html#Quantity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirQuantity {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<FhirDecimal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comparator: Option<FhirQuantityComparator>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+}
+impl FhirQuantity {
+    pub fn ucum(value: f64, code: &str) -> Result<Self, String> {
+        validate_ucum(code)?;
+        Ok(Self {
+            value: Some(FhirDecimal::new(value)?),
+            comparator: None,
+            unit: Some(code.to_string()),
+            system: Some(UCUM_SYSTEM.to_string()),
+            code: Some(code.to_string()),
+        })
+    }
+    pub fn with_comparator(mut self, comparator: FhirQuantityComparator) -> Self {
+        self.comparator = Some(comparator);
+        self
+    }
+    // qty-3: if a code for the unit is present, the system SHALL also be present
+    pub fn validate(&self) -> Result<(), String> {
+        match (&self.system, &self.code) {
+            (None, Some(code)) => Err(format!("Quantity code {} has no system", code)),
+            (Some(system), Some(code)) if system == UCUM_SYSTEM => validate_ucum(code),
+            (Some(system), Some(code)) if system == ISO_4217_SYSTEM => validate_currency(code),
+            _ => Ok(()),
+        }
+    }
+    pub fn same_unit(&self, other: &FhirQuantity) -> bool {
+        self.system == other.system && self.code == other.code
+    }
+}
+
+// https://www.hl7.org/fhir/datatypes.html#Duration
+// A Quantity restricted to UCUM time units.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FhirDuration(FhirQuantity);
+impl FhirDuration {
+    pub fn new(value: f64, code: &str) -> Result<Self, String> {
+        if units::ucum_time_unit_seconds(code).is_none() {
+            return Err(format!("Not a UCUM time unit: {}", code));
+        }
+        Ok(Self(FhirQuantity::ucum(value, code)?))
+    }
+    pub fn from_quantity(quantity: FhirQuantity) -> Result<Self, String> {
+        quantity.validate()?;
+        let duration = Self(quantity);
+        duration.seconds()?;
+        Ok(duration)
+    }
+    pub fn from_chrono(duration: chrono::Duration) -> Result<Self, String> {
+        let seconds = duration.num_milliseconds() as f64 / 1000.0;
+        match seconds.fract() == 0.0 {
+            true => Self::new(seconds, "s"),
+            false => Self::new(duration.num_milliseconds() as f64, "ms"),
+        }
+    }
+    pub fn quantity(&self) -> &FhirQuantity {
+        &self.0
+    }
+    pub fn seconds(&self) -> Result<f64, String> {
+        if self.0.system.as_deref() != Some(UCUM_SYSTEM) {
+            return Err("Duration must use the UCUM system".to_string());
+        }
+        let code = self.0.code.as_deref().ok_or("Duration has no unit code")?;
+        let factor =
+            units::ucum_time_unit_seconds(code).ok_or(format!("Not a UCUM time unit: {}", code))?;
+        let value = self.0.value.ok_or("Duration has no value")?.value();
+        Ok(value * factor)
+    }
+    pub fn to_chrono(&self) -> Result<chrono::Duration, String> {
+        let milliseconds = (self.seconds()? * 1000.0).round();
+        if milliseconds.abs() > i64::MAX as f64 {
+            return Err("Duration out of range".to_string());
+        }
+        Ok(chrono::Duration::milliseconds(milliseconds as i64))
+    }
+}
+
+// https://www.hl7.org/fhir/datatypes.html#Range
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirRange {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub low: Option<FhirQuantity>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub high: Option<FhirQuantity>,
+}
+impl FhirRange {
+    pub fn new(low: Option<FhirQuantity>, high: Option<FhirQuantity>) -> Result<Self, String> {
+        let range = Self { low, high };
+        range.validate()?;
+        Ok(range)
+    }
+    // rng-2: if present, low SHALL have a lower value than high
+    pub fn validate(&self) -> Result<(), String> {
+        for bound in [&self.low, &self.high].into_iter().flatten() {
+            bound.validate()?;
+            if bound.comparator.is_some() {
+                return Err(
+                    "Range bounds are SimpleQuantities and cannot have a comparator".to_string(),
+                );
+            }
+        }
+        if let (Some(low), Some(high)) = (&self.low, &self.high) {
+            if !low.same_unit(high) {
+                return Err("Range bounds must use the same unit".to_string());
+            }
+            if let (Some(low), Some(high)) = (low.value, high.value) {
+                if low > high {
+                    return Err(format!("Range low {} is above high {}", low, high));
+                }
+            }
+        }
+        Ok(())
+    }
+    pub fn contains(&self, value: f64) -> bool {
+        let above_low = self
+            .low
+            .as_ref()
+            .and_then(|low| low.value)
+            .is_none_or(|low| low.value() <= value);
+        let below_high = self
+            .high
+            .as_ref()
+            .and_then(|high| high.value)
+            .is_none_or(|high| value <= high.value());
+        above_low && below_high
+    }
+}
+
+// https://www.hl7.org/fhir/datatypes.html#Ratio
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirRatio {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub numerator: Option<FhirQuantity>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub denominator: Option<FhirQuantity>,
+}
+impl FhirRatio {
+    pub fn new(numerator: FhirQuantity, denominator: FhirQuantity) -> Result<Self, String> {
+        let ratio = Self {
+            numerator: Some(numerator),
+            denominator: Some(denominator),
+        };
+        ratio.validate()?;
+        Ok(ratio)
+    }
+    // rat-1: numerator and denominator SHALL both be present, or both absent
+    pub fn validate(&self) -> Result<(), String> {
+        match (&self.numerator, &self.denominator) {
+            (Some(numerator), Some(denominator)) => {
+                numerator.validate()?;
+                denominator.validate()?;
+                if denominator.value.is_some_and(|value| value.value() == 0.0) {
+                    return Err("Ratio denominator is zero".to_string());
+                }
+                Ok(())
+            }
+            (None, None) => Ok(()),
+            _ => Err("Ratio needs both numerator and denominator".to_string()),
+        }
+    }
+    pub fn value(&self) -> Option<f64> {
+        let numerator = self.numerator.as_ref()?.value?.value();
+        let denominator = self.denominator.as_ref()?.value?.value();
+        (denominator != 0.0).then(|| numerator / denominator)
+    }
+}
+
+// https://www.hl7.org/fhir/datatypes.html#Money
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirMoney {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<FhirDecimal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+}
+impl FhirMoney {
+    pub fn new(value: f64, currency: &str) -> Result<Self, String> {
+        let money = Self {
+            value: Some(FhirDecimal::new(value)?),
+            currency: Some(currency.to_string()),
+        };
+        money.validate()?;
+        Ok(money)
+    }
+    pub fn validate(&self) -> Result<(), String> {
+        match &self.currency {
+            Some(currency) => validate_currency(currency),
+            None => Ok(()),
+        }
+    }
 }
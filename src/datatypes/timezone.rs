@@ -0,0 +1,143 @@
+use chrono::{DateTime, Datelike, LocalResult, NaiveDateTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+
+use super::{FhirDateTime, FhirInstant};
+use crate::locale::FhirLocalization;
+
+// Time zone support uses the IANA database compiled into chrono-tz, so no
+// system tz files are needed. Zones are given by name, e.g. "America/Caracas".
+pub fn parse_time_zone(name: &str) -> Result<Tz, String> {
+    name.parse::<Tz>()
+        .map_err(|_| format!("Unknown IANA time zone: {}", name))
+}
+
+impl FhirInstant {
+    // Wall-clock time in a zone. Ambiguous times (clocks going back) resolve to
+    // the earlier instant; times skipped by a DST jump are rejected.
+    pub fn from_local(local: NaiveDateTime, zone: &str) -> Result<Self, String> {
+        let tz = parse_time_zone(zone)?;
+        match tz.from_local_datetime(&local) {
+            LocalResult::Single(value) | LocalResult::Ambiguous(value, _) => {
+                Ok(Self::from_chrono(value.fixed_offset()).to_utc())
+            }
+            LocalResult::None => Err(format!("{} does not exist in {}", local, zone)),
+        }
+    }
+    // Parses "YYYY-MM-DD hh:mm" or "YYYY-MM-DDThh:mm:ss" as wall-clock time in a zone
+    pub fn parse_local(text: &str, zone: &str) -> Result<Self, String> {
+        let local = [
+            "%Y-%m-%d %H:%M",
+            "%Y-%m-%dT%H:%M",
+            "%Y-%m-%d %H:%M:%S",
+            "%Y-%m-%dT%H:%M:%S",
+        ]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .ok_or(format!("Invalid local date time: {}", text))?;
+        Self::from_local(local, zone)
+    }
+    pub fn to_utc(&self) -> Self {
+        Self::from_chrono(self.to_chrono().with_timezone(&Utc).fixed_offset())
+    }
+    pub fn in_zone(&self, zone: &str) -> Result<DateTime<Tz>, String> {
+        Ok(self.to_chrono().with_timezone(&parse_time_zone(zone)?))
+    }
+    // Long human readable form in the zone, e.g. "lunes, 4 de marzo de 2024, 09:30 -04"
+    pub fn format_local(&self, zone: &str, language: &str) -> Result<String, String> {
+        self.format_local_with(zone, FhirLocalization::defaults(), language)
+    }
+    // Month and weekday names come from the catalogs of `localization`
+    pub fn format_local_with(
+        &self,
+        zone: &str,
+        localization: &FhirLocalization,
+        language: &str,
+    ) -> Result<String, String> {
+        Ok(format_long(&self.in_zone(zone)?, localization, language))
+    }
+    pub fn format_local_time(&self, zone: &str, language: &str) -> Result<String, String> {
+        self.format_local_time_with(zone, FhirLocalization::defaults(), language)
+    }
+    pub fn format_local_time_with(
+        &self,
+        zone: &str,
+        localization: &FhirLocalization,
+        language: &str,
+    ) -> Result<String, String> {
+        Ok(format_time(&self.in_zone(zone)?, localization, language))
+    }
+    // Long form in the time zone of `localization`, as shown in narratives
+    pub fn format_narrative(&self, localization: &FhirLocalization, language: &str) -> String {
+        self.format_local_with(localization.get_time_zone(), localization, language)
+            .unwrap_or_else(|_| self.to_string())
+    }
+}
+
+impl FhirDateTime {
+    pub fn to_utc(&self) -> Self {
+        match self.precision() {
+            super::FhirDateTimePrecision::Second => {
+                FhirInstant::from_chrono(self.to_chrono()).to_utc().into()
+            }
+            _ => *self,
+        }
+    }
+}
+
+// Month and weekday names are narrative labels, translated by the locale
+// catalogs like any other label
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+// Date and time patterns are labels as well, so each catalog decides the
+// order of the parts and whether the clock has 12 or 24 hours
+const DATE_TIME_PATTERN: &str = "{weekday}, {month} {day}, {year}, {time}";
+const TIME_PATTERN: &str = "{hour12}:{minute} {period} {zone}";
+
+fn format_long(value: &DateTime<Tz>, localization: &FhirLocalization, language: &str) -> String {
+    let weekday = localization.label(
+        WEEKDAYS[value.weekday().num_days_from_monday() as usize],
+        language,
+    );
+    let month = localization.label(MONTHS[value.month0() as usize], language);
+    localization
+        .label(DATE_TIME_PATTERN, language)
+        .replace("{weekday}", weekday)
+        .replace("{month}", month)
+        .replace("{day}", &value.day().to_string())
+        .replace("{year}", &value.year().to_string())
+        .replace("{time}", &format_time(value, localization, language))
+}
+
+fn format_time(value: &DateTime<Tz>, localization: &FhirLocalization, language: &str) -> String {
+    let (pm, hour12) = value.hour12();
+    let period = localization.label(if pm { "PM" } else { "AM" }, language);
+    localization
+        .label(TIME_PATTERN, language)
+        .replace("{hour12}", &hour12.to_string())
+        .replace("{hour}", &format!("{:02}", value.hour()))
+        .replace("{minute}", &format!("{:02}", value.minute()))
+        .replace("{period}", period)
+        .replace("{zone}", &value.format("%Z").to_string())
+}
@@ -0,0 +1,56 @@
+use super::{FhirExtension, FhirTypedExtension, FhirValue};
+
+// https://www.hl7.org/fhir/extensions/StructureDefinition-translation.html
+pub const FHIR_EXTENSION_TRANSLATION: &str = "http://hl7.org/fhir/StructureDefinition/translation";
+
+// A string element in another language, e.g. a coding's display in Spanish
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FhirTranslation {
+    language: String,
+    content: String,
+}
+impl FhirTranslation {
+    pub fn new(language: &str, content: &str) -> Self {
+        Self {
+            language: language.to_string(),
+            content: content.to_string(),
+        }
+    }
+    pub fn get_language(&self) -> &str {
+        &self.language
+    }
+    pub fn get_content(&self) -> &str {
+        &self.content
+    }
+}
+impl FhirTypedExtension for FhirTranslation {
+    const URL: &'static str = FHIR_EXTENSION_TRANSLATION;
+    fn to_extension(&self) -> FhirExtension {
+        FhirExtension::new_complex(
+            Self::URL,
+            vec![
+                FhirExtension::new_value("lang", FhirValue::Code(self.language.clone())),
+                FhirExtension::new_value("content", FhirValue::String(self.content.clone())),
+            ],
+        )
+    }
+    fn from_extension(extension: &FhirExtension) -> Result<Self, String> {
+        let language = match extension
+            .get_extension("lang")
+            .and_then(|part| part.get_value())
+        {
+            Some(FhirValue::Code(language)) => language,
+            _ => return Err("Translation is missing its lang code".to_string()),
+        };
+        // R5 also allows markdown content
+        match extension
+            .get_extension("content")
+            .and_then(|part| part.get_value())
+        {
+            Some(FhirValue::String(content) | FhirValue::Markdown(content)) => {
+                Ok(Self::new(language, content))
+            }
+            _ => Err("Translation is missing its content".to_string()),
+        }
+    }
+}
@@ -0,0 +1,288 @@
+// Validation tables for unit and currency codes.
+// UCUM: https://ucum.org/ucum  ISO 4217: https://www.iso.org/iso-4217-currency-codes.html
+
+pub const UCUM_SYSTEM: &str = "http://unitsofmeasure.org";
+pub const ISO_4217_SYSTEM: &str = "urn:iso:std:iso:4217";
+
+const UCUM_PREFIXES: [&str; 24] = [
+    "Y", "Z", "E", "P", "T", "G", "M", "k", "h", "da", "d", "c", "m", "u", "n", "p", "f", "a", "z",
+    "y", "Ki", "Mi", "Gi", "Ti",
+];
+
+// (atom, accepts metric prefixes)
+const UCUM_ATOMS: [(&str, bool); 92] = [
+    ("m", true),
+    ("s", true),
+    ("g", true),
+    ("rad", true),
+    ("K", true),
+    ("C", true),
+    ("cd", true),
+    ("mol", true),
+    ("sr", true),
+    ("Hz", true),
+    ("N", true),
+    ("Pa", true),
+    ("J", true),
+    ("W", true),
+    ("A", true),
+    ("V", true),
+    ("F", true),
+    ("Ohm", true),
+    ("S", true),
+    ("Wb", true),
+    ("T", true),
+    ("H", true),
+    ("Cel", true),
+    ("lm", true),
+    ("lx", true),
+    ("Bq", true),
+    ("Gy", true),
+    ("Sv", true),
+    ("kat", true),
+    ("U", true),
+    ("L", true),
+    ("l", true),
+    ("t", true),
+    ("bar", true),
+    ("eq", true),
+    ("osm", true),
+    ("cal", true),
+    ("B", true),
+    ("dB", false),
+    ("Np", true),
+    ("bit", true),
+    ("By", true),
+    ("Ci", true),
+    ("R", true),
+    ("gf", true),
+    ("u", true),
+    ("eV", true),
+    ("st", true),
+    ("Ao", false),
+    ("b", true),
+    ("atm", false),
+    ("min", false),
+    ("h", false),
+    ("d", false),
+    ("wk", false),
+    ("mo", false),
+    ("a", false),
+    ("ar", true),
+    ("deg", false),
+    ("%", false),
+    ("mm[Hg]", false),
+    ("cm[H2O]", false),
+    ("m[H2O]", true),
+    ("m[Hg]", true),
+    ("[iU]", true),
+    ("[IU]", true),
+    ("[arb'U]", false),
+    ("[USP'U]", false),
+    ("[ppth]", false),
+    ("[ppm]", false),
+    ("[ppb]", false),
+    ("[pptr]", false),
+    ("[pH]", false),
+    ("[pi]", false),
+    ("[in_i]", false),
+    ("[ft_i]", false),
+    ("[yd_i]", false),
+    ("[mi_i]", false),
+    ("[lb_av]", false),
+    ("[oz_av]", false),
+    ("[degF]", false),
+    ("[Cal]", false),
+    ("[drp]", false),
+    ("[gal_us]", false),
+    ("[foz_us]", false),
+    ("[tbs_us]", false),
+    ("[tsp_us]", false),
+    ("[cup_us]", false),
+    ("[HPF]", false),
+    ("[LPF]", false),
+    ("[CFU]", false),
+    ("[beth'U]", false),
+];
+
+// Structural UCUM check: terms joined by '.' and '/', each an (optionally
+// prefixed) atom with an integer exponent, a factor, an annotation or a
+// parenthesised term. Atoms outside the table above are rejected.
+pub fn validate_ucum(code: &str) -> Result<(), String> {
+    let invalid = |reason: &str| Err(format!("Invalid UCUM unit \"{}\": {}", code, reason));
+    if code.is_empty() || !code.is_ascii() || code.contains(char::is_whitespace) {
+        return invalid("must be non-empty ASCII without spaces");
+    }
+    let mut parser = UcumParser {
+        input: code.as_bytes(),
+        position: 0,
+    };
+    if let Err(reason) = parser.term(true) {
+        return invalid(&reason);
+    }
+    if parser.position != code.len() {
+        return invalid("unexpected trailing characters");
+    }
+    Ok(())
+}
+
+struct UcumParser<'a> {
+    input: &'a [u8],
+    position: usize,
+}
+impl UcumParser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.position).copied()
+    }
+    fn rest(&self) -> &str {
+        std::str::from_utf8(&self.input[self.position..]).unwrap_or_default()
+    }
+    fn term(&mut self, top_level: bool) -> Result<(), String> {
+        if top_level && self.peek() == Some(b'/') {
+            self.position += 1;
+        }
+        self.component()?;
+        while let Some(b'.' | b'/') = self.peek() {
+            self.position += 1;
+            self.component()?;
+        }
+        Ok(())
+    }
+    fn component(&mut self) -> Result<(), String> {
+        match self.peek() {
+            Some(b'(') => {
+                self.position += 1;
+                self.term(false)?;
+                if self.peek() != Some(b')') {
+                    return Err("unbalanced parenthesis".to_string());
+                }
+                self.position += 1;
+                Ok(())
+            }
+            Some(b'{') => self.annotation(),
+            Some(b'0'..=b'9')
+                if !self.rest().starts_with("10*") && !self.rest().starts_with("10^") =>
+            {
+                while let Some(b'0'..=b'9') = self.peek() {
+                    self.position += 1;
+                }
+                Ok(())
+            }
+            Some(_) => {
+                self.simple_unit()?;
+                self.exponent();
+                if self.peek() == Some(b'{') {
+                    self.annotation()?;
+                }
+                Ok(())
+            }
+            None => Err("missing unit after operator".to_string()),
+        }
+    }
+    fn annotation(&mut self) -> Result<(), String> {
+        let end = self.rest().find('}').ok_or("unterminated annotation")?;
+        self.position += end + 1;
+        Ok(())
+    }
+    fn exponent(&mut self) {
+        let start = self.position;
+        if let Some(b'+' | b'-') = self.peek() {
+            self.position += 1;
+        }
+        let digits = self.position;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.position += 1;
+        }
+        if self.position == digits {
+            self.position = start;
+        }
+    }
+    fn simple_unit(&mut self) -> Result<(), String> {
+        let rest = self.rest();
+        for power in ["10*", "10^"] {
+            if rest.starts_with(power) {
+                self.position += power.len();
+                return Ok(());
+            }
+        }
+        let mut matched = UCUM_ATOMS
+            .iter()
+            .filter(|(atom, _)| rest.starts_with(atom) && at_boundary(rest, atom.len()))
+            .map(|(atom, _)| atom.len())
+            .max();
+        if matched.is_none() {
+            matched = UCUM_PREFIXES
+                .iter()
+                .filter(|prefix| rest.starts_with(**prefix))
+                .flat_map(|prefix| {
+                    let after = &rest[prefix.len()..];
+                    UCUM_ATOMS
+                        .iter()
+                        .filter(move |(atom, metric)| {
+                            *metric && after.starts_with(atom) && at_boundary(after, atom.len())
+                        })
+                        .map(move |(atom, _)| prefix.len() + atom.len())
+                })
+                .max();
+        }
+        match matched {
+            Some(length) => {
+                self.position += length;
+                Ok(())
+            }
+            None => Err(format!("unknown unit at \"{}\"", rest)),
+        }
+    }
+}
+
+// An atom only matches if it is followed by an operator, exponent or annotation
+fn at_boundary(text: &str, length: usize) -> bool {
+    text.as_bytes().get(length).is_none_or(|next| {
+        matches!(
+            next,
+            b'.' | b'/' | b'(' | b')' | b'{' | b'+' | b'-' | b'0'..=b'9'
+        )
+    })
+}
+
+// Active ISO 4217 alphabetic codes
+const ISO_4217_CODES: [&str; 177] = [
+    "AED", "AFN", "ALL", "AMD", "ANG", "AOA", "ARS", "AUD", "AWG", "AZN", "BAM", "BBD", "BDT",
+    "BGN", "BHD", "BIF", "BMD", "BND", "BOB", "BOV", "BRL", "BSD", "BTN", "BWP", "BYN", "BZD",
+    "CAD", "CDF", "CHE", "CHF", "CHW", "CLF", "CLP", "CNY", "COP", "COU", "CRC", "CUC", "CUP",
+    "CVE", "CZK", "DJF", "DKK", "DOP", "DZD", "EGP", "ERN", "ETB", "EUR", "FJD", "FKP", "GBP",
+    "GEL", "GHS", "GIP", "GMD", "GNF", "GTQ", "GYD", "HKD", "HNL", "HTG", "HUF", "IDR", "ILS",
+    "INR", "IQD", "IRR", "ISK", "JMD", "JOD", "JPY", "KES", "KGS", "KHR", "KMF", "KPW", "KRW",
+    "KWD", "KYD", "KZT", "LAK", "LBP", "LKR", "LRD", "LSL", "LYD", "MAD", "MDL", "MGA", "MKD",
+    "MMK", "MNT", "MOP", "MRU", "MUR", "MVR", "MWK", "MXN", "MXV", "MYR", "MZN", "NAD", "NGN",
+    "NIO", "NOK", "NPR", "NZD", "OMR", "PAB", "PEN", "PGK", "PHP", "PKR", "PLN", "PYG", "QAR",
+    "RON", "RSD", "RUB", "RWF", "SAR", "SBD", "SCR", "SDG", "SEK", "SGD", "SHP", "SLE", "SOS",
+    "SRD", "SSP", "STN", "SVC", "SYP", "SZL", "THB", "TJS", "TMT", "TND", "TOP", "TRY", "TTD",
+    "TWD", "TZS", "UAH", "UGX", "USD", "USN", "UYI", "UYU", "UYW", "UZS", "VED", "VES", "VND",
+    "VUV", "WST", "XAF", "XAG", "XAU", "XBA", "XBB", "XCD", "XDR", "XOF", "XPD", "XPF", "XPT",
+    "XSU", "XUA", "YER", "ZAR", "ZMW", "ZWG", "XTS", "XXX",
+];
+
+pub fn validate_currency(code: &str) -> Result<(), String> {
+    if ISO_4217_CODES.contains(&code) {
+        Ok(())
+    } else {
+        Err(format!("Unknown ISO 4217 currency code: {}", code))
+    }
+}
+
+// UCUM time units accepted by the FHIR Duration profile, in seconds. Months and
+// years use the UCUM mean Julian values.
+pub(crate) fn ucum_time_unit_seconds(code: &str) -> Option<f64> {
+    match code {
+        "ms" => Some(0.001),
+        "s" => Some(1.0),
+        "min" => Some(60.0),
+        "h" => Some(3_600.0),
+        "d" => Some(86_400.0),
+        "wk" => Some(604_800.0),
+        "mo" => Some(2_629_800.0),
+        "a" => Some(31_557_600.0),
+        _ => None,
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+// Members this version does not model, such as elements added by newer
+// clients. Flattened into a resource they are read and written back as they
+// were, so re-signing an updated copy keeps them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FhirUnknownElements(Map<String, Value>);
+impl FhirUnknownElements {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    // Leaves out the members of choice elements such as valueQuantity, which
+    // the choice fields read from the same flattened map
+    pub fn without_choices(mut members: Map<String, Value>, choices: &[&str]) -> Self {
+        members.retain(|key, _| {
+            let key = key.strip_prefix('_').unwrap_or(key);
+            !choices.iter().any(|choice| {
+                key.strip_prefix(choice).is_some_and(|type_name| {
+                    type_name.starts_with(|c: char| c.is_ascii_uppercase())
+                })
+            })
+        });
+        Self(members)
+    }
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.0.get(name)
+    }
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn insert(&mut self, name: &str, value: Value) -> Option<Value> {
+        self.0.insert(name.to_string(), value)
+    }
+    pub fn remove(&mut self, name: &str) -> Option<Value> {
+        self.0.shift_remove(name)
+    }
+}
+
+// Generates a serde `with` module for the unknown members of a struct that
+// also has choice elements, used with `#[serde(flatten, with = "module")]`:
+//
+//     fhir_unknown_field!(observation_unknown, ["value", "effective"]);
+#[macro_export]
+macro_rules! fhir_unknown_field {
+    ($module:ident, [$($choice:expr),* $(,)?]) => {
+        pub mod $module {
+            pub fn serialize<S: serde::Serializer>(
+                unknown: &$crate::datatypes::FhirUnknownElements,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                serde::Serialize::serialize(unknown, serializer)
+            }
+            pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<$crate::datatypes::FhirUnknownElements, D::Error> {
+                use serde::Deserialize;
+                let members = serde_json::Map::<String, serde_json::Value>::deserialize(deserializer)?;
+                Ok($crate::datatypes::FhirUnknownElements::without_choices(
+                    members,
+                    &[$($choice),*],
+                ))
+            }
+        }
+    };
+}
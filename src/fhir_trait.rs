@@ -2,24 +2,72 @@ use nostro2::{
     notes::{Note, SignedNote},
     userkeys::UserKeys,
 };
+use quick_xml::escape::partial_escape;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json::{json, Value};
 
+pub use ignis_nostr_derive::FhirResource;
+
+use crate::{
+    consts::IGNIS_SCHEMA_VERSION,
+    datatypes::{FhirCodeableConcept, FhirHumanName, FhirPeriod, FhirUnknownElements},
+    locale::{FhirLocalization, DEFAULT_LANGUAGE},
+    narrative::{join_languages, sanitize_xhtml, split_languages, FhirNarrative},
+    resources::{
+        operation_outcome::{FhirOperationOutcome, FhirValidationMode},
+        from_fhir_version,
+        organization::FhirOrganization,
+        to_fhir_version, FhirConversion, FhirElementDefinition, FhirResourceDefinition,
+        FhirVersion, IgnisPayload,
+    },
+    terminology::language_matches,
+    valuesets::{FhirIdentifierType, FhirIdentifierUse},
+    xml::{from_json, json_to_xml, xml_to_json_with},
+};
+
+// https://www.hl7.org/fhir/references.html#Reference
+// Every element is optional. FHIR gives a reference one identifier, written as
+// an object; notes from earlier versions hold a list of them.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FhirReference<T> {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     reference: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    reference_type: Option<T>,
+    #[serde(
+        default,
+        serialize_with = "serialize_reference_identifiers",
+        deserialize_with = "deserialize_reference_identifiers",
+        skip_serializing_if = "Vec::is_empty"
+    )]
     identifier: Vec<FhirIdentifier>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     display: String,
-    #[serde(rename = "type")]
-    reference_type: Option<T>,
 }
-impl<'a, T> FhirReference<T>
+impl<T> FhirReference<T> {
+    // A plain "Patient/123" reference, as found in resources from other servers
+    pub fn literal(reference: &str, display: &str) -> Self {
+        Self {
+            reference: reference.to_string(),
+            identifier: vec![],
+            display: display.to_string(),
+            reference_type: None,
+        }
+    }
+    pub fn get_literal(&self) -> &str {
+        &self.reference
+    }
+    pub fn get_display(&self) -> &str {
+        &self.display
+    }
+}
+impl<T> FhirReference<T>
 where
     T: FhirResource + Serialize +  Sized + Clone + DeserializeOwned,
 {
     pub fn new(new_reference: T, reference_id: String, identifier: Vec<FhirIdentifier>) -> Self {
         let reference = format!("{}/{}", new_reference.resource_type(), reference_id);
-        let display = new_reference.text().div.clone();
+        let display = new_reference.display();
         let reference_type = Some(new_reference);
         Self {
             reference,
@@ -32,31 +80,204 @@ where
         self.reference_type.clone()
     }
 }
+fn serialize_reference_identifiers<S: serde::Serializer>(
+    identifiers: &[FhirIdentifier],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match identifiers {
+        [identifier] => identifier.serialize(serializer),
+        identifiers => identifiers.serialize(serializer),
+    }
+}
+
+fn deserialize_reference_identifiers<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<FhirIdentifier>, D::Error> {
+    match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => Ok(vec![]),
+        Some(Value::Array(items)) => {
+            serde_json::from_value(Value::Array(items)).map_err(serde::de::Error::custom)
+        }
+        Some(identifier) => serde_json::from_value(identifier)
+            .map(|identifier| vec![identifier])
+            .map_err(serde::de::Error::custom),
+    }
+}
+
+// https://www.hl7.org/fhir/datatypes.html#Identifier
+// Every element is optional; an empty system or value is left out of the JSON.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FhirIdentifier {
+    #[serde(rename = "use", default, skip_serializing_if = "Option::is_none")]
+    identifier_use: Option<FhirIdentifierUse>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    identifier_type: Option<FhirCodeableConcept>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     system: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    period: Option<FhirPeriod>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    assigner: Option<Box<FhirReference<FhirOrganization>>>,
 }
 impl FhirIdentifier {
     pub fn new(system: String, value: String) -> Self {
-        Self { system, value }
+        Self {
+            identifier_use: None,
+            identifier_type: None,
+            system,
+            value,
+            period: None,
+            assigner: None,
+        }
+    }
+    pub fn with_use(mut self, identifier_use: FhirIdentifierUse) -> Self {
+        self.identifier_use = Some(identifier_use);
+        self
+    }
+    pub fn with_type(mut self, identifier_type: impl Into<FhirCodeableConcept>) -> Self {
+        self.identifier_type = Some(identifier_type.into());
+        self
+    }
+    pub fn with_period(mut self, period: FhirPeriod) -> Self {
+        self.period = Some(period);
+        self
+    }
+    pub fn with_assigner(mut self, assigner: FhirReference<FhirOrganization>) -> Self {
+        self.assigner = Some(Box::new(assigner));
+        self
+    }
+    pub fn get_use(&self) -> Option<FhirIdentifierUse> {
+        self.identifier_use
+    }
+    pub fn get_type(&self) -> Option<&FhirCodeableConcept> {
+        self.identifier_type.as_ref()
+    }
+    pub fn get_system(&self) -> &str {
+        &self.system
+    }
+    pub fn get_value(&self) -> &str {
+        &self.value
+    }
+    pub fn get_period(&self) -> Option<&FhirPeriod> {
+        self.period.as_ref()
+    }
+    pub fn get_assigner(&self) -> Option<&FhirReference<FhirOrganization>> {
+        self.assigner.as_deref()
+    }
+    pub fn is_type(&self, identifier_type: FhirIdentifierType) -> bool {
+        self.identifier_type
+            .as_ref()
+            .is_some_and(|concept| concept.decode_all().contains(&identifier_type))
+    }
+    // Identifier values are case sensitive, so only the system is compared loosely
+    pub fn matches(&self, system: &str, value: &str) -> bool {
+        self.system.trim_end_matches('/') == system.trim_end_matches('/') && self.value == value
+    }
+    // Old identifiers and those outside their period are kept for history only
+    pub fn is_active(&self) -> bool {
+        self.identifier_use != Some(FhirIdentifierUse::Old)
+            && self.period.as_ref().is_none_or(|period| period.is_active_now())
     }
 }
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// https://www.hl7.org/fhir/narrative.html
+// Narratives read from JSON are sanitized, so one received from elsewhere
+// never carries scripts or other markup FHIR does not allow
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedFhirText")]
 pub struct FhirText {
     status: String,
     div: String,
 }
 impl FhirText {
+    // The text is escaped so the div stays well-formed XHTML
     pub fn new(div_text: String) -> Self {
         Self {
             status: "generated".to_string(),
             div: format!(
                 "<div xmlns=\"http://www.w3.org/1999/xhtml\">{}</div>",
-                div_text
+                partial_escape(div_text.as_str())
             ),
         }
     }
+    // Markup from elsewhere, sanitized with `sanitize_xhtml`
+    pub fn from_xhtml(status: &str, div: &str) -> Result<Self, String> {
+        Ok(Self {
+            status: status.to_string(),
+            div: sanitize_xhtml(div)?,
+        })
+    }
+    pub fn get_status(&self) -> &str {
+        &self.status
+    }
+    pub fn get_div(&self) -> &str {
+        &self.div
+    }
+    // One narrative holding each text as a section in its language, the
+    // first being the resource's own language
+    pub fn from_languages(texts: &[(&str, FhirText)]) -> Result<Self, String> {
+        let divs: Vec<(&str, &str)> = texts
+            .iter()
+            .map(|(language, text)| (*language, text.div.as_str()))
+            .collect();
+        Ok(Self {
+            status: texts
+                .first()
+                .map_or("generated".to_string(), |(_, text)| text.status.clone()),
+            div: join_languages(&divs)?,
+        })
+    }
+    // Languages of the sections; empty for a narrative in a single language
+    pub fn get_languages(&self) -> Vec<String> {
+        split_languages(&self.div)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(language, _)| language)
+            .collect()
+    }
+    // The section in `language`, else one in a related tag such as "es" for
+    // "es-VE", else the first. A narrative in a single language is returned
+    // as it is.
+    pub fn in_language(&self, language: &str) -> FhirText {
+        let sections = split_languages(&self.div).unwrap_or_default();
+        let section = sections
+            .iter()
+            .find(|(own, _)| own.eq_ignore_ascii_case(language))
+            .or_else(|| sections.iter().find(|(own, _)| language_matches(own, language)))
+            .or(sections.first());
+        match section {
+            Some((_, div)) => Self {
+                status: self.status.clone(),
+                div: div.clone(),
+            },
+            None => self.clone(),
+        }
+    }
+}
+impl From<String> for FhirText {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+impl From<FhirNarrative> for FhirText {
+    fn from(narrative: FhirNarrative) -> Self {
+        Self {
+            status: "generated".to_string(),
+            div: narrative.to_xhtml(),
+        }
+    }
+}
+#[derive(Deserialize)]
+struct UncheckedFhirText {
+    status: String,
+    div: String,
+}
+impl TryFrom<UncheckedFhirText> for FhirText {
+    type Error = String;
+    fn try_from(text: UncheckedFhirText) -> Result<Self, String> {
+        Self::from_xhtml(&text.status, &text.div)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -64,6 +285,14 @@ pub struct IgnisResource<T> {
     resource: T,
     signed_note: Option<SignedNote>,
     relay: Option<String>,
+    #[serde(skip)]
+    validation: FhirValidationMode,
+    // Issues found the last time the resource was validated
+    #[serde(skip)]
+    outcome: FhirOperationOutcome,
+    // Payload schema the note was written with
+    #[serde(default)]
+    schema_version: u32,
 }
 impl<T> IgnisResource<T>
 where
@@ -74,17 +303,46 @@ where
             resource,
             signed_note,
             relay,
+            validation: FhirValidationMode::default(),
+            outcome: FhirOperationOutcome::new(),
+            schema_version: IGNIS_SCHEMA_VERSION,
         }
     }
+    // Validation runs before signing; `Enforce` by default
+    pub fn with_validation(mut self, validation: FhirValidationMode) -> Self {
+        self.validation = validation;
+        self
+    }
+    pub fn validate(&self) -> FhirOperationOutcome {
+        self.resource.validate()
+    }
+    pub fn get_validation_outcome(&self) -> &FhirOperationOutcome {
+        &self.outcome
+    }
     pub fn get_resource(&self) -> &T {
         &self.resource
     }
+    // For updating a decoded resource before signing it again
+    pub fn get_resource_mut(&mut self) -> &mut T {
+        &mut self.resource
+    }
+    // Members of the resource this version does not model
+    pub fn get_unknown_elements(&self) -> Option<&FhirUnknownElements> {
+        self.resource.unknown_elements()
+    }
+    // 0 for notes written before payloads were versioned
+    pub fn get_schema_version(&self) -> u32 {
+        self.schema_version
+    }
+    // Written by a newer client: members this version does not know are kept
+    // as they are, but may carry meaning it cannot check
+    pub fn is_newer_schema(&self) -> bool {
+        self.schema_version > IGNIS_SCHEMA_VERSION
+    }
     pub fn get_pubkey(&self) -> Option<String> {
-        if let Some(note) = &self.signed_note {
-            Some(note.get_pubkey().to_string())
-        } else {
-            None
-        }
+        self.signed_note
+            .as_ref()
+            .map(|note| note.get_pubkey().to_string())
     }
     pub fn get_signed_note(&self) -> Option<&SignedNote> {
         self.signed_note.as_ref()
@@ -93,11 +351,9 @@ where
         self.resource.resource_type()
     }
     pub fn get_resource_id(&self) -> Option<String> {
-        if let Some(note) = &self.signed_note {
-            Some(note.get_id().to_string())
-        } else {
-            None
-        }
+        self.signed_note
+            .as_ref()
+            .map(|note| note.get_id().to_string())
     }
     pub fn get_resource_identifier(&self) -> FhirIdentifier {
         let relay = match &self.relay {
@@ -112,6 +368,10 @@ where
     pub fn get_resource_text(&self) -> FhirText {
         self.resource.text()
     }
+    pub fn get_localized_text(&self, language: &str) -> FhirText {
+        self.resource
+            .localized_text(FhirLocalization::defaults(), language)
+    }
     pub fn get_resource_reference(&self) -> Result<FhirReference<T>, String> {
         if let Some(id) = self.get_resource_id() {
             Ok(FhirReference::new(
@@ -124,12 +384,21 @@ where
         }
     }
     pub fn get_fhir_json(&self) -> Result<Value, String> {
-        let resource_map = serde_json::to_value(&self.resource).map_err(|e| e.to_string())?;
+        let mut resource_map =
+            serde_json::to_value(&self.resource).map_err(|e| e.to_string())?;
         if let Some(id) = self.get_resource_id() {
+            // The nostr identifier goes first, followed by any business identifiers
+            let mut identifiers = vec![serde_json::to_value(self.get_resource_identifier())
+                .map_err(|e| e.to_string())?];
+            if let Some(Value::Array(resource_identifiers)) =
+                resource_map.as_object_mut().unwrap().shift_remove("identifier")
+            {
+                identifiers.extend(resource_identifiers);
+            }
             let mut resource_json = json!({
                 "resourceType": self.resource.resource_type(),
                 "id": id,
-                "identifier": self.get_resource_identifier(),
+                "identifier": identifiers,
                 "text": self.get_resource_text(),
             });
             resource_json
@@ -141,32 +410,278 @@ where
             Err("Resource has not been signed yet.".to_string())
         }
     }
-    pub fn sign_data(&mut self, user_keys: &UserKeys) -> SignedNote {
-        self.signed_note = Some(self.resource.sign_data(user_keys));
-        self.signed_note.clone().unwrap()
+    // The resource as FHIR JSON for readers of several languages, the first
+    // being the resource's language: the narrative has a section for each one
+    // and coding displays carry their translations
+    pub fn get_fhir_json_in(&self, languages: &[&str]) -> Result<Value, String> {
+        self.get_fhir_json_in_with(FhirLocalization::defaults(), languages)
+    }
+    pub fn get_fhir_json_in_with(
+        &self,
+        localization: &FhirLocalization,
+        languages: &[&str],
+    ) -> Result<Value, String> {
+        let mut resource_json = self.get_fhir_json()?;
+        let Some(language) = languages.first() else {
+            return Ok(resource_json);
+        };
+        let text = match languages {
+            [_] => self.resource.localized_text(localization, language),
+            _ => {
+                let texts: Vec<(&str, FhirText)> = languages
+                    .iter()
+                    .map(|language| (*language, self.resource.localized_text(localization, language)))
+                    .collect();
+                FhirText::from_languages(&texts)?
+            }
+        };
+        let map = resource_json.as_object_mut().unwrap();
+        let index = map.keys().position(|key| key == "text").unwrap_or(map.len());
+        map.shift_insert(index, "language".to_string(), Value::String(language.to_string()));
+        map.insert(
+            "text".to_string(),
+            serde_json::to_value(text).map_err(|e| e.to_string())?,
+        );
+        localization.add_translations(&mut resource_json, languages);
+        Ok(resource_json)
+    }
+    pub fn get_fhir_xml_in(&self, languages: &[&str]) -> Result<String, String> {
+        json_to_xml(&self.get_fhir_json_in(languages)?)
+    }
+    // The resource as FHIR JSON of another version, with the issues found
+    // converting it from the R5 model
+    pub fn get_fhir_json_as(&self, version: FhirVersion) -> Result<FhirConversion<Value>, String> {
+        Ok(to_fhir_version(self.get_fhir_json()?, version))
+    }
+    pub fn get_fhir_xml(&self) -> Result<String, String> {
+        json_to_xml(&self.get_fhir_json()?)
+    }
+    pub fn get_fhir_xml_as(&self, version: FhirVersion) -> Result<FhirConversion<String>, String> {
+        let conversion = self.get_fhir_json_as(version)?;
+        let xml = json_to_xml(conversion.get_value())?;
+        Ok(FhirConversion::new(xml, conversion.get_outcome().clone()))
+    }
+    // Reads a resource from FHIR JSON of the given version, such as the output
+    // of get_fhir_json_as. The nostr identifier belongs to the signed note and
+    // is dropped.
+    pub fn resource_from_fhir_json(
+        json: Value,
+        version: FhirVersion,
+    ) -> Result<FhirConversion<T>, String> {
+        let conversion = from_fhir_version(json, version);
+        let outcome = conversion.get_outcome().clone();
+        let resource = Self::resource_from_json(conversion.into_value())?;
+        Ok(FhirConversion::new(resource, outcome))
+    }
+    pub fn resource_from_fhir_xml(
+        xml: &str,
+        version: FhirVersion,
+    ) -> Result<FhirConversion<T>, String> {
+        Self::resource_from_fhir_json(xml_to_json_with(xml, T::structure())?, version)
+    }
+    fn resource_from_json(mut json: Value) -> Result<T, String> {
+        let map = json.as_object_mut().ok_or("Resource must be a JSON object")?;
+        let resource_type = map.shift_remove("resourceType");
+        // Written by get_fhir_json from the note rather than kept in the resource
+        for element in ["id", "meta", "text"] {
+            map.shift_remove(element);
+        }
+        if let Some(Value::Array(identifiers)) = map.get_mut("identifier") {
+            identifiers.retain(|identifier| {
+                !identifier
+                    .get("system")
+                    .and_then(Value::as_str)
+                    .is_some_and(|system| system.starts_with("nostr:"))
+            });
+            if identifiers.is_empty() {
+                map.shift_remove("identifier");
+            }
+        }
+        let resource: T = from_json(&json)?;
+        match resource_type.as_ref().and_then(Value::as_str) == Some(resource.resource_type()) {
+            true => Ok(resource),
+            false => Err(format!("Expected a {} resource", resource.resource_type())),
+        }
+    }
+    pub fn sign_data(&mut self, user_keys: &UserKeys) -> Result<SignedNote, String> {
+        self.outcome = self.validation.run(|| self.resource.validate())?;
+        self.signed_note = Some(sign_payload(
+            &self.resource,
+            user_keys,
+            self.signing_schema_version(),
+        )?);
+        Ok(self.signed_note.clone().unwrap())
     }
     pub fn sign_encrypted_data(
         &mut self,
         user_keys: &UserKeys,
         pubkey: String,
     ) -> Result<SignedNote, String> {
-        self.signed_note = Some(self.resource.sign_encrypted_data(user_keys, pubkey)?);
+        self.outcome = self.validation.run(|| self.resource.validate())?;
+        self.signed_note = Some(sign_encrypted_payload(
+            &self.resource,
+            user_keys,
+            pubkey,
+            self.signing_schema_version(),
+        )?);
         Ok(self.signed_note.clone().unwrap())
     }
+    // A copy decoded from a newer schema keeps its version, since its unknown
+    // members are written back unchanged
+    fn signing_schema_version(&self) -> u32 {
+        self.schema_version.max(IGNIS_SCHEMA_VERSION)
+    }
     pub fn from_signed_note(
         signed_note: &SignedNote,
         relay: Option<String>,
-    ) -> Result<Self, String> {
-        let resource = T::from_signed_note(signed_note)?;
-        Ok(Self::new(resource, Some(signed_note.clone()), relay))
+    ) -> Result<Self, String>
+    where
+        T: FhirResourceDefinition,
+    {
+        Self::from_signed_note_with(signed_note, relay, FhirValidationMode::default())
+    }
+    // Use `Warn` to read notes signed before validation existed
+    pub fn from_signed_note_with(
+        signed_note: &SignedNote,
+        relay: Option<String>,
+        validation: FhirValidationMode,
+    ) -> Result<Self, String>
+    where
+        T: FhirResourceDefinition,
+    {
+        let (resource, schema_version) = from_payload(&signed_note.get_content())?;
+        Self::new(resource, Some(signed_note.clone()), relay).validated(validation, schema_version)
     }
     pub fn from_encrypted_note(
         signed_note: &SignedNote,
         user_keys: &UserKeys,
         relay: Option<String>,
-    ) -> Result<Self, String> {
-        let resource = T::from_encrypted_note(signed_note, user_keys)?;
-        Ok(Self::new(resource, Some(signed_note.clone()), relay))
+    ) -> Result<Self, String>
+    where
+        T: FhirResourceDefinition,
+    {
+        Self::from_encrypted_note_with(signed_note, user_keys, relay, FhirValidationMode::default())
+    }
+    pub fn from_encrypted_note_with(
+        signed_note: &SignedNote,
+        user_keys: &UserKeys,
+        relay: Option<String>,
+        validation: FhirValidationMode,
+    ) -> Result<Self, String>
+    where
+        T: FhirResourceDefinition,
+    {
+        let plaintext = user_keys
+            .decrypt_nip_44_content(signed_note)
+            .map_err(|e| e.to_string())?;
+        let (resource, schema_version) = from_payload(&plaintext)?;
+        Self::new(resource, Some(signed_note.clone()), relay).validated(validation, schema_version)
+    }
+    fn validated(mut self, validation: FhirValidationMode, schema_version: u32) -> Result<Self, String> {
+        self.outcome = validation.run(|| self.resource.validate())?;
+        self.validation = validation;
+        self.schema_version = schema_version;
+        Ok(self)
+    }
+}
+impl<T> IgnisResource<T>
+where
+    T: FhirResource + FhirIdentifiable + Serialize + Sized + Clone + DeserializeOwned,
+{
+    // Matches business identifiers as well as the nostr event identifier
+    pub fn has_identifier(&self, system: &str, value: &str) -> bool {
+        self.resource.has_identifier(system, value)
+            || self.get_resource_id().is_some_and(|_| {
+                self.get_resource_identifier().matches(system, value)
+            })
+    }
+    pub fn find_by_identifier<'a>(
+        store: &'a [IgnisResource<T>],
+        system: &str,
+        value: &str,
+    ) -> Option<&'a IgnisResource<T>> {
+        store
+            .iter()
+            .find(|resource| resource.has_identifier(system, value))
+    }
+}
+
+// Resources carrying business identifiers such as licences, national IDs or tax numbers
+pub trait FhirIdentifiable {
+    fn identifiers(&self) -> &[FhirIdentifier];
+    fn has_identifier(&self, system: &str, value: &str) -> bool {
+        self.identifiers()
+            .iter()
+            .any(|identifier| identifier.matches(system, value))
+    }
+    // Prefers an active identifier when a system has been reissued
+    fn find_identifier(&self, system: &str) -> Option<&FhirIdentifier> {
+        let mut matching = self
+            .identifiers()
+            .iter()
+            .filter(|identifier| {
+                identifier.get_system().trim_end_matches('/') == system.trim_end_matches('/')
+            })
+            .peekable();
+        let first = matching.peek().copied();
+        matching
+            .find(|identifier| identifier.is_active())
+            .or(first)
+    }
+    fn find_identifier_by_type(
+        &self,
+        identifier_type: FhirIdentifierType,
+    ) -> Option<&FhirIdentifier> {
+        self.identifiers()
+            .iter()
+            .find(|identifier| identifier.is_type(identifier_type) && identifier.is_active())
+    }
+}
+
+// Values written into narratives and event tags by `#[derive(FhirResource)]`
+pub trait FhirTextValue {
+    fn text_values(&self) -> Vec<String>;
+}
+macro_rules! display_text_value {
+    ($($ty:ty),*) => {
+        $(impl FhirTextValue for $ty {
+            fn text_values(&self) -> Vec<String> {
+                vec![self.to_string()]
+            }
+        })*
+    };
+}
+display_text_value!(bool, u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+impl FhirTextValue for String {
+    fn text_values(&self) -> Vec<String> {
+        match self.trim().is_empty() {
+            true => vec![],
+            false => vec![self.clone()],
+        }
+    }
+}
+impl<T: FhirTextValue> FhirTextValue for Option<T> {
+    fn text_values(&self) -> Vec<String> {
+        self.as_ref().map(T::text_values).unwrap_or_default()
+    }
+}
+impl<T: FhirTextValue> FhirTextValue for Vec<T> {
+    fn text_values(&self) -> Vec<String> {
+        self.iter().flat_map(T::text_values).collect()
+    }
+}
+impl FhirTextValue for FhirHumanName {
+    fn text_values(&self) -> Vec<String> {
+        self.display().text_values()
+    }
+}
+// The concept's codes, or its text when it has no codings
+impl FhirTextValue for FhirCodeableConcept {
+    fn text_values(&self) -> Vec<String> {
+        match self.coding.is_empty() {
+            true => self.text.text_values(),
+            false => self.coding.iter().map(|coding| coding.code.clone()).collect(),
+        }
     }
 }
 
@@ -176,43 +691,118 @@ where
 {
     fn nostr_kind(&self) -> u32;
     fn resource_type(&self) -> &str;
-    fn text(&self) -> FhirText;
-    fn sign_data(&self, user_keys: &UserKeys) -> SignedNote {
-        let new_note = Note::new(
-            &user_keys.get_public_key(),
-            self.nostr_kind(),
-            &serde_json::to_string(self).unwrap(),
-        );
-        user_keys.sign_nostr_event(new_note)
+    // Short plain text, used as the display of references to the resource;
+    // the resource type unless the resource has something better
+    fn display(&self) -> String {
+        self.resource_type().to_string()
+    }
+    // The resource's narrative, in English
+    fn text(&self) -> FhirText {
+        self.localized_text(FhirLocalization::defaults(), DEFAULT_LANGUAGE)
+    }
+    // The narrative with labels and codes in `language`; the display text
+    // unless the resource builds one
+    fn localized_text(&self, _localization: &FhirLocalization, _language: &str) -> FhirText {
+        FhirText::new(self.display())
+    }
+    // Cardinality, binding and invariant checks; resources without rules are always valid
+    fn validate(&self) -> FhirOperationOutcome {
+        FhirOperationOutcome::new()
+    }
+    // Event tags such as ["t", "cardiology"] that let relays filter resources.
+    // They are only added to public notes, never to encrypted ones.
+    fn tags(&self) -> Vec<Vec<String>> {
+        vec![]
+    }
+    // Elements of the resource's StructureDefinition, telling the FHIR XML reader
+    // which elements repeat and which are not strings; empty for hand-written ones
+    fn structure() -> &'static [FhirElementDefinition] {
+        &[]
+    }
+    // Release of the FHIR model the resource follows, written to note payloads
+    fn fhir_version() -> &'static str {
+        FhirVersion::R5.fhir_version()
+    }
+    // Members kept from a newer client's payload, for resources with a
+    // #[fhir(unknown)] field
+    fn unknown_elements(&self) -> Option<&FhirUnknownElements> {
+        None
+    }
+    fn sign_data(&self, user_keys: &UserKeys) -> Result<SignedNote, String> {
+        sign_payload(self, user_keys, IGNIS_SCHEMA_VERSION)
     }
     fn sign_encrypted_data(
         &self,
         user_keys: &UserKeys,
         pubkey: String,
     ) -> Result<SignedNote, String> {
-        let new_note = Note::new(
-            &user_keys.get_public_key(),
-            self.nostr_kind(),
-            &serde_json::to_string(self).unwrap(),
-        );
-        user_keys
-            .sign_nip_44_encrypted(new_note, pubkey)
-            .map_err(|e| e.to_string())
+        sign_encrypted_payload(self, user_keys, pubkey, IGNIS_SCHEMA_VERSION)
     }
     fn from_signed_note(
         signed_note: &SignedNote,
-    ) -> Result<Self, String> {
-        let resource: Self = serde_json::from_str(&signed_note.get_content()).map_err(|e| e.to_string())?;
-        Ok(resource)
+    ) -> Result<Self, String>
+    where
+        Self: FhirResourceDefinition,
+    {
+        from_payload(&signed_note.get_content()).map(|(resource, _)| resource)
     }
     fn from_encrypted_note(
         signed_note: &SignedNote,
         user_keys: &UserKeys,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, String>
+    where
+        Self: FhirResourceDefinition,
+    {
         let plaintext = user_keys
             .decrypt_nip_44_content(signed_note)
             .map_err(|e| e.to_string())?;
-        let resource: Self = serde_json::from_str(&plaintext).map_err(|e| e.to_string())?;
-        Ok(resource)
+        from_payload(&plaintext).map(|(resource, _)| resource)
     }
 }
+
+// Note content: the resource's JSON in an `IgnisPayload` envelope
+fn to_payload<T: FhirResource>(resource: &T, schema_version: u32) -> Result<String, String> {
+    let json = serde_json::to_value(resource).map_err(|e| e.to_string())?;
+    IgnisPayload::new(resource.resource_type(), T::fhir_version(), json)
+        .with_schema_version(schema_version)
+        .to_content()
+}
+
+// The resource in note content of any schema, upgraded to the current one, and
+// the schema version it was written with
+fn from_payload<T: FhirResourceDefinition>(content: &str) -> Result<(T, u32), String> {
+    let payload = IgnisPayload::from_content(content, T::RESOURCE_TYPE, T::fhir_version())?;
+    let schema_version = payload.get_schema_version();
+    let resource = serde_json::from_value(payload.into_resource()).map_err(|e| e.to_string())?;
+    Ok((resource, schema_version))
+}
+
+fn sign_payload<T: FhirResource>(
+    resource: &T,
+    user_keys: &UserKeys,
+    schema_version: u32,
+) -> Result<SignedNote, String> {
+    let mut new_note = Note::new(
+        &user_keys.get_public_key(),
+        resource.nostr_kind(),
+        &to_payload(resource, schema_version)?,
+    );
+    new_note.tags.extend(resource.tags());
+    Ok(user_keys.sign_nostr_event(new_note))
+}
+
+fn sign_encrypted_payload<T: FhirResource>(
+    resource: &T,
+    user_keys: &UserKeys,
+    pubkey: String,
+    schema_version: u32,
+) -> Result<SignedNote, String> {
+    let new_note = Note::new(
+        &user_keys.get_public_key(),
+        resource.nostr_kind(),
+        &to_payload(resource, schema_version)?,
+    );
+    user_keys
+        .sign_nip_44_encrypted(new_note, pubkey)
+        .map_err(|e| e.to_string())
+}
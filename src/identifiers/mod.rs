@@ -0,0 +1,92 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use crate::{
+    consts::{
+        FHIR_IDENTIFIER_EMAIL, FHIR_IDENTIFIER_URI, FHIR_IDENTIFIER_US_NPI, IGNIS_IDENTIFIER_CO_CC,
+        IGNIS_IDENTIFIER_MX_CURP, IGNIS_IDENTIFIER_MX_RFC, IGNIS_IDENTIFIER_VE_CEDULA,
+        IGNIS_IDENTIFIER_VE_RIF,
+    },
+    fhir_trait::FhirIdentifier,
+};
+
+mod schemes;
+pub use schemes::{
+    validate_co_cc, validate_email, validate_mx_curp, validate_mx_rfc, validate_npi, validate_uri,
+    validate_ve_cedula, validate_ve_rif,
+};
+
+// Checks the value of an identifier issued under one system
+pub trait FhirIdentifierValidator: Send + Sync {
+    fn validate(&self, value: &str) -> Result<(), String>;
+}
+impl<F> FhirIdentifierValidator for F
+where
+    F: Fn(&str) -> Result<(), String> + Send + Sync,
+{
+    fn validate(&self, value: &str) -> Result<(), String> {
+        self(value)
+    }
+}
+
+// Validators keyed by identifier system URI. Systems without a validator are
+// accepted as is, since most assigning authorities publish no format.
+#[derive(Default)]
+pub struct FhirIdentifierRegistry {
+    validators: HashMap<String, Box<dyn FhirIdentifierValidator>>,
+}
+impl FhirIdentifierRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        registry.register(FHIR_IDENTIFIER_US_NPI, validate_npi);
+        registry.register(FHIR_IDENTIFIER_URI, validate_uri);
+        registry.register(FHIR_IDENTIFIER_EMAIL, validate_email);
+        registry.register(IGNIS_IDENTIFIER_VE_CEDULA, validate_ve_cedula);
+        registry.register(IGNIS_IDENTIFIER_VE_RIF, validate_ve_rif);
+        registry.register(IGNIS_IDENTIFIER_CO_CC, validate_co_cc);
+        registry.register(IGNIS_IDENTIFIER_MX_CURP, validate_mx_curp);
+        registry.register(IGNIS_IDENTIFIER_MX_RFC, validate_mx_rfc);
+        registry
+    }
+    // Shared registry with the built-in validators, used by resource builders
+    pub fn defaults() -> &'static Self {
+        static DEFAULTS: OnceLock<FhirIdentifierRegistry> = OnceLock::new();
+        DEFAULTS.get_or_init(Self::with_defaults)
+    }
+    pub fn register(&mut self, system: &str, validator: impl FhirIdentifierValidator + 'static) {
+        self.validators
+            .insert(normalize_system(system), Box::new(validator));
+    }
+    pub fn has_validator(&self, system: &str) -> bool {
+        self.validators.contains_key(&normalize_system(system))
+    }
+    pub fn validate_value(&self, system: &str, value: &str) -> Result<(), String> {
+        if value.trim().is_empty() {
+            return Err(format!("Identifier for {} has an empty value", system));
+        }
+        match self.validators.get(&normalize_system(system)) {
+            Some(validator) => validator
+                .validate(value)
+                .map_err(|e| format!("Invalid identifier for {}: {}", system, e)),
+            None => Ok(()),
+        }
+    }
+    pub fn validate(&self, identifier: &FhirIdentifier) -> Result<(), String> {
+        if let Some(period) = identifier.get_period() {
+            period.validate()?;
+        }
+        self.validate_value(identifier.get_system(), identifier.get_value())
+    }
+}
+
+fn normalize_system(system: &str) -> String {
+    system.trim().trim_end_matches('/').to_string()
+}
+
+impl FhirIdentifier {
+    pub fn validate(&self) -> Result<(), String> {
+        FhirIdentifierRegistry::defaults().validate(self)
+    }
+}
@@ -0,0 +1,255 @@
+use chrono::NaiveDate;
+
+// US National Provider Identifier: ten digits whose last digit is a Luhn check
+// digit computed over the number prefixed with the 80840 health card issuer.
+// https://www.cms.gov/Regulations-and-Guidance/Administrative-Simplification/NationalProvIdentStand
+pub fn validate_npi(value: &str) -> Result<(), String> {
+    if value.len() != 10 || !value.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(format!("NPI must be 10 digits: {}", value));
+    }
+    if !value.starts_with(['1', '2']) {
+        return Err(format!("NPI must start with 1 or 2: {}", value));
+    }
+    let digits = format!("80840{}", value);
+    if !luhn(&digits) {
+        return Err(format!("NPI check digit does not match: {}", value));
+    }
+    Ok(())
+}
+
+fn luhn(digits: &str) -> bool {
+    let sum: u32 = digits
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(index, byte)| {
+            let digit = (byte - b'0') as u32;
+            match index % 2 {
+                1 if digit * 2 > 9 => digit * 2 - 9,
+                1 => digit * 2,
+                _ => digit,
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+// Strips the separators people type in national ID numbers ("V-12.345.678")
+fn compact(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !matches!(c, '.' | '-' | ' '))
+        .collect::<String>()
+        .to_uppercase()
+}
+
+// Venezuelan cédula de identidad: V (national) or E (foreign) followed by the number
+pub fn validate_ve_cedula(value: &str) -> Result<(), String> {
+    let compact = compact(value);
+    let number = compact.trim_start_matches(['V', 'E']);
+    if compact.len() - number.len() > 1 {
+        return Err(format!("Invalid cédula prefix: {}", value));
+    }
+    if !(5..=9).contains(&number.len())
+        || !number.bytes().all(|byte| byte.is_ascii_digit())
+        || number.starts_with('0')
+    {
+        return Err(format!("Cédula must be 5 to 9 digits: {}", value));
+    }
+    Ok(())
+}
+
+// Venezuelan RIF: type letter, eight digits and a modulo 11 check digit
+pub fn validate_ve_rif(value: &str) -> Result<(), String> {
+    let compact = compact(value);
+    let mut chars = compact.chars();
+    let letter_value = match chars.next() {
+        Some('V') => 1,
+        Some('E') => 2,
+        Some('J') => 3,
+        Some('P') => 4,
+        Some('G') => 5,
+        _ => return Err(format!("RIF must start with V, E, J, P or G: {}", value)),
+    };
+    let digits: Vec<u32> = chars
+        .map(|c| c.to_digit(10))
+        .collect::<Option<_>>()
+        .ok_or(format!(
+            "RIF must be a letter followed by 9 digits: {}",
+            value
+        ))?;
+    if digits.len() != 9 {
+        return Err(format!(
+            "RIF must be a letter followed by 9 digits: {}",
+            value
+        ));
+    }
+    let weighted: u32 = letter_value * 4
+        + digits[..8]
+            .iter()
+            .zip([3, 2, 7, 6, 5, 4, 3, 2])
+            .map(|(digit, weight)| digit * weight)
+            .sum::<u32>();
+    let check = match 11 - weighted % 11 {
+        10 | 11 => 0,
+        check => check,
+    };
+    if digits[8] != check {
+        return Err(format!("RIF check digit does not match: {}", value));
+    }
+    Ok(())
+}
+
+// Colombian cédula de ciudadanía
+pub fn validate_co_cc(value: &str) -> Result<(), String> {
+    let number = compact(value);
+    if !(4..=10).contains(&number.len())
+        || !number.bytes().all(|byte| byte.is_ascii_digit())
+        || number.starts_with('0')
+    {
+        return Err(format!(
+            "Cédula de ciudadanía must be 4 to 10 digits: {}",
+            value
+        ));
+    }
+    Ok(())
+}
+
+// Mexican states as used in the CURP, plus NE for people born abroad
+const MX_STATES: [&str; 33] = [
+    "AS", "BC", "BS", "CC", "CL", "CM", "CS", "CH", "DF", "DG", "GT", "GR", "HG", "JC", "MC", "MN",
+    "MS", "NT", "NL", "OC", "PL", "QT", "QR", "SP", "SL", "SR", "TC", "TS", "TL", "VZ", "YN", "ZS",
+    "NE",
+];
+
+// Two digit years are ambiguous, so a date is valid if it exists in either century
+fn valid_short_date(digits: &str) -> bool {
+    let parse = |index: std::ops::Range<usize>| digits.get(index)?.parse::<u32>().ok();
+    let (Some(year), Some(month), Some(day)) = (parse(0..2), parse(2..4), parse(4..6)) else {
+        return false;
+    };
+    [1900, 2000]
+        .iter()
+        .any(|century| NaiveDate::from_ymd_opt(*century + year as i32, month, day).is_some())
+}
+
+// Mexican Clave Única de Registro de Población, 18 characters with a check digit
+pub fn validate_mx_curp(value: &str) -> Result<(), String> {
+    let curp = value.trim().to_uppercase();
+    let chars: Vec<char> = curp.chars().collect();
+    let invalid = || Err(format!("Invalid CURP: {}", value));
+    // Ñ is written as X in the CURP, so it is always ASCII
+    if chars.len() != 18 || !curp.is_ascii() {
+        return invalid();
+    }
+    let letters = |range: std::ops::Range<usize>| chars[range].iter().all(char::is_ascii_uppercase);
+    if !letters(0..4)
+        || !valid_short_date(&curp[4..10])
+        || !matches!(chars[10], 'H' | 'M' | 'X')
+        || !MX_STATES.contains(&&curp[11..13])
+        || !letters(13..16)
+        || !chars[16].is_ascii_alphanumeric()
+        || !chars[17].is_ascii_digit()
+    {
+        return invalid();
+    }
+    // Check digit alphabet is 0-9 then A-Z with Ñ after N
+    let sum: u32 = chars[..17]
+        .iter()
+        .enumerate()
+        .map(|(index, c)| {
+            let value = match c {
+                '0'..='9' => *c as u32 - '0' as u32,
+                'A'..='N' => *c as u32 - 'A' as u32 + 10,
+                _ => *c as u32 - 'A' as u32 + 11,
+            };
+            value * (18 - index as u32)
+        })
+        .sum();
+    let check = (10 - sum % 10) % 10;
+    if chars[17].to_digit(10) != Some(check) {
+        return Err(format!("CURP check digit does not match: {}", value));
+    }
+    Ok(())
+}
+
+// Mexican Registro Federal de Contribuyentes: 4 letters for individuals or 3
+// for companies, the registration date and a 3 character homoclave
+pub fn validate_mx_rfc(value: &str) -> Result<(), String> {
+    let rfc = value.trim().to_uppercase();
+    let chars: Vec<char> = rfc.chars().filter(|c| *c != '-' && *c != ' ').collect();
+    let prefix_length = match chars.len() {
+        13 => 4,
+        12 => 3,
+        _ => return Err(format!("RFC must be 12 or 13 characters: {}", value)),
+    };
+    let date: String = chars[prefix_length..prefix_length + 6].iter().collect();
+    let homoclave = &chars[prefix_length + 6..];
+    if !chars[..prefix_length]
+        .iter()
+        .all(|c| c.is_ascii_uppercase() || matches!(c, 'Ñ' | '&'))
+        || !valid_short_date(&date)
+        || !homoclave[..2].iter().all(char::is_ascii_alphanumeric)
+        || !(homoclave[2].is_ascii_digit() || homoclave[2] == 'A')
+    {
+        return Err(format!("Invalid RFC: {}", value));
+    }
+    Ok(())
+}
+
+pub fn validate_email(value: &str) -> Result<(), String> {
+    let invalid = || Err(format!("Invalid email address: {}", value));
+    let Some((local, domain)) = value.rsplit_once('@') else {
+        return invalid();
+    };
+    let local_valid = !local.is_empty()
+        && local.len() <= 64
+        && !local.starts_with('.')
+        && !local.ends_with('.')
+        && !local.contains("..")
+        && local
+            .chars()
+            .all(|c| c.is_alphanumeric() || "!#$%&'*+/=?^_`{|}~.-".contains(c));
+    let labels: Vec<&str> = domain.split('.').collect();
+    let domain_valid = labels.len() >= 2
+        && domain.len() <= 253
+        && labels.iter().all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_alphanumeric() || c == '-')
+        })
+        && labels
+            .last()
+            .is_some_and(|tld| tld.len() >= 2 && tld.chars().all(char::is_alphabetic));
+    if !local_valid || !domain_valid {
+        return invalid();
+    }
+    Ok(())
+}
+
+// Any absolute URI; web URLs must also name a host
+pub fn validate_uri(value: &str) -> Result<(), String> {
+    let invalid = || Err(format!("Invalid URI: {}", value));
+    let Some((scheme, rest)) = value.split_once(':') else {
+        return invalid();
+    };
+    let scheme_valid = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    if !scheme_valid || rest.is_empty() || value.contains(char::is_whitespace) {
+        return invalid();
+    }
+    if matches!(scheme.to_lowercase().as_str(), "http" | "https") {
+        let host = rest
+            .strip_prefix("//")
+            .and_then(|authority| authority.split(['/', '?', '#']).next())
+            .unwrap_or_default();
+        if host.is_empty() {
+            return invalid();
+        }
+    }
+    Ok(())
+}
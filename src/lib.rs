@@ -1,6 +1,17 @@
+// Lets `#[derive(FhirResource)]` refer to this crate by name from inside it
+extern crate self as ignis_nostr;
+// Used by `#[derive(FhirResource)]` to register resources
+#[doc(hidden)]
+pub use inventory;
+
 pub mod consts;
 pub mod datatypes;
 pub mod fhir_trait;
+pub mod identifiers;
+pub mod locale;
 pub mod metadata;
+pub mod narrative;
 pub mod resources;
+pub mod terminology;
 pub mod valuesets;
+pub mod xml;
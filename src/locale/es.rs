@@ -0,0 +1,360 @@
+use crate::consts::{IGNIS_CODESYSTEM_SPECIALTY, SNOMED_CT_SYSTEM};
+
+// Spanish displays by code system, then code
+pub(super) const CODES: &[(&str, &[(&str, &str)])] = &[
+    (
+        SNOMED_CT_SYSTEM,
+        &[
+            ("408467006", "Enfermedad Mental Adulta"),
+            ("394577000", "Anestesiología"),
+            ("394578005", "Medicina Audiologica"),
+            ("421661004", "Banco de Sangre y Medicina de Transfusión"),
+            ("408462000", "Cuidado de Quemaduras"),
+            ("394579002", "Cardiología"),
+            ("394804000", "Citogenética Clínica y Genética Molecular"),
+            ("394580004", "Genética Clínica"),
+            ("394803006", "Hematología Clínica"),
+            ("408480009", "Inmunología Clínica"),
+            ("408454008", "Microbiología Clínica"),
+            ("394809005", "Neurofisiología Clínica"),
+            ("394592004", "Oncología Clínica"),
+            ("394600006", "Farmacología Clínica"),
+            ("394601005", "Fisiología Clínica"),
+            ("394581000", "Medicina Comunitaria"),
+            ("408478003", "Medicina de Cuidados Críticos"),
+            ("394812008", "Especialidades en Medicina Dental"),
+            ("408444009", "Práctica Dental General"),
+            ("394582007", "Dermatología"),
+            ("408475000", "Medicina Diabética"),
+            ("410005002", "Medicina de Buceo"),
+            ("394583002", "Endocrinología"),
+            ("419772000", "Medicina Familiar"),
+            ("394584008", "Gastroenterología"),
+            ("408443003", "Práctica Médica General"),
+            ("394802001", "Medicina General"),
+            ("394915009", "Patología General"),
+            ("394814009", "Práctica General"),
+            ("394808002", "Medicina Genito-Urinaria"),
+            ("394811001", "Medicina Geriátrica"),
+            ("408446006", "Oncología Ginecológica"),
+            ("394586005", "Ginecología"),
+            ("394916005", "Hematopatología"),
+            ("408472002", "Hepatología"),
+            ("394597005", "Histopatología"),
+            ("394598000", "Inmunopatología"),
+            ("394807007", "Enfermedades Infecciosas"),
+            ("419192003", "Medicina Interna"),
+            ("408468001", "Discapacidad de Aprendizaje"),
+            ("394593009", "Oncología Médica"),
+            ("394813003", "Oftalmología Médica"),
+            ("410001006", "Medicina Militar"),
+            ("394589003", "Nefrología"),
+            ("394591006", "Neurología"),
+            ("394599008", "Neuropatología"),
+            ("394649004", "Medicina Nuclear"),
+            ("408470005", "Obstetricia"),
+            ("394585009", "Obstetricia y Ginecología"),
+            ("394821009", "Medicina Ocupacional"),
+            ("422191005", "Cirugía Oftalmológica"),
+            ("394594003", "Oftalmología"),
+            ("416304004", "Medicina Osteopática Manipulativa"),
+            ("418960008", "Otorrinolaringología"),
+            ("394882004", "Algología"),
+            ("394806003", "Medicina Paliativa"),
+            ("394588008", "Pediatría General"),
+            ("394588006", "Psiquiatría Pediátrica"),
+            ("408459003", "Cardiología Pediátrica"),
+            ("394607009", "Odontología Pediátrica"),
+            ("419610006", "Endocrinología Pediátrica"),
+            ("418058008", "Gastroenterología Pediátrica"),
+            ("420208008", "Genética Pediátrica"),
+            ("418652005", "Hematología Pediátrica"),
+            ("418535003", "Inmunología Pediátrica"),
+            ("418862001", "Enfermedades Infecciosas Pediátricas"),
+            ("419365004", "Nefrología Pediátrica"),
+            ("418002000", "Oncología Pediátrica"),
+            ("419983000", "Oftalmología Pediátrica"),
+            ("419170002", "Neumología Pediátrica"),
+            ("419472004", "Reumatología Pediátrica"),
+            ("394539006", "Cirugía Pediátrica"),
+            (
+                "420112009",
+                "Cirugía Pediátrica de Trasplante de Médula Ósea",
+            ),
+            ("409968004", "Medicina Preventiva"),
+            ("394587001", "Psiquiatría"),
+            ("394913002", "Psicología"),
+            ("408440000", "Medicina de Salud Pública"),
+            ("418112009", "Neumología"),
+            ("419815003", "Oncología Radioterápica"),
+            ("394914008", "Radiología"),
+            ("408455009", "Radiología Intervencionista"),
+            ("394602003", "Rehabilitación"),
+            ("408447002", "Cuidado de Respiro"),
+            ("394810000", "Reumatología"),
+            ("408450004", "Estudios del Sueño"),
+            ("408469010", "Consulta Prequirúrgica"),
+            ("408476004", "Cirugía de Trasplante de Hueso y Médula"),
+            ("408469009", "Cirugía de Mama"),
+            ("408466002", "Cirugía Cardíaca"),
+            ("408471009", "Trasplante Cardio-Torácico"),
+            ("408464004", "Cirugía Colorrectal"),
+            ("408441001", "Endodoncia Dental"),
+            ("408465003", "Cirugía Oral y Maxilofacial Dental"),
+            ("394605001", "Cirugía Oral Dental"),
+            ("394608004", "Ortodoncia Dental"),
+            ("408461007", "Cirugía Periodontal Dental"),
+            ("408460008", "Prótesis Dental"),
+            ("394606000", "Odontología Restauradora"),
+            ("408449004", "Cirugía Dental"),
+            ("418018006", "Cirugía Dermatológica"),
+            ("394604002", "Cirugía de Oído, Nariz y Garganta"),
+            ("394609007", "Cirugía General"),
+            ("408474001", "Cirugía Hepatobiliar y Pancreática"),
+            ("394610002", "Neurocirugía"),
+            ("394611003", "Cirugía Plástica"),
+            ("408477008", "Cirugía de Trasplante"),
+            ("394801008", "Ortopedia"),
+            ("408463005", "Cirugía Vascular"),
+            ("419321007", "Cirugia Oncología"),
+            ("394576009", "Cirugia de Emergencia"),
+            ("394590007", "Medicina Torácica"),
+            ("409967009", "Toxicología"),
+            ("408448007", "Medicina Tropical"),
+            ("419043006", "Oncología Urológica"),
+            ("394612005", "Urología"),
+            ("394733009", "Otra Especialidad Médica No Listada"),
+            ("394732004", "Otra Especialidad Quirúrgica No Listada"),
+        ],
+    ),
+    (
+        IGNIS_CODESYSTEM_SPECIALTY,
+        &[
+            ("999999903", "Ginecología Mastología"),
+            ("999999900", "Perinatología"),
+            ("999999901", "Cirugía Bariátrica"),
+            ("999999902", "Cirugia Endoscópica"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/appointmentstatus",
+        &[
+            ("proposed", "Propuesto"),
+            ("pending", "Pendiente"),
+            ("booked", "Reservado"),
+            ("arrived", "Llegado"),
+            ("fulfilled", "Cumplido"),
+            ("cancelled", "Cancelado"),
+            ("noshow", "No presentado"),
+            ("entered-in-error", "Ingresado por error"),
+            ("checked-in", "Registrado"),
+            ("waitlist", "Lista de espera"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/appointmentresponse-status",
+        &[
+            ("accepted", "Aceptado"),
+            ("declined", "Rechazado"),
+            ("tentative", "Tentativo"),
+            ("needs-action", "Necesita acción"),
+            ("entered-in-error", "Ingresado por error"),
+        ],
+    ),
+    (
+        "http://terminology.hl7.org/CodeSystem/v2-0203",
+        &[
+            ("DL", "Licencia de Conducir"),
+            ("MD", "Matrícula Médica"),
+            ("NPI", "Identificador Nacional de Proveedor"),
+            ("NI", "Documento Nacional de Identidad"),
+            ("PPN", "Pasaporte"),
+            ("PRN", "Número de Proveedor"),
+            ("TAX", "Identificación Fiscal"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/identifier-use",
+        &[
+            ("usual", "Habitual"),
+            ("official", "Oficial"),
+            ("temp", "Temporal"),
+            ("secondary", "Secundario"),
+            ("old", "Antiguo"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/name-use",
+        &[
+            ("usual", "Habitual"),
+            ("official", "Oficial"),
+            ("temp", "Temporal"),
+            ("nickname", "Apodo"),
+            ("anonymous", "Anónimo"),
+            ("old", "Antiguo"),
+            ("maiden", "Nombre cambiado por matrimonio"),
+        ],
+    ),
+    (
+        "http://terminology.hl7.org/CodeSystem/organization-type",
+        &[
+            ("prov", "Proveedor de salud"),
+            ("dept", "Departamento hospitalario"),
+            ("team", "Equipo organizacional"),
+            ("govt", "Gobierno"),
+            ("ins", "Compañía de seguros"),
+            ("pay", "Pagador"),
+            ("edu", "Institución educativa"),
+            ("reli", "Institución religiosa"),
+            ("crs", "Patrocinador de investigación clínica"),
+            ("cg", "Grupo comunitario"),
+            ("bus", "Empresa no sanitaria"),
+            ("other", "Otro"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/address-type",
+        &[
+            ("postal", "Postal"),
+            ("physical", "Física"),
+            ("both", "Postal y física"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/address-use",
+        &[
+            ("home", "Domicilio"),
+            ("work", "Trabajo"),
+            ("temp", "Temporal"),
+            ("old", "Antigua / Incorrecta"),
+            ("billing", "Facturación"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/contact-point-use",
+        &[
+            ("home", "Casa"),
+            ("work", "Trabajo"),
+            ("temp", "Temporal"),
+            ("old", "Antiguo"),
+            ("mobile", "Móvil"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/contact-point-system",
+        &[
+            ("phone", "Teléfono"),
+            ("fax", "Fax"),
+            ("email", "Correo electrónico"),
+            ("pager", "Buscapersonas"),
+            ("url", "URL"),
+            ("sms", "SMS"),
+            ("other", "Otro"),
+        ],
+    ),
+    (
+        "http://terminology.hl7.org/CodeSystem/contactentity-type",
+        &[
+            ("BILL", "Facturación"),
+            ("ADMIN", "Administrativo"),
+            ("HR", "Recursos humanos"),
+            ("PAYOR", "Pagador"),
+            ("PATINF", "Paciente"),
+            ("PRESS", "Prensa"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/quantity-comparator",
+        &[
+            ("<", "Menor que"),
+            ("<=", "Menor o igual que"),
+            (">=", "Mayor o igual que"),
+            (">", "Mayor que"),
+            ("ad", "Suficiente para alcanzar la cantidad total"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/issue-severity",
+        &[
+            ("fatal", "Fatal"),
+            ("error", "Error"),
+            ("warning", "Advertencia"),
+            ("information", "Información"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/issue-type",
+        &[
+            ("invalid", "Contenido inválido"),
+            ("structure", "Problema estructural"),
+            ("required", "Falta un elemento obligatorio"),
+            ("value", "Valor de elemento inválido"),
+            ("invariant", "Regla de validación fallida"),
+            ("code-invalid", "Código inválido"),
+            ("business-rule", "Violación de regla de negocio"),
+            ("not-supported", "Contenido no soportado"),
+            ("informational", "Nota informativa"),
+        ],
+    ),
+];
+
+// Narrative labels, specialty groups and calendar names, by their English text
+pub(super) const LABELS: &[(&str, &str)] = &[
+    ("Appointment", "Cita"),
+    ("Status", "Estado"),
+    ("Service category", "Categoría de servicio"),
+    ("Specialty", "Especialidad"),
+    ("Start", "Inicio"),
+    ("End", "Fin"),
+    ("Proposed new time", "Nuevo horario propuesto"),
+    ("Yes", "Sí"),
+    ("Participant", "Participante"),
+    ("Comment", "Comentario"),
+    ("Type", "Tipo"),
+    ("Identifier", "Identificador"),
+    ("Contact", "Contacto"),
+    ("Telecom", "Medios de contacto"),
+    ("Address", "Dirección"),
+    ("License", "Matrícula"),
+    ("System", "Sistema"),
+    ("Value", "Valor"),
+    ("Use", "Uso"),
+    ("Surgery", "Cirugía"),
+    ("Oncology", "Oncología"),
+    ("Pediatrics", "Pediatría"),
+    ("Dentistry", "Odontología"),
+    ("Women's Health", "Salud de la Mujer"),
+    ("Pathology", "Patología"),
+    ("Imaging", "Imagenología"),
+    ("Mental Health", "Salud Mental"),
+    ("Primary Care", "Atención Primaria"),
+    // Date and time patterns of schedules, with a 24 hour clock
+    (
+        "{weekday}, {month} {day}, {year}, {time}",
+        "{weekday}, {day} de {month} de {year}, {time}",
+    ),
+    (
+        "{hour12}:{minute} {period} {zone}",
+        "{hour}:{minute} {zone}",
+    ),
+    // Months and weekdays of schedules
+    ("January", "enero"),
+    ("February", "febrero"),
+    ("March", "marzo"),
+    ("April", "abril"),
+    ("May", "mayo"),
+    ("June", "junio"),
+    ("July", "julio"),
+    ("August", "agosto"),
+    ("September", "septiembre"),
+    ("October", "octubre"),
+    ("November", "noviembre"),
+    ("December", "diciembre"),
+    ("Monday", "lunes"),
+    ("Tuesday", "martes"),
+    ("Wednesday", "miércoles"),
+    ("Thursday", "jueves"),
+    ("Friday", "viernes"),
+    ("Saturday", "sábado"),
+    ("Sunday", "domingo"),
+];
@@ -0,0 +1,188 @@
+mod es;
+mod pt;
+mod translation;
+
+use std::{collections::HashMap, sync::OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{datatypes::parse_time_zone, terminology::language_matches, valuesets::FhirCodedValue};
+
+// Displays and narrative labels are written in English, which needs no catalog
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+// Translations for one language: code displays by code system and code, and
+// narrative labels by their English text. Catalogs can be built in code or
+// read from JSON such as
+// {"language": "pt-BR", "codes": {"http://hl7.org/fhir/appointmentstatus": {"booked": "Marcado"}}, "labels": {}}
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirLocaleCatalog {
+    language: String,
+    #[serde(default)]
+    codes: HashMap<String, HashMap<String, String>>,
+    #[serde(default)]
+    labels: HashMap<String, String>,
+}
+impl FhirLocaleCatalog {
+    pub fn new(language: &str) -> Self {
+        Self {
+            language: language.to_string(),
+            codes: HashMap::new(),
+            labels: HashMap::new(),
+        }
+    }
+    fn bundled(language: &str, codes: &[(&str, &[(&str, &str)])], labels: &[(&str, &str)]) -> Self {
+        let mut catalog = Self::new(language);
+        for (system, displays) in codes {
+            for (code, display) in *displays {
+                catalog.add_display(system, code, display);
+            }
+        }
+        for (label, text) in labels {
+            catalog.add_label(label, text);
+        }
+        catalog
+    }
+    pub fn with_display(mut self, system: &str, code: &str, display: &str) -> Self {
+        self.add_display(system, code, display);
+        self
+    }
+    pub fn with_label(mut self, label: &str, text: &str) -> Self {
+        self.add_label(label, text);
+        self
+    }
+    pub fn add_display(&mut self, system: &str, code: &str, display: &str) {
+        self.codes
+            .entry(system.to_string())
+            .or_default()
+            .insert(code.to_string(), display.to_string());
+    }
+    pub fn add_label(&mut self, label: &str, text: &str) {
+        self.labels.insert(label.to_string(), text.to_string());
+    }
+    pub fn get_language(&self) -> &str {
+        &self.language
+    }
+    pub fn display(&self, system: &str, code: &str) -> Option<&str> {
+        self.codes
+            .get(system)
+            .and_then(|displays| displays.get(code))
+            .map(String::as_str)
+    }
+    pub fn label(&self, label: &str) -> Option<&str> {
+        self.labels.get(label).map(String::as_str)
+    }
+    // (system, code, display) for every code in the catalog
+    pub fn displays(&self) -> impl Iterator<Item = (&str, &str, &str)> {
+        self.codes.iter().flat_map(|(system, displays)| {
+            displays
+                .iter()
+                .map(move |(code, display)| (system.as_str(), code.as_str(), display.as_str()))
+        })
+    }
+    // Entries of `other` replace those already in the catalog
+    pub fn merge(&mut self, other: FhirLocaleCatalog) {
+        for (system, displays) in other.codes {
+            self.codes.entry(system).or_default().extend(displays);
+        }
+        self.labels.extend(other.labels);
+    }
+}
+
+// Catalogs for the languages displays and narratives can be rendered in. A
+// language tag uses its own catalog first and then its base language's, so
+// "pt-BR" falls back to "pt"; anything without a translation stays English.
+// Dates in narratives are shown in `time_zone`, UTC unless set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FhirLocalization {
+    catalogs: Vec<FhirLocaleCatalog>,
+    time_zone: Option<String>,
+}
+impl FhirLocalization {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    // Spanish and Portuguese for every bundled value set and narrative
+    pub fn with_defaults() -> Self {
+        let mut localization = Self::new();
+        localization.add_catalog(FhirLocaleCatalog::new(DEFAULT_LANGUAGE));
+        localization.add_catalog(FhirLocaleCatalog::bundled("es", es::CODES, es::LABELS));
+        localization.add_catalog(FhirLocaleCatalog::bundled("pt", pt::CODES, pt::LABELS));
+        localization
+    }
+    // Shared bundled catalogs, used by `FhirCodedValue::display_in` and the
+    // default narratives
+    pub fn defaults() -> &'static Self {
+        static DEFAULTS: OnceLock<FhirLocalization> = OnceLock::new();
+        DEFAULTS.get_or_init(Self::with_defaults)
+    }
+    // A catalog for a language already present is merged into it, overriding
+    // the entries it shares
+    pub fn add_catalog(&mut self, catalog: FhirLocaleCatalog) {
+        match self
+            .catalogs
+            .iter_mut()
+            .find(|own| own.language == catalog.language)
+        {
+            Some(own) => own.merge(catalog),
+            None => self.catalogs.push(catalog),
+        }
+    }
+    pub fn with_catalog(mut self, catalog: FhirLocaleCatalog) -> Self {
+        self.add_catalog(catalog);
+        self
+    }
+    // An IANA time zone name, e.g. "America/Caracas"
+    pub fn set_time_zone(&mut self, zone: &str) -> Result<(), String> {
+        parse_time_zone(zone)?;
+        self.time_zone = Some(zone.to_string());
+        Ok(())
+    }
+    pub fn with_time_zone(mut self, zone: &str) -> Result<Self, String> {
+        self.set_time_zone(zone)?;
+        Ok(self)
+    }
+    pub fn get_time_zone(&self) -> &str {
+        self.time_zone.as_deref().unwrap_or("UTC")
+    }
+    pub fn get_catalog(&self, language: &str) -> Option<&FhirLocaleCatalog> {
+        self.catalogs
+            .iter()
+            .find(|catalog| catalog.language == language)
+    }
+    pub fn get_catalogs(&self) -> &[FhirLocaleCatalog] {
+        &self.catalogs
+    }
+    pub fn languages(&self) -> Vec<&str> {
+        self.catalogs
+            .iter()
+            .map(|catalog| catalog.language.as_str())
+            .collect()
+    }
+    // The exact catalog for `language` first, then those of related tags
+    fn lookup<'a>(
+        &'a self,
+        language: &str,
+        find: impl Fn(&'a FhirLocaleCatalog) -> Option<&'a str>,
+    ) -> Option<&'a str> {
+        self.get_catalog(language).and_then(&find).or_else(|| {
+            self.catalogs
+                .iter()
+                .filter(|catalog| language_matches(&catalog.language, language))
+                .find_map(&find)
+        })
+    }
+    pub fn display(&self, system: &str, code: &str, language: &str) -> Option<&str> {
+        self.lookup(language, |catalog| catalog.display(system, code))
+    }
+    // The translated display, or the value's FHIR display when there is none
+    pub fn display_of<'a, T: FhirCodedValue>(&'a self, value: &'a T, language: &str) -> &'a str {
+        self.display(value.fhir_system(), &value.fhir_code(), language)
+            .unwrap_or(value.fhir_display())
+    }
+    // The translated label, or the English label itself
+    pub fn label<'a>(&'a self, label: &'a str, language: &str) -> &'a str {
+        self.lookup(language, |catalog| catalog.label(label))
+            .unwrap_or(label)
+    }
+}
@@ -0,0 +1,357 @@
+use crate::consts::{IGNIS_CODESYSTEM_SPECIALTY, SNOMED_CT_SYSTEM};
+
+// Portuguese displays by code system, then code
+pub(super) const CODES: &[(&str, &[(&str, &str)])] = &[
+    (
+        SNOMED_CT_SYSTEM,
+        &[
+            ("408467006", "Doença mental do adulto"),
+            ("394577000", "Anestesiologia"),
+            ("394578005", "Medicina audiológica"),
+            ("421661004", "Hemoterapia e medicina transfusional"),
+            ("408462000", "Cuidado de queimados"),
+            ("394579002", "Cardiologia"),
+            ("394804000", "Citogenética clínica e genética molecular"),
+            ("394580004", "Genética clínica"),
+            ("394803006", "Hematologia clínica"),
+            ("408480009", "Imunologia clínica"),
+            ("408454008", "Microbiologia clínica"),
+            ("394809005", "Neurofisiologia clínica"),
+            ("394592004", "Oncologia clínica"),
+            ("394600006", "Farmacologia clínica"),
+            ("394601005", "Fisiologia clínica"),
+            ("394581000", "Medicina comunitária"),
+            ("408478003", "Medicina intensiva"),
+            ("394812008", "Especialidades odontológicas"),
+            ("408444009", "Clínica geral odontológica"),
+            ("394582007", "Dermatologia"),
+            ("408475000", "Diabetologia"),
+            ("410005002", "Medicina do mergulho"),
+            ("394583002", "Endocrinologia"),
+            ("419772000", "Medicina de família"),
+            ("394584008", "Gastroenterologia"),
+            ("408443003", "Clínica médica geral"),
+            ("394802001", "Medicina geral"),
+            ("394915009", "Patologia geral"),
+            ("394814009", "Clínica geral"),
+            ("394808002", "Medicina geniturinária"),
+            ("394811001", "Geriatria"),
+            ("408446006", "Oncologia ginecológica"),
+            ("394586005", "Ginecologia"),
+            ("394916005", "Hematopatologia"),
+            ("408472002", "Hepatologia"),
+            ("394597005", "Histopatologia"),
+            ("394598000", "Imunopatologia"),
+            ("394807007", "Infectologia"),
+            ("419192003", "Medicina interna"),
+            ("408468001", "Deficiência de aprendizagem"),
+            ("394593009", "Oncologia médica"),
+            ("394813003", "Oftalmologia clínica"),
+            ("410001006", "Medicina militar"),
+            ("394589003", "Nefrologia"),
+            ("394591006", "Neurologia"),
+            ("394599008", "Neuropatologia"),
+            ("394649004", "Medicina nuclear"),
+            ("408470005", "Obstetrícia"),
+            ("394585009", "Ginecologia e obstetrícia"),
+            ("394821009", "Medicina do trabalho"),
+            ("422191005", "Cirurgia oftalmológica"),
+            ("394594003", "Oftalmologia"),
+            ("416304004", "Medicina osteopática manipulativa"),
+            ("418960008", "Otorrinolaringologia"),
+            ("394882004", "Medicina da dor"),
+            ("394806003", "Medicina paliativa"),
+            ("394588008", "Pediatria geral"),
+            ("394588006", "Psiquiatria da infância e adolescência"),
+            ("408459003", "Cardiologia pediátrica"),
+            ("394607009", "Odontopediatria"),
+            ("419610006", "Endocrinologia pediátrica"),
+            ("418058008", "Gastroenterologia pediátrica"),
+            ("420208008", "Genética pediátrica"),
+            ("418652005", "Hematologia pediátrica"),
+            ("418535003", "Imunologia pediátrica"),
+            ("418862001", "Infectologia pediátrica"),
+            ("419365004", "Nefrologia pediátrica"),
+            ("418002000", "Oncologia pediátrica"),
+            ("419983000", "Oftalmologia pediátrica"),
+            ("419170002", "Pneumologia pediátrica"),
+            ("419472004", "Reumatologia pediátrica"),
+            ("394539006", "Cirurgia pediátrica"),
+            ("420112009", "Transplante de medula óssea pediátrico"),
+            ("409968004", "Medicina preventiva"),
+            ("394587001", "Psiquiatria"),
+            ("394913002", "Psicoterapia"),
+            ("408440000", "Medicina de saúde pública"),
+            ("418112009", "Pneumologia"),
+            ("419815003", "Radioterapia"),
+            ("394914008", "Radiologia"),
+            ("408455009", "Radiologia intervencionista"),
+            ("394602003", "Reabilitação"),
+            ("408447002", "Cuidados temporários de alívio"),
+            ("394810000", "Reumatologia"),
+            ("408450004", "Medicina do sono"),
+            ("408469010", "Consulta pré-cirúrgica"),
+            ("408476004", "Cirurgia de transplante de medula óssea"),
+            ("408469009", "Cirurgia da mama"),
+            ("408466002", "Cirurgia cardíaca"),
+            ("408471009", "Transplante cardiotorácico"),
+            ("408464004", "Coloproctologia"),
+            ("408441001", "Endodontia"),
+            ("408465003", "Cirurgia bucomaxilofacial"),
+            ("394605001", "Cirurgia oral"),
+            ("394608004", "Ortodontia"),
+            ("408461007", "Periodontia"),
+            ("408460008", "Prótese dentária"),
+            ("394606000", "Dentística restauradora"),
+            ("408449004", "Cirurgia odontológica"),
+            ("418018006", "Cirurgia dermatológica"),
+            ("394604002", "Cirurgia de ouvido, nariz e garganta"),
+            ("394609007", "Cirurgia geral"),
+            ("408474001", "Cirurgia hepatobiliar e pancreática"),
+            ("394610002", "Neurocirurgia"),
+            ("394611003", "Cirurgia plástica"),
+            ("408477008", "Cirurgia de transplante"),
+            ("394801008", "Ortopedia e traumatologia"),
+            ("408463005", "Cirurgia vascular"),
+            ("419321007", "Cirurgia oncológica"),
+            ("394576009", "Cirurgia de emergência"),
+            ("394590007", "Medicina torácica"),
+            ("409967009", "Toxicologia"),
+            ("408448007", "Medicina tropical"),
+            ("419043006", "Oncologia urológica"),
+            ("394612005", "Urologia"),
+            ("394733009", "Outra especialidade médica não listada"),
+            ("394732004", "Outra especialidade cirúrgica não listada"),
+        ],
+    ),
+    (
+        IGNIS_CODESYSTEM_SPECIALTY,
+        &[
+            ("999999903", "Ginecologia e mastologia"),
+            ("999999900", "Medicina perinatal"),
+            ("999999901", "Cirurgia bariátrica"),
+            ("999999902", "Cirurgia endoscópica"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/appointmentstatus",
+        &[
+            ("proposed", "Proposto"),
+            ("pending", "Pendente"),
+            ("booked", "Agendado"),
+            ("arrived", "Chegou"),
+            ("fulfilled", "Realizado"),
+            ("cancelled", "Cancelado"),
+            ("noshow", "Não compareceu"),
+            ("entered-in-error", "Registrado por engano"),
+            ("checked-in", "Check-in realizado"),
+            ("waitlist", "Lista de espera"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/appointmentresponse-status",
+        &[
+            ("accepted", "Aceito"),
+            ("declined", "Recusado"),
+            ("tentative", "Provisório"),
+            ("needs-action", "Requer ação"),
+            ("entered-in-error", "Registrado por engano"),
+        ],
+    ),
+    (
+        "http://terminology.hl7.org/CodeSystem/v2-0203",
+        &[
+            ("DL", "Carteira de motorista"),
+            ("MD", "Registro profissional médico"),
+            ("NPI", "Identificador nacional de prestador"),
+            ("NI", "Documento nacional de identidade"),
+            ("PPN", "Passaporte"),
+            ("PRN", "Número do prestador"),
+            ("TAX", "Identificação fiscal"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/identifier-use",
+        &[
+            ("usual", "Usual"),
+            ("official", "Oficial"),
+            ("temp", "Temporário"),
+            ("secondary", "Secundário"),
+            ("old", "Antigo"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/name-use",
+        &[
+            ("usual", "Usual"),
+            ("official", "Oficial"),
+            ("temp", "Temporário"),
+            ("nickname", "Apelido"),
+            ("anonymous", "Anônimo"),
+            ("old", "Antigo"),
+            ("maiden", "Nome alterado por casamento"),
+        ],
+    ),
+    (
+        "http://terminology.hl7.org/CodeSystem/organization-type",
+        &[
+            ("prov", "Prestador de saúde"),
+            ("dept", "Departamento hospitalar"),
+            ("team", "Equipe organizacional"),
+            ("govt", "Governo"),
+            ("ins", "Seguradora"),
+            ("pay", "Pagador"),
+            ("edu", "Instituição de ensino"),
+            ("reli", "Instituição religiosa"),
+            ("crs", "Patrocinador de pesquisa clínica"),
+            ("cg", "Grupo comunitário"),
+            ("bus", "Empresa fora da área da saúde"),
+            ("other", "Outro"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/address-type",
+        &[
+            ("postal", "Postal"),
+            ("physical", "Físico"),
+            ("both", "Postal e físico"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/address-use",
+        &[
+            ("home", "Residencial"),
+            ("work", "Trabalho"),
+            ("temp", "Temporário"),
+            ("old", "Antigo / Incorreto"),
+            ("billing", "Cobrança"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/contact-point-use",
+        &[
+            ("home", "Residencial"),
+            ("work", "Trabalho"),
+            ("temp", "Temporário"),
+            ("old", "Antigo"),
+            ("mobile", "Celular"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/contact-point-system",
+        &[
+            ("phone", "Telefone"),
+            ("fax", "Fax"),
+            ("email", "E-mail"),
+            ("pager", "Pager"),
+            ("url", "URL"),
+            ("sms", "SMS"),
+            ("other", "Outro"),
+        ],
+    ),
+    (
+        "http://terminology.hl7.org/CodeSystem/contactentity-type",
+        &[
+            ("BILL", "Cobrança"),
+            ("ADMIN", "Administrativo"),
+            ("HR", "Recursos humanos"),
+            ("PAYOR", "Pagador"),
+            ("PATINF", "Paciente"),
+            ("PRESS", "Imprensa"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/quantity-comparator",
+        &[
+            ("<", "Menor que"),
+            ("<=", "Menor ou igual a"),
+            (">=", "Maior ou igual a"),
+            (">", "Maior que"),
+            ("ad", "Suficiente para atingir a quantidade total"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/issue-severity",
+        &[
+            ("fatal", "Fatal"),
+            ("error", "Erro"),
+            ("warning", "Aviso"),
+            ("information", "Informação"),
+        ],
+    ),
+    (
+        "http://hl7.org/fhir/issue-type",
+        &[
+            ("invalid", "Conteúdo inválido"),
+            ("structure", "Problema estrutural"),
+            ("required", "Elemento obrigatório ausente"),
+            ("value", "Valor do elemento inválido"),
+            ("invariant", "Regra de validação falhou"),
+            ("code-invalid", "Código inválido"),
+            ("business-rule", "Violação de regra de negócio"),
+            ("not-supported", "Conteúdo não suportado"),
+            ("informational", "Nota informativa"),
+        ],
+    ),
+];
+
+// Narrative labels, specialty groups and calendar names, by their English text
+pub(super) const LABELS: &[(&str, &str)] = &[
+    ("Appointment", "Consulta"),
+    ("Status", "Status"),
+    ("Service category", "Categoria de serviço"),
+    ("Specialty", "Especialidade"),
+    ("Start", "Início"),
+    ("End", "Fim"),
+    ("Proposed new time", "Novo horário proposto"),
+    ("Yes", "Sim"),
+    ("Participant", "Participante"),
+    ("Comment", "Comentário"),
+    ("Type", "Tipo"),
+    ("Identifier", "Identificador"),
+    ("Contact", "Contato"),
+    ("Telecom", "Meios de contato"),
+    ("Address", "Endereço"),
+    ("License", "Registro profissional"),
+    ("System", "Sistema"),
+    ("Value", "Valor"),
+    ("Use", "Uso"),
+    ("Surgery", "Cirurgia"),
+    ("Oncology", "Oncologia"),
+    ("Pediatrics", "Pediatria"),
+    ("Dentistry", "Odontologia"),
+    ("Women's Health", "Saúde da Mulher"),
+    ("Pathology", "Patologia"),
+    ("Imaging", "Diagnóstico por Imagem"),
+    ("Mental Health", "Saúde Mental"),
+    ("Primary Care", "Atenção Primária"),
+    // Date and time patterns of schedules, with a 24 hour clock
+    (
+        "{weekday}, {month} {day}, {year}, {time}",
+        "{weekday}, {day} de {month} de {year}, {time}",
+    ),
+    (
+        "{hour12}:{minute} {period} {zone}",
+        "{hour}:{minute} {zone}",
+    ),
+    // Months and weekdays of schedules
+    ("January", "janeiro"),
+    ("February", "fevereiro"),
+    ("March", "março"),
+    ("April", "abril"),
+    ("May", "maio"),
+    ("June", "junho"),
+    ("July", "julho"),
+    ("August", "agosto"),
+    ("September", "setembro"),
+    ("October", "outubro"),
+    ("November", "novembro"),
+    ("December", "dezembro"),
+    ("Monday", "segunda-feira"),
+    ("Tuesday", "terça-feira"),
+    ("Wednesday", "quarta-feira"),
+    ("Thursday", "quinta-feira"),
+    ("Friday", "sexta-feira"),
+    ("Saturday", "sábado"),
+    ("Sunday", "domingo"),
+];
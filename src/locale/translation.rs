@@ -0,0 +1,85 @@
+use serde_json::Value;
+
+use super::{FhirLocalization, DEFAULT_LANGUAGE};
+use crate::{
+    datatypes::{FhirCoding, FhirTranslation},
+    terminology::language_matches,
+};
+
+// https://www.hl7.org/fhir/datatypes.html#Coding
+const CODING_ELEMENTS: [&str; 6] = [
+    "system",
+    "version",
+    "code",
+    "display",
+    "_display",
+    "userSelected",
+];
+
+// Objects with a system, a code and nothing else a Coding does not have, so a
+// Quantity's system and code are left alone
+fn as_coding(json: &Value) -> Option<FhirCoding> {
+    let map = json.as_object()?;
+    let is_coding = map.get("system").is_some_and(Value::is_string)
+        && map.get("code").is_some_and(Value::is_string)
+        && map
+            .keys()
+            .all(|key| CODING_ELEMENTS.contains(&key.as_str()));
+    match is_coding {
+        true => serde_json::from_value(json.clone()).ok(),
+        false => None,
+    }
+}
+
+impl FhirLocalization {
+    // Adds the translation extension to the display of every coding in FHIR
+    // JSON, for each language with a translation in the catalogs. English is
+    // the display itself, and languages the coding already has are kept.
+    pub fn add_translations(&self, json: &mut Value, languages: &[&str]) {
+        if let Some(mut coding) = as_coding(json) {
+            if self.translate(&mut coding, languages) {
+                if let Ok(translated) = serde_json::to_value(coding) {
+                    *json = translated;
+                }
+            }
+            return;
+        }
+        match json {
+            Value::Object(map) => {
+                for value in map.values_mut() {
+                    self.add_translations(value, languages);
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.add_translations(item, languages);
+                }
+            }
+            _ => {}
+        }
+    }
+    // Whether any translation was added
+    fn translate(&self, coding: &mut FhirCoding, languages: &[&str]) -> bool {
+        let Some(system) = coding.system.clone() else {
+            return false;
+        };
+        let mut translated = false;
+        for language in languages {
+            let known = coding
+                .get_translations()
+                .iter()
+                .any(|translation| translation.get_language().eq_ignore_ascii_case(language));
+            if known || language_matches(DEFAULT_LANGUAGE, language) {
+                continue;
+            }
+            match self.display(&system, &coding.code, language) {
+                Some(display) if Some(display) != coding.display.as_deref() => {
+                    coding.add_translation(FhirTranslation::new(language, display));
+                    translated = true;
+                }
+                _ => {}
+            }
+        }
+        translated
+    }
+}
@@ -1,13 +1,27 @@
 
 use serde::{Deserialize, Serialize};
 
-use super::{datatypes::{FhirContactPoint, FhirAddress, FhirPeriod}, valuesets::FhirContactEntityType};
+use super::datatypes::{FhirAddress, FhirCodeableConcept, FhirContactPoint, FhirPeriod};
+use super::valuesets::FhirContactPointSystem;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FhirExtendedContactDetail {
-    pub purpose: FhirContactEntityType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<FhirCodeableConcept>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub telecom: Vec<FhirContactPoint>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub address: Option<FhirAddress>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub period: Option<FhirPeriod>,
 }
+impl FhirExtendedContactDetail {
+    pub fn get_email(&self) -> Option<&FhirContactPoint> {
+        FhirContactPoint::preferred(&self.telecom, FhirContactPointSystem::Email)
+    }
+    pub fn get_phone(&self) -> Option<&FhirContactPoint> {
+        FhirContactPoint::preferred(&self.telecom, FhirContactPointSystem::Phone)
+    }
+}
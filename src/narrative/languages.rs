@@ -0,0 +1,97 @@
+use quick_xml::{
+    escape::escape,
+    events::{BytesStart, Event},
+    Reader,
+};
+
+use crate::xml::XHTML_NAMESPACE;
+
+// https://www.hl7.org/fhir/narrative.html#lang
+// A narrative in several languages holds a div per language inside the
+// narrative div, each marked with both lang and xml:lang
+
+fn section_language(start: &BytesStart) -> Result<Option<String>, String> {
+    for attribute in start.attributes() {
+        let attribute = attribute.map_err(|e| e.to_string())?;
+        if matches!(attribute.key.as_ref(), b"lang" | b"xml:lang") {
+            let language = attribute.unescape_value().map_err(|e| e.to_string())?;
+            return Ok(Some(language.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+// The markup inside a narrative div
+fn inner_xhtml(div: &str) -> Result<&str, String> {
+    let mut reader = Reader::from_str(div);
+    loop {
+        match reader.read_event().map_err(|e| e.to_string())? {
+            Event::Start(start) => {
+                let span = reader
+                    .read_to_end(start.name())
+                    .map_err(|e| e.to_string())?;
+                return Ok(&div[span.start as usize..span.end as usize]);
+            }
+            Event::Empty(_) => return Ok(""),
+            Event::Eof => return Err("Narrative has no div".to_string()),
+            _ => {}
+        }
+    }
+}
+
+fn section(language: String, content: &str) -> (String, String) {
+    let div = format!("<div xmlns=\"{}\">{}</div>", XHTML_NAMESPACE, content);
+    (language, div)
+}
+
+// One narrative div with a section for each (language, div) pair, in order
+pub fn join_languages(narratives: &[(&str, &str)]) -> Result<String, String> {
+    let mut xhtml = format!("<div xmlns=\"{}\">", XHTML_NAMESPACE);
+    for (language, div) in narratives {
+        xhtml.push_str(&format!(
+            "<div lang=\"{0}\" xml:lang=\"{0}\">{1}</div>",
+            escape(*language),
+            inner_xhtml(div)?
+        ));
+    }
+    xhtml.push_str("</div>");
+    Ok(xhtml)
+}
+
+// The (language, div) sections of a narrative in several languages. A
+// narrative with anything else at its top level is in a single language and
+// has no sections.
+pub fn split_languages(xhtml: &str) -> Result<Vec<(String, String)>, String> {
+    let mut reader = Reader::from_str(xhtml);
+    let mut sections = vec![];
+    let mut depth = 0;
+    loop {
+        match reader.read_event().map_err(|e| e.to_string())? {
+            Event::Start(start) if depth == 1 => {
+                let language = section_language(&start)?;
+                let span = reader
+                    .read_to_end(start.name())
+                    .map_err(|e| e.to_string())?;
+                match (start.local_name().as_ref(), language) {
+                    (b"div", Some(language)) => sections.push(section(
+                        language,
+                        &xhtml[span.start as usize..span.end as usize],
+                    )),
+                    _ => return Ok(vec![]),
+                }
+            }
+            Event::Empty(start) if depth == 1 => {
+                match (start.local_name().as_ref(), section_language(&start)?) {
+                    (b"div", Some(language)) => sections.push(section(language, "")),
+                    _ => return Ok(vec![]),
+                }
+            }
+            Event::Start(_) => depth += 1,
+            Event::Empty(_) => return Ok(vec![]),
+            Event::End(_) => return Ok(sections),
+            Event::Text(text) if !text.iter().all(u8::is_ascii_whitespace) => return Ok(vec![]),
+            Event::Eof => return Err("Narrative div is not closed".to_string()),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,164 @@
+mod languages;
+mod sanitize;
+pub use languages::{join_languages, split_languages};
+pub use sanitize::sanitize_xhtml;
+
+use quick_xml::escape::escape;
+
+use crate::{datatypes::FhirCodeableConcept, locale::FhirLocalization, xml::XHTML_NAMESPACE};
+
+// https://www.hl7.org/fhir/narrative.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FhirNarrativeBlock {
+    Heading(String),
+    Paragraph(String),
+    // Label and value pairs, shown as a two column table
+    Rows(Vec<(String, String)>),
+    Table(Vec<String>, Vec<Vec<String>>),
+    List(Vec<String>),
+}
+
+// Builds a narrative `div` from plain text. All text is escaped and only
+// elements allowed in FHIR narratives are written, so values taken from a
+// resource cannot inject markup.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FhirNarrative {
+    blocks: Vec<FhirNarrativeBlock>,
+}
+impl FhirNarrative {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_heading(mut self, text: &str) -> Self {
+        if !text.trim().is_empty() {
+            self.blocks
+                .push(FhirNarrativeBlock::Heading(text.to_string()));
+        }
+        self
+    }
+    pub fn with_paragraph(mut self, text: &str) -> Self {
+        if !text.trim().is_empty() {
+            self.blocks
+                .push(FhirNarrativeBlock::Paragraph(text.to_string()));
+        }
+        self
+    }
+    // Consecutive rows share a table; empty values are left out
+    pub fn with_row(mut self, label: &str, value: &str) -> Self {
+        if value.trim().is_empty() {
+            return self;
+        }
+        let row = (label.to_string(), value.to_string());
+        match self.blocks.last_mut() {
+            Some(FhirNarrativeBlock::Rows(rows)) => rows.push(row),
+            _ => self.blocks.push(FhirNarrativeBlock::Rows(vec![row])),
+        }
+        self
+    }
+    pub fn with_table(mut self, headers: &[&str], rows: Vec<Vec<String>>) -> Self {
+        if !rows.is_empty() {
+            self.blocks.push(FhirNarrativeBlock::Table(
+                headers.iter().map(|header| header.to_string()).collect(),
+                rows,
+            ));
+        }
+        self
+    }
+    pub fn with_list(mut self, items: Vec<String>) -> Self {
+        let items: Vec<String> = items
+            .into_iter()
+            .filter(|item| !item.trim().is_empty())
+            .collect();
+        if !items.is_empty() {
+            self.blocks.push(FhirNarrativeBlock::List(items));
+        }
+        self
+    }
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+    pub fn to_xhtml(&self) -> String {
+        let mut xhtml = format!("<div xmlns=\"{}\">", XHTML_NAMESPACE);
+        for block in &self.blocks {
+            match block {
+                FhirNarrativeBlock::Heading(text) => {
+                    xhtml.push_str(&format!("<h3>{}</h3>", escape_text(text)))
+                }
+                FhirNarrativeBlock::Paragraph(text) => {
+                    xhtml.push_str(&format!("<p>{}</p>", escape_text(text)))
+                }
+                FhirNarrativeBlock::Rows(rows) => {
+                    xhtml.push_str("<table><tbody>");
+                    for (label, value) in rows {
+                        xhtml.push_str(&format!(
+                            "<tr><th>{}</th><td>{}</td></tr>",
+                            escape_text(label),
+                            escape_text(value)
+                        ));
+                    }
+                    xhtml.push_str("</tbody></table>");
+                }
+                FhirNarrativeBlock::Table(headers, rows) => {
+                    xhtml.push_str("<table>");
+                    if !headers.is_empty() {
+                        xhtml.push_str("<thead><tr>");
+                        for header in headers {
+                            xhtml.push_str(&format!("<th>{}</th>", escape_text(header)));
+                        }
+                        xhtml.push_str("</tr></thead>");
+                    }
+                    xhtml.push_str("<tbody>");
+                    for row in rows {
+                        xhtml.push_str("<tr>");
+                        for cell in row {
+                            xhtml.push_str(&format!("<td>{}</td>", escape_text(cell)));
+                        }
+                        xhtml.push_str("</tr>");
+                    }
+                    xhtml.push_str("</tbody></table>");
+                }
+                FhirNarrativeBlock::List(items) => {
+                    xhtml.push_str("<ul>");
+                    for item in items {
+                        xhtml.push_str(&format!("<li>{}</li>", escape_text(item)));
+                    }
+                    xhtml.push_str("</ul>");
+                }
+            }
+        }
+        xhtml.push_str("</div>");
+        xhtml
+    }
+}
+
+// Displays of the concepts for a single table cell, each from the first of its
+// codings translated into `language` or else from its own text
+pub fn concepts_text(
+    concepts: &[FhirCodeableConcept],
+    localization: &FhirLocalization,
+    language: &str,
+) -> String {
+    concepts
+        .iter()
+        .map(|concept| {
+            concept
+                .coding
+                .iter()
+                .find_map(|coding| {
+                    localization.display(coding.system.as_deref()?, &coding.code, language)
+                })
+                .map(str::to_string)
+                .unwrap_or_else(|| concept.display())
+        })
+        .filter(|display| !display.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// Line breaks in values are kept as <br/>
+fn escape_text(text: &str) -> String {
+    text.lines()
+        .map(|line| escape(line).to_string())
+        .collect::<Vec<_>>()
+        .join("<br/>")
+}
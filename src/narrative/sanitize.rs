@@ -0,0 +1,251 @@
+use quick_xml::{
+    escape::{escape, resolve_predefined_entity},
+    events::{BytesStart, Event},
+    Reader,
+};
+
+use crate::xml::XHTML_NAMESPACE;
+
+// https://www.hl7.org/fhir/narrative.html#xhtml
+// Formatting elements of HTML 4.0 chapters 7-11 and 15, links and images
+const ALLOWED_ELEMENTS: [&str; 58] = [
+    "a",
+    "abbr",
+    "acronym",
+    "address",
+    "b",
+    "bdo",
+    "big",
+    "blockquote",
+    "br",
+    "caption",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "dd",
+    "del",
+    "dfn",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "ins",
+    "kbd",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "q",
+    "s",
+    "samp",
+    "small",
+    "span",
+    "strike",
+    "strong",
+    "sub",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "tt",
+    "u",
+    "ul",
+    "var",
+    "map",
+    "area",
+];
+// Elements dropped along with everything inside them. Any other element not
+// allowed is unwrapped, keeping its text.
+const DROPPED_ELEMENTS: [&str; 25] = [
+    "script", "style", "head", "title", "meta", "link", "base", "basefont", "iframe", "frame",
+    "frameset", "noframes", "object", "embed", "applet", "param", "form", "input", "textarea",
+    "select", "option", "button", "noscript", "template", "svg",
+];
+const ALLOWED_ATTRIBUTES: [&str; 36] = [
+    "id",
+    "class",
+    "style",
+    "title",
+    "lang",
+    "xml:lang",
+    "dir",
+    "href",
+    "name",
+    "rel",
+    "rev",
+    "src",
+    "alt",
+    "width",
+    "height",
+    "colspan",
+    "rowspan",
+    "span",
+    "align",
+    "valign",
+    "abbr",
+    "axis",
+    "headers",
+    "scope",
+    "summary",
+    "border",
+    "cellpadding",
+    "cellspacing",
+    "frame",
+    "rules",
+    "cite",
+    "datetime",
+    "start",
+    "value",
+    "type",
+    "hreflang",
+];
+const URL_ATTRIBUTES: [&str; 3] = ["href", "src", "cite"];
+const URL_SCHEMES: [&str; 5] = ["http", "https", "mailto", "tel", "urn"];
+
+// Relative and fragment links are fine; absolute ones need a known scheme
+fn is_safe_url(url: &str) -> bool {
+    let url: String = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_lowercase();
+    match url.split_once(':') {
+        Some((scheme, _)) if !scheme.contains(['/', '?', '#']) => URL_SCHEMES.contains(&scheme),
+        _ => true,
+    }
+}
+
+// Inline styles may not load anything or run script
+fn is_safe_style(style: &str) -> bool {
+    let style = style.to_lowercase();
+    !["url(", "expression", "javascript", "@import", "behavior"]
+        .iter()
+        .any(|pattern| style.contains(pattern))
+}
+
+fn element_name(start: &BytesStart) -> String {
+    String::from_utf8_lossy(start.local_name().as_ref()).to_lowercase()
+}
+
+fn write_attributes(xhtml: &mut String, start: &BytesStart) -> Result<(), String> {
+    for attribute in start.attributes() {
+        let attribute = attribute.map_err(|e| e.to_string())?;
+        let key = String::from_utf8_lossy(attribute.key.as_ref()).to_lowercase();
+        if !ALLOWED_ATTRIBUTES.contains(&key.as_str()) {
+            continue;
+        }
+        let value = attribute.unescape_value().map_err(|e| e.to_string())?;
+        if URL_ATTRIBUTES.contains(&key.as_str()) && !is_safe_url(&value) {
+            continue;
+        }
+        if key == "style" && !is_safe_style(&value) {
+            continue;
+        }
+        xhtml.push_str(&format!(" {}=\"{}\"", key, escape(value.as_ref())));
+    }
+    Ok(())
+}
+
+// Cleans a narrative received from elsewhere so it only holds what FHIR
+// allows in a narrative: scripts, forms, frames, event attributes such as
+// onclick and script links are removed. The result is a `div` in the XHTML
+// namespace; content that is not wrapped in one is wrapped. Fails when the
+// markup is not well-formed XML.
+pub fn sanitize_xhtml(xhtml: &str) -> Result<String, String> {
+    let xhtml = xhtml.trim();
+    let wrapped;
+    let source = match xhtml.starts_with("<div") {
+        true => xhtml,
+        false => {
+            wrapped = format!("<div>{}</div>", xhtml);
+            &wrapped
+        }
+    };
+    let mut reader = Reader::from_str(source);
+    let mut sanitized = String::new();
+    // Element names written to the output; None for unwrapped elements
+    let mut open: Vec<Option<String>> = vec![];
+    // Depth inside a dropped element
+    let mut dropped = 0;
+    loop {
+        match reader.read_event().map_err(|e| e.to_string())? {
+            Event::Start(start) => {
+                let name = element_name(&start);
+                if dropped > 0 || DROPPED_ELEMENTS.contains(&name.as_str()) {
+                    dropped += 1;
+                } else if open.is_empty() {
+                    sanitized.push_str(&format!("<div xmlns=\"{}\"", XHTML_NAMESPACE));
+                    write_attributes(&mut sanitized, &start)?;
+                    sanitized.push('>');
+                    open.push(Some("div".to_string()));
+                } else if ALLOWED_ELEMENTS.contains(&name.as_str()) {
+                    sanitized.push_str(&format!("<{}", name));
+                    write_attributes(&mut sanitized, &start)?;
+                    sanitized.push('>');
+                    open.push(Some(name));
+                } else {
+                    open.push(None);
+                }
+            }
+            Event::Empty(start) => {
+                let name = element_name(&start);
+                if dropped > 0 || DROPPED_ELEMENTS.contains(&name.as_str()) {
+                    continue;
+                }
+                if open.is_empty() {
+                    return Ok(format!("<div xmlns=\"{}\"/>", XHTML_NAMESPACE));
+                }
+                if ALLOWED_ELEMENTS.contains(&name.as_str()) {
+                    sanitized.push_str(&format!("<{}", name));
+                    write_attributes(&mut sanitized, &start)?;
+                    sanitized.push_str("/>");
+                }
+            }
+            Event::End(_) => {
+                if dropped > 0 {
+                    dropped -= 1;
+                    continue;
+                }
+                match open.pop() {
+                    Some(Some(name)) => sanitized.push_str(&format!("</{}>", name)),
+                    Some(None) => {}
+                    None => return Err("Unexpected closing tag".to_string()),
+                }
+                // Anything after the narrative div is not part of it
+                if open.is_empty() {
+                    return Ok(sanitized);
+                }
+            }
+            Event::Text(text) if dropped == 0 && !open.is_empty() => {
+                // FHIR narratives only use XML entities, but &nbsp; is common
+                let text = text
+                    .unescape_with(|entity| match entity {
+                        "nbsp" => Some("\u{a0}"),
+                        entity => resolve_predefined_entity(entity),
+                    })
+                    .map_err(|e| e.to_string())?;
+                sanitized.push_str(&escape(text.as_ref()));
+            }
+            Event::CData(data) if dropped == 0 && !open.is_empty() => {
+                sanitized.push_str(&escape(String::from_utf8_lossy(&data).as_ref()));
+            }
+            Event::Eof => return Err("Narrative div is not closed".to_string()),
+            // Comments, processing instructions and doctypes are left out
+            _ => {}
+        }
+    }
+}
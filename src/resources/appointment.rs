@@ -1,58 +1,133 @@
 use crate::{
     consts::{FHIR_RESOURCE_APPOINTMENT, NOSTR_KIND_APPOINTMENT},
-    datatypes::FhirInstant,
-    fhir_trait::{FhirResource, FhirText},
-    valuesets::{FhirAppointmentStatus, FhirSpecialty},
+    datatypes::{
+        deserialize_extensions, FhirCodeableConcept, FhirExtensible, FhirExtension, FhirInstant,
+        FhirUnknownElements,
+    },
+    fhir_trait::FhirResource,
+    locale::FhirLocalization,
+    narrative::{concepts_text, FhirNarrative},
+    resources::operation_outcome::FhirOperationOutcome,
+    valuesets::{
+        FhirAppointmentStatus, FhirCodedValue, FhirIssueType, FhirSpecialty, FhirSpecialtyGroup,
+    },
 };
-use serde::{Deserialize, Serialize, de::DeserializeOwned};
-use serde_json::Value;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct FhirAppointment<T> {
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FhirResource)]
+#[fhir(
+    resource_type = FHIR_RESOURCE_APPOINTMENT,
+    kind = NOSTR_KIND_APPOINTMENT,
+    text_with = narrative,
+    validate_with = validate_rules
+)]
+pub struct FhirAppointment {
     status: FhirAppointmentStatus,
-    #[serde(rename = "serviceCategory")]
-    service_category: T,
-    specialty: FhirSpecialty,
+    #[serde(
+        rename = "serviceCategory",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    service_category: Vec<FhirCodeableConcept>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    specialty: Vec<FhirCodeableConcept>,
+    #[fhir(narrative)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
     start: FhirInstant,
     end: FhirInstant,
-    description: Option<String>,
-    extension: Option<Value>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_extensions",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    extension: Vec<FhirExtension>,
+    #[serde(flatten)]
+    #[fhir(unknown)]
+    unknown: FhirUnknownElements,
 }
-impl<T> FhirAppointment<T>
-where
-    T: Serialize + Deserialize<'static> + Clone,
-{
+impl FhirAppointment {
     pub fn new(
         status: FhirAppointmentStatus,
-        service_category: T,
+        service_category: Vec<FhirCodeableConcept>,
         specialty: FhirSpecialty,
         start: FhirInstant,
         end: FhirInstant,
         description: Option<String>,
-        extension: Option<Value>,
+        extension: Vec<FhirExtension>,
     ) -> Self {
         Self {
             status,
             service_category,
-            specialty,
-            start,
-            end,
+            specialty: vec![specialty.into()],
+            start: start.to_utc(),
+            end: end.to_utc(),
             description,
             extension,
+            unknown: FhirUnknownElements::new(),
         }
     }
-    pub fn get_extension(&self) -> &Option<Value> {
-        &self.extension
-    }
     pub fn get_start(&self) -> &FhirInstant {
         &self.start
     }
     pub fn get_end(&self) -> &FhirInstant {
         &self.end
     }
-    pub fn get_specialty(&self) -> &FhirSpecialty {
+    // Start and end are stored in UTC; these render them in a clinic's zone
+    pub fn format_start(&self, zone: &str, language: &str) -> Result<String, String> {
+        self.start.format_local(zone, language)
+    }
+    pub fn format_end(&self, zone: &str, language: &str) -> Result<String, String> {
+        self.end.format_local(zone, language)
+    }
+    // The end shows only its time when it falls on the start's local date
+    pub fn format_schedule(&self, zone: &str, language: &str) -> Result<String, String> {
+        self.format_schedule_with(zone, FhirLocalization::defaults(), language)
+    }
+    pub fn format_schedule_with(
+        &self,
+        zone: &str,
+        localization: &FhirLocalization,
+        language: &str,
+    ) -> Result<String, String> {
+        let same_day =
+            self.start.in_zone(zone)?.date_naive() == self.end.in_zone(zone)?.date_naive();
+        let end = match same_day {
+            true => self
+                .end
+                .format_local_time_with(zone, localization, language)?,
+            false => self.end.format_local_with(zone, localization, language)?,
+        };
+        Ok(format!(
+            "{} - {}",
+            self.start.format_local_with(zone, localization, language)?,
+            end
+        ))
+    }
+    pub fn get_specialty(&self) -> Option<FhirSpecialty> {
+        self.specialty.iter().find_map(|concept| concept.decode())
+    }
+    pub fn get_specialties(&self) -> Vec<FhirSpecialty> {
+        self.specialty
+            .iter()
+            .flat_map(|concept| concept.decode_all())
+            .collect()
+    }
+    pub fn get_specialty_concepts(&self) -> &Vec<FhirCodeableConcept> {
         &self.specialty
     }
+    // True if any specialty is `specialty` or one of its sub-specialties
+    pub fn has_specialty(&self, specialty: FhirSpecialty) -> bool {
+        self.get_specialties().iter().any(|own| own.is_a(specialty))
+    }
+    pub fn in_specialty_group(&self, group: FhirSpecialtyGroup) -> bool {
+        self.get_specialties()
+            .iter()
+            .any(|specialty| specialty.in_group(group))
+    }
+    pub fn add_specialty(&mut self, specialty: FhirSpecialty) {
+        self.specialty.push(specialty.into());
+    }
     pub fn get_description(&self) -> Option<String> {
         self.description.clone()
     }
@@ -62,21 +137,63 @@ where
     pub fn get_status(&self) -> &FhirAppointmentStatus {
         &self.status
     }
-    pub fn get_service_category(&self) -> &T {
+    pub fn get_service_category(&self) -> &Vec<FhirCodeableConcept> {
         &self.service_category
     }
+    // The description over a table of status, schedule and specialties
+    pub fn narrative(&self, localization: &FhirLocalization, language: &str) -> FhirNarrative {
+        let label = |label| localization.label(label, language);
+        let heading = match &self.description {
+            Some(description) => description.as_str(),
+            None => label("Appointment"),
+        };
+        FhirNarrative::new()
+            .with_heading(heading)
+            .with_row(
+                label("Status"),
+                localization.display_of(&self.status, language),
+            )
+            .with_row(
+                label("Service category"),
+                &concepts_text(&self.service_category, localization, language),
+            )
+            .with_row(
+                label("Specialty"),
+                &concepts_text(&self.specialty, localization, language),
+            )
+            .with_row(
+                label("Start"),
+                &self.start.format_narrative(localization, language),
+            )
+            .with_row(
+                label("End"),
+                &self.end.format_narrative(localization, language),
+            )
+    }
+    fn validate_rules(&self) -> FhirOperationOutcome {
+        let mut outcome = FhirOperationOutcome::new();
+        // app-3: the appointment cannot end before it starts
+        if self.end < self.start {
+            outcome.error(
+                FhirIssueType::Invariant,
+                "Appointment.end",
+                format!("app-3: end {} is before start {}", self.end, self.start),
+            );
+        }
+        outcome.check_binding::<FhirSpecialty>("Appointment.specialty", &self.specialty);
+        outcome
+    }
+    pub fn get_service_category_as<T: FhirCodedValue>(&self) -> Option<T> {
+        self.service_category
+            .iter()
+            .find_map(|concept| concept.decode())
+    }
 }
-impl<T> FhirResource for FhirAppointment<T>
-where
-    T: Serialize + DeserializeOwned + Clone,
-{
-    fn nostr_kind(&self) -> u32 {
-        NOSTR_KIND_APPOINTMENT
-    }
-    fn resource_type(&self) -> &str {
-        FHIR_RESOURCE_APPOINTMENT
-    }
-    fn text(&self) -> FhirText {
-        FhirText::new(self.description.clone().unwrap_or_default())
+impl FhirExtensible for FhirAppointment {
+    fn extensions(&self) -> &Vec<FhirExtension> {
+        &self.extension
+    }
+    fn extensions_mut(&mut self) -> &mut Vec<FhirExtension> {
+        &mut self.extension
     }
 }
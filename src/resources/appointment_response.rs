@@ -1,42 +1,62 @@
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use serde_json::Value;
 
 use crate::{
     consts::{FHIR_RESOURCE_APPOINTMENT_RESPONSE, NOSTR_KIND_APPOINTMENT_RESPONSE},
-    datatypes::FhirInstant,
-    fhir_trait::{FhirReference, FhirResource, FhirText},
-    valuesets::{FhirAppointmentResponseStatus, FhirAppointmentStatus},
+    datatypes::{
+        deserialize_extensions, FhirExtensible, FhirExtension, FhirInstant, FhirUnknownElements,
+    },
+    fhir_trait::{FhirReference, FhirResource},
+    locale::FhirLocalization,
+    narrative::FhirNarrative,
+    resources::operation_outcome::FhirOperationOutcome,
+    valuesets::{FhirAppointmentResponseStatus, FhirAppointmentStatus, FhirIssueType},
 };
 
 use super::appointment::FhirAppointment;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct FhirAppointmentResponse<A, P> {
-    appointment: FhirAppointment<A>,
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FhirResource)]
+#[fhir(
+    resource_type = FHIR_RESOURCE_APPOINTMENT_RESPONSE,
+    kind = NOSTR_KIND_APPOINTMENT_RESPONSE,
+    text_with = narrative,
+    validate_with = validate_rules,
+    register = FhirAppointmentResponse<serde_json::Value>
+)]
+pub struct FhirAppointmentResponse<P> {
+    appointment: FhirAppointment,
+    #[serde(rename = "proposedNewTime")]
+    proposed_new_time: bool,
     start: FhirInstant,
     end: FhirInstant,
     actor: FhirReference<P>,
     #[serde(rename = "participantStatus")]
     participant_status: FhirAppointmentResponseStatus,
-    #[serde(rename = "proposedNewTime")]
-    proposed_new_time: bool,
+    #[fhir(narrative)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     comment: Option<String>,
-    extension: Option<Value>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_extensions",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    extension: Vec<FhirExtension>,
+    #[serde(flatten)]
+    #[fhir(unknown)]
+    unknown: FhirUnknownElements,
 }
 
-impl<A, P> FhirAppointmentResponse<A, P>
+impl<P> FhirAppointmentResponse<P>
 where
-    A: Serialize + Deserialize<'static> + Clone,
     P: Serialize + Deserialize<'static> + Clone,
 {
     pub fn new(
-        appointment: FhirAppointment<A>,
+        appointment: FhirAppointment,
         actor: FhirReference<P>,
         comment: Option<String>,
-        extension: Option<Value>,
+        extension: Vec<FhirExtension>,
     ) -> Self {
-        let start = appointment.get_start().clone();
-        let end = appointment.get_end().clone();
+        let start = *appointment.get_start();
+        let end = *appointment.get_end();
         let status = match appointment.get_status() {
             FhirAppointmentStatus::Booked => FhirAppointmentResponseStatus::Accepted,
             FhirAppointmentStatus::Cancelled => FhirAppointmentResponseStatus::Declined,
@@ -51,16 +71,17 @@ where
             proposed_new_time: false,
             comment,
             extension,
+            unknown: FhirUnknownElements::new(),
         }
     }
     pub fn propose_new_time(
-        appointment: FhirAppointment<A>,
+        appointment: FhirAppointment,
         actor: FhirReference<P>,
         comment: Option<String>,
-        extension: Option<Value>,
+        extension: Vec<FhirExtension>,
     ) -> Self {
-        let start = appointment.get_start().clone();
-        let end = appointment.get_end().clone();
+        let start = *appointment.get_start();
+        let end = *appointment.get_end();
         Self {
             appointment,
             start,
@@ -70,12 +91,10 @@ where
             proposed_new_time: true,
             comment,
             extension,
+            unknown: FhirUnknownElements::new(),
         }
     }
-    pub fn get_extension(&self) -> &Option<Value> {
-        &self.extension
-    }
-    pub fn get_fhir_appointment(&self) -> &FhirAppointment<A> {
+    pub fn get_fhir_appointment(&self) -> &FhirAppointment {
         &self.appointment
     }
     pub fn get_fhir_actor(&self) -> &FhirReference<P> {
@@ -94,19 +113,70 @@ where
         self.comment.clone()
     }
 }
-
-impl<A, P> FhirResource for FhirAppointmentResponse<A, P>
+impl<P> FhirAppointmentResponse<P>
 where
-    A: Serialize + DeserializeOwned + Clone,
     P: Serialize + DeserializeOwned + Clone,
 {
-    fn nostr_kind(&self) -> u32 {
-        NOSTR_KIND_APPOINTMENT_RESPONSE
+    // The appointment's schedule, with the participant's answer as a table
+    pub fn narrative(&self, localization: &FhirLocalization, language: &str) -> FhirNarrative {
+        let label = |label| localization.label(label, language);
+        let actor = match self.actor.get_display().is_empty() {
+            true => self.actor.get_literal(),
+            false => self.actor.get_display(),
+        };
+        let participant = vec![
+            actor.to_string(),
+            localization
+                .display_of(&self.participant_status, language)
+                .to_string(),
+            self.comment.clone().unwrap_or_default(),
+        ];
+        let proposed = match self.proposed_new_time {
+            true => label("Yes"),
+            false => "",
+        };
+        FhirNarrative::new()
+            .with_heading(&self.appointment.display())
+            .with_row(
+                label("Start"),
+                &self.start.format_narrative(localization, language),
+            )
+            .with_row(
+                label("End"),
+                &self.end.format_narrative(localization, language),
+            )
+            .with_row(label("Proposed new time"), proposed)
+            .with_table(
+                &[label("Participant"), label("Status"), label("Comment")],
+                vec![participant],
+            )
+    }
+    fn validate_rules(&self) -> FhirOperationOutcome {
+        let mut outcome = FhirOperationOutcome::new();
+        if self.end < self.start {
+            outcome.error(
+                FhirIssueType::Invariant,
+                "AppointmentResponse.end",
+                format!("end {} is before start {}", self.end, self.start),
+            );
+        }
+        if self.actor.get_literal().trim().is_empty() {
+            outcome.error(
+                FhirIssueType::Required,
+                "AppointmentResponse.actor",
+                "Response has no actor reference",
+            );
+        }
+        outcome.merge(self.appointment.validate());
+        outcome
     }
-    fn resource_type(&self) -> &str {
-        FHIR_RESOURCE_APPOINTMENT_RESPONSE
+}
+
+impl<P> FhirExtensible for FhirAppointmentResponse<P> {
+    fn extensions(&self) -> &Vec<FhirExtension> {
+        &self.extension
     }
-    fn text(&self) -> FhirText {
-        FhirText::new(self.comment.clone().unwrap_or_default())
+    fn extensions_mut(&mut self) -> &mut Vec<FhirExtension> {
+        &mut self.extension
     }
 }
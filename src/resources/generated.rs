@@ -0,0 +1,226 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use super::operation_outcome::FhirOperationOutcome;
+use crate::{terminology::FhirTerminology, valuesets::FhirIssueType};
+
+// https://www.hl7.org/fhir/terminologies.html#strength
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FhirBindingStrength {
+    Required,
+    Extensible,
+    Preferred,
+    Example,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FhirElementBinding {
+    pub strength: FhirBindingStrength,
+    // Canonical url without its version
+    pub value_set: &'static str,
+}
+
+// https://www.hl7.org/fhir/elementdefinition.html
+// One element of a generated resource, as read from its StructureDefinition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FhirElementDefinition {
+    pub path: &'static str,
+    pub min: u32,
+    // None for "*"
+    pub max: Option<u32>,
+    pub types: &'static [&'static str],
+    pub binding: Option<FhirElementBinding>,
+}
+
+// https://www.hl7.org/fhir/structuredefinition.html
+pub trait FhirStructureDefinition {
+    const URL: &'static str;
+    const FHIR_VERSION: &'static str;
+    const ELEMENTS: &'static [FhirElementDefinition];
+    fn element(path: &str) -> Option<&'static FhirElementDefinition> {
+        Self::ELEMENTS.iter().find(|element| element.path == path)
+    }
+}
+
+// Cardinality and binding checks for a generated resource, read from the
+// elements of its StructureDefinition
+pub fn validate_structure<T: FhirStructureDefinition + Serialize>(
+    resource: &T,
+) -> FhirOperationOutcome {
+    let mut outcome = FhirOperationOutcome::new();
+    let root = T::ELEMENTS
+        .first()
+        .and_then(|element| element.path.split('.').next())
+        .unwrap_or_default();
+    match serde_json::to_value(resource) {
+        Ok(json) => check_elements(&mut outcome, T::ELEMENTS, root, root, &json),
+        Err(e) => outcome.error(FhirIssueType::Structure, root, e.to_string()),
+    }
+    outcome
+}
+
+fn parent(path: &str) -> &str {
+    path.rsplit_once('.')
+        .map(|(parent, _)| parent)
+        .unwrap_or_default()
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(text) => text.is_empty(),
+        Value::Array(items) => items.iter().all(is_empty),
+        Value::Object(map) => map.values().all(is_empty),
+        _ => false,
+    }
+}
+
+fn check_elements(
+    outcome: &mut FhirOperationOutcome,
+    elements: &[FhirElementDefinition],
+    definition_path: &str,
+    expression: &str,
+    json: &Value,
+) {
+    for element in elements
+        .iter()
+        .filter(|element| parent(element.path) == definition_path)
+    {
+        let name = element.path.rsplit('.').next().unwrap_or_default();
+        let expression = format!("{}.{}", expression, name);
+        let values: Vec<&Value> = match name.strip_suffix("[x]") {
+            Some(base) => json
+                .as_object()
+                .map(|map| {
+                    map.iter()
+                        .filter(|(key, _)| {
+                            key.strip_prefix(base).is_some_and(|rest| {
+                                rest.starts_with(|c: char| c.is_ascii_uppercase())
+                            })
+                        })
+                        .map(|(_, value)| value)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            None => match json.get(name) {
+                None | Some(Value::Null) => vec![],
+                Some(Value::Array(items)) => items.iter().collect(),
+                Some(value) => vec![value],
+            },
+        };
+        // FHIR has no empty values, so "" and {} count as missing
+        let values: Vec<&Value> = values
+            .into_iter()
+            .filter(|value| !is_empty(value))
+            .collect();
+        let count = values.len() as u32;
+        if count < element.min {
+            outcome.error(
+                FhirIssueType::Required,
+                &expression,
+                format!("At least {} required, found {}", element.min, count),
+            );
+        }
+        if element.max.is_some_and(|max| count > max) {
+            outcome.error(
+                FhirIssueType::Structure,
+                &expression,
+                format!(
+                    "At most {} allowed, found {}",
+                    element.max.unwrap_or_default(),
+                    count
+                ),
+            );
+        }
+        // Backbone elements, or elements reusing one through "#Path"
+        let child_path = match element.types {
+            [reference] if reference.starts_with('#') => &reference[1..],
+            _ => element.path,
+        };
+        let has_children = elements
+            .iter()
+            .any(|child| parent(child.path) == child_path);
+        for (index, value) in values.into_iter().enumerate() {
+            let expression = match element.max {
+                Some(1) => expression.clone(),
+                _ => format!("{}[{}]", expression, index),
+            };
+            if let Some(binding) = &element.binding {
+                check_binding(outcome, binding, &expression, value);
+            }
+            if has_children {
+                check_elements(outcome, elements, child_path, &expression, value);
+            }
+        }
+    }
+}
+
+// Only value sets known to the default terminology are checked. HL7 value sets
+// that include a whole code system are also looked up by the code system url,
+// which is how compiled-in enums are registered.
+fn check_binding(
+    outcome: &mut FhirOperationOutcome,
+    binding: &FhirElementBinding,
+    expression: &str,
+    value: &Value,
+) {
+    if !matches!(
+        binding.strength,
+        FhirBindingStrength::Required | FhirBindingStrength::Extensible
+    ) {
+        return;
+    }
+    let terminology = FhirTerminology::defaults();
+    let Some(expansion) = [
+        binding.value_set.to_string(),
+        binding.value_set.replace("/ValueSet/", "/"),
+    ]
+    .iter()
+    .find_map(|url| terminology.expand(url).ok()) else {
+        return;
+    };
+    let codings: Vec<(Option<&str>, &str)> = match value {
+        Value::String(code) => vec![(None, code.as_str())],
+        Value::Object(map) => match map.get("coding").and_then(Value::as_array) {
+            Some(codings) => codings.iter().filter_map(system_and_code).collect(),
+            None => system_and_code(value).into_iter().collect(),
+        },
+        _ => vec![],
+    };
+    // Text-only concepts carry nothing to check
+    if codings.is_empty() {
+        return;
+    }
+    let found = codings.iter().any(|(system, code)| {
+        expansion.iter().any(|contains| {
+            contains.code.as_deref() == Some(*code)
+                && system.is_none_or(|system| contains.system.as_deref() == Some(system))
+        })
+    });
+    if !found {
+        let diagnostics = format!("Not in value set {}", binding.value_set);
+        match binding.strength {
+            FhirBindingStrength::Required => {
+                outcome.error(FhirIssueType::CodeInvalid, expression, diagnostics)
+            }
+            _ => outcome.warning(FhirIssueType::CodeInvalid, expression, diagnostics),
+        }
+    }
+}
+
+fn system_and_code(coding: &Value) -> Option<(Option<&str>, &str)> {
+    Some((
+        coding.get("system").and_then(Value::as_str),
+        coding.get("code")?.as_str()?,
+    ))
+}
+
+// Resources generated by build.rs from the StructureDefinitions in
+// `definitions/<version>/`. Each one is behind a cargo feature named after the
+// version and resource, e.g. `r4-patient`.
+pub mod r4 {
+    include!(concat!(env!("OUT_DIR"), "/r4.rs"));
+}
+pub mod r5 {
+    include!(concat!(env!("OUT_DIR"), "/r5.rs"));
+}
@@ -1,4 +1,19 @@
 pub mod appointment;
 pub mod appointment_response;
+pub mod operation_outcome;
 pub mod organization;
 pub mod practitioner;
+mod generated;
+mod payload;
+mod registry;
+mod versions;
+
+pub use generated::{
+    r4, r5, validate_structure, FhirBindingStrength, FhirElementBinding, FhirElementDefinition,
+    FhirStructureDefinition,
+};
+pub use payload::{migrate_payload, IgnisPayload};
+pub use registry::{
+    FhirResourceDefinition, FhirResourceDescriptor, FhirResourceRegistration, FhirResourceRegistry,
+};
+pub use versions::{from_fhir_version, to_fhir_version, FhirConversion, FhirVersion};
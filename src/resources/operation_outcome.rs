@@ -0,0 +1,246 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    datatypes::{FhirCodeableConcept, FhirContactPoint},
+    fhir_trait::FhirIdentifier,
+    identifiers::FhirIdentifierRegistry,
+    valuesets::{FhirCodedValue, FhirIssueSeverity, FhirIssueType},
+};
+
+// https://www.hl7.org/fhir/operationoutcome.html#OperationOutcome.issue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirOperationOutcomeIssue {
+    severity: FhirIssueSeverity,
+    code: FhirIssueType,
+    diagnostics: String,
+    // FHIRPath of the offending element, e.g. "Appointment.end"
+    expression: Vec<String>,
+}
+impl FhirOperationOutcomeIssue {
+    pub fn new(
+        severity: FhirIssueSeverity,
+        code: FhirIssueType,
+        expression: &str,
+        diagnostics: impl Into<String>,
+    ) -> Self {
+        Self {
+            severity,
+            code,
+            diagnostics: diagnostics.into(),
+            expression: vec![expression.to_string()],
+        }
+    }
+    pub fn get_severity(&self) -> FhirIssueSeverity {
+        self.severity
+    }
+    pub fn get_code(&self) -> FhirIssueType {
+        self.code
+    }
+    pub fn get_diagnostics(&self) -> &str {
+        &self.diagnostics
+    }
+    pub fn get_expression(&self) -> &[String] {
+        &self.expression
+    }
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self.severity,
+            FhirIssueSeverity::Fatal | FhirIssueSeverity::Error
+        )
+    }
+}
+impl Display for FhirOperationOutcomeIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}: {}",
+            self.severity.fhir_code(),
+            self.expression.join(", "),
+            self.diagnostics
+        )
+    }
+}
+
+// https://www.hl7.org/fhir/operationoutcome.html
+// Result of validating a resource. No issues, or only warnings, means valid.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirOperationOutcome {
+    issue: Vec<FhirOperationOutcomeIssue>,
+}
+impl FhirOperationOutcome {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn add_issue(&mut self, issue: FhirOperationOutcomeIssue) {
+        self.issue.push(issue);
+    }
+    pub fn error(&mut self, code: FhirIssueType, expression: &str, diagnostics: impl Into<String>) {
+        self.add_issue(FhirOperationOutcomeIssue::new(
+            FhirIssueSeverity::Error,
+            code,
+            expression,
+            diagnostics,
+        ));
+    }
+    pub fn warning(
+        &mut self,
+        code: FhirIssueType,
+        expression: &str,
+        diagnostics: impl Into<String>,
+    ) {
+        self.add_issue(FhirOperationOutcomeIssue::new(
+            FhirIssueSeverity::Warning,
+            code,
+            expression,
+            diagnostics,
+        ));
+    }
+    pub fn information(
+        &mut self,
+        code: FhirIssueType,
+        expression: &str,
+        diagnostics: impl Into<String>,
+    ) {
+        self.add_issue(FhirOperationOutcomeIssue::new(
+            FhirIssueSeverity::Information,
+            code,
+            expression,
+            diagnostics,
+        ));
+    }
+    // Records the error of a datatype's own `validate`
+    pub fn check(&mut self, code: FhirIssueType, expression: &str, result: Result<(), String>) {
+        if let Err(e) = result {
+            self.error(code, expression, e);
+        }
+    }
+    // Identifier values against the default validators for their system
+    pub fn check_identifiers(&mut self, expression: &str, identifiers: &[FhirIdentifier]) {
+        for (index, identifier) in identifiers.iter().enumerate() {
+            self.check(
+                FhirIssueType::Value,
+                &format!("{}[{}]", expression, index),
+                FhirIdentifierRegistry::defaults().validate(identifier),
+            );
+        }
+    }
+    pub fn check_contact_points(&mut self, expression: &str, contacts: &[FhirContactPoint]) {
+        for (index, contact) in contacts.iter().enumerate() {
+            let expression = format!("{}[{}]", expression, index);
+            if contact.value.trim().is_empty() {
+                self.error(
+                    FhirIssueType::Required,
+                    &format!("{}.value", expression),
+                    "Contact point has no value",
+                );
+            }
+            if let Some(period) = &contact.period {
+                self.check(
+                    FhirIssueType::Invariant,
+                    &format!("{}.period", expression),
+                    period.validate(),
+                );
+            }
+        }
+    }
+    // Extensible binding to a value set enum: codings outside it are allowed
+    // but reported, and a concept with neither coding nor text is an error
+    pub fn check_binding<T: FhirCodedValue>(
+        &mut self,
+        expression: &str,
+        concepts: &[FhirCodeableConcept],
+    ) {
+        for (index, concept) in concepts.iter().enumerate() {
+            let expression = format!("{}[{}]", expression, index);
+            if concept.coding.is_empty()
+                && concept
+                    .text
+                    .as_deref()
+                    .unwrap_or_default()
+                    .trim()
+                    .is_empty()
+            {
+                self.error(
+                    FhirIssueType::Required,
+                    &expression,
+                    "Concept has no coding or text",
+                );
+            } else if !concept.coding.is_empty() && concept.decode::<T>().is_none() {
+                self.warning(
+                    FhirIssueType::CodeInvalid,
+                    &expression,
+                    "No coding is from the bound value set",
+                );
+            }
+        }
+    }
+    pub fn merge(&mut self, other: FhirOperationOutcome) {
+        self.issue.extend(other.issue);
+    }
+    pub fn get_issues(&self) -> &[FhirOperationOutcomeIssue] {
+        &self.issue
+    }
+    pub fn errors(&self) -> Vec<&FhirOperationOutcomeIssue> {
+        self.issue.iter().filter(|issue| issue.is_error()).collect()
+    }
+    pub fn is_valid(&self) -> bool {
+        !self.issue.iter().any(FhirOperationOutcomeIssue::is_error)
+    }
+    // Fails with the error diagnostics joined, keeping warnings on success
+    pub fn into_result(self) -> Result<Self, String> {
+        match self.is_valid() {
+            true => Ok(self),
+            false => Err(self
+                .errors()
+                .iter()
+                .map(|issue| issue.to_string())
+                .collect::<Vec<_>>()
+                .join("; ")),
+        }
+    }
+    pub fn to_fhir_json(&self) -> Result<Value, String> {
+        let issues: Vec<Value> = self
+            .issue
+            .iter()
+            .map(|issue| {
+                json!({
+                    "severity": issue.severity.fhir_code(),
+                    "code": issue.code.fhir_code(),
+                    "diagnostics": issue.diagnostics,
+                    "expression": issue.expression,
+                })
+            })
+            .collect();
+        Ok(json!({
+            "resourceType": "OperationOutcome",
+            "issue": issues,
+        }))
+    }
+}
+
+// When IgnisResource validates its resource, before signing and after decoding
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FhirValidationMode {
+    // Skip validation
+    Off,
+    // Validate and keep the outcome, but never fail
+    Warn,
+    // Fail when the outcome has errors
+    #[default]
+    Enforce,
+}
+impl FhirValidationMode {
+    pub fn run(
+        &self,
+        validate: impl FnOnce() -> FhirOperationOutcome,
+    ) -> Result<FhirOperationOutcome, String> {
+        match self {
+            Self::Off => Ok(FhirOperationOutcome::new()),
+            Self::Warn => Ok(validate()),
+            Self::Enforce => validate().into_result(),
+        }
+    }
+}
@@ -1,42 +1,186 @@
 use crate::{
     consts::{FHIR_RESOURCE_ORGANIZATION, NOSTR_KIND_ORGANIZATION},
-    fhir_trait::{FhirResource, FhirText},
+    datatypes::{
+        deserialize_extensions, FhirCodeableConcept, FhirExtensible, FhirExtension,
+        FhirUnknownElements,
+    },
+    fhir_trait::{FhirIdentifiable, FhirIdentifier, FhirResource},
+    identifiers::FhirIdentifierRegistry,
+    locale::FhirLocalization,
     metadata::FhirExtendedContactDetail,
-    valuesets::FhirOrganizationType,
+    narrative::{concepts_text, FhirNarrative},
+    resources::operation_outcome::FhirOperationOutcome,
+    valuesets::{FhirIssueType, FhirOrganizationType},
 };
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FhirResource)]
+#[fhir(
+    resource_type = FHIR_RESOURCE_ORGANIZATION,
+    kind = NOSTR_KIND_ORGANIZATION,
+    text_with = narrative,
+    validate_with = validate_rules
+)]
 pub struct FhirOrganization {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    identifier: Vec<FhirIdentifier>,
+    #[serde(rename = "type", default, skip_serializing_if = "Vec::is_empty")]
+    org_type: Vec<FhirCodeableConcept>,
+    #[fhir(narrative)]
     name: String,
-    #[serde(rename = "type")]
-    org_type: FhirOrganizationType,
-    contact: Vec<FhirExtendedContactDetail>,
     description: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    contact: Vec<FhirExtendedContactDetail>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_extensions",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    extension: Vec<FhirExtension>,
+    #[serde(flatten)]
+    #[fhir(unknown)]
+    unknown: FhirUnknownElements,
 }
 impl FhirOrganization {
+    // Identifiers are checked against the validators for their system
     pub fn new(
+        identifier: Vec<FhirIdentifier>,
         name: String,
         org_type: FhirOrganizationType,
         contact: Vec<FhirExtendedContactDetail>,
         description: String,
-    ) -> Self {
-        Self {
+    ) -> Result<Self, String> {
+        Self::new_with(
+            identifier,
             name,
             org_type,
             contact,
             description,
+            FhirIdentifierRegistry::defaults(),
+        )
+    }
+    pub fn new_with(
+        identifier: Vec<FhirIdentifier>,
+        name: String,
+        org_type: FhirOrganizationType,
+        contact: Vec<FhirExtendedContactDetail>,
+        description: String,
+        registry: &FhirIdentifierRegistry,
+    ) -> Result<Self, String> {
+        let mut organization = Self {
+            identifier: vec![],
+            name,
+            org_type: vec![org_type.into()],
+            contact,
+            description,
+            extension: vec![],
+            unknown: FhirUnknownElements::new(),
+        };
+        for identifier in identifier {
+            organization.add_identifier_with(identifier, registry)?;
         }
+        Ok(organization)
+    }
+    // Checks the identifier against the validators for its system before adding it
+    pub fn add_identifier(&mut self, identifier: FhirIdentifier) -> Result<(), String> {
+        self.add_identifier_with(identifier, FhirIdentifierRegistry::defaults())
+    }
+    pub fn add_identifier_with(
+        &mut self,
+        identifier: FhirIdentifier,
+        registry: &FhirIdentifierRegistry,
+    ) -> Result<(), String> {
+        registry.validate(&identifier)?;
+        self.identifier.push(identifier);
+        Ok(())
+    }
+    pub fn get_identifiers(&self) -> &Vec<FhirIdentifier> {
+        &self.identifier
+    }
+    pub fn get_org_type(&self) -> Option<FhirOrganizationType> {
+        self.org_type.iter().find_map(|concept| concept.decode())
+    }
+    pub fn get_org_type_concepts(&self) -> &Vec<FhirCodeableConcept> {
+        &self.org_type
+    }
+    // Name, type and identifiers, with a table of contacts
+    pub fn narrative(&self, localization: &FhirLocalization, language: &str) -> FhirNarrative {
+        let label = |label| localization.label(label, language);
+        let identifiers: Vec<&str> = self
+            .identifier
+            .iter()
+            .map(FhirIdentifier::get_value)
+            .collect();
+        let contacts = self
+            .contact
+            .iter()
+            .map(|contact| {
+                vec![
+                    contact.name.clone().unwrap_or_default(),
+                    contact
+                        .telecom
+                        .iter()
+                        .map(|telecom| telecom.value.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    contact
+                        .address
+                        .as_ref()
+                        .map(|address| address.display(language))
+                        .unwrap_or_default(),
+                ]
+            })
+            .collect();
+        FhirNarrative::new()
+            .with_heading(&self.name)
+            .with_row(
+                label("Type"),
+                &concepts_text(&self.org_type, localization, language),
+            )
+            .with_row(label("Identifier"), &identifiers.join(", "))
+            .with_paragraph(&self.description)
+            .with_table(
+                &[label("Contact"), label("Telecom"), label("Address")],
+                contacts,
+            )
+    }
+    fn validate_rules(&self) -> FhirOperationOutcome {
+        let mut outcome = FhirOperationOutcome::new();
+        if self.name.trim().is_empty() {
+            outcome.error(
+                FhirIssueType::Required,
+                "Organization.name",
+                "Organization has no name",
+            );
+        }
+        outcome.check_identifiers("Organization.identifier", &self.identifier);
+        outcome.check_binding::<FhirOrganizationType>("Organization.type", &self.org_type);
+        for (index, contact) in self.contact.iter().enumerate() {
+            outcome.check_contact_points(
+                &format!("Organization.contact[{}].telecom", index),
+                &contact.telecom,
+            );
+            if let Some(period) = &contact.period {
+                outcome.check(
+                    FhirIssueType::Invariant,
+                    &format!("Organization.contact[{}].period", index),
+                    period.validate(),
+                );
+            }
+        }
+        outcome
     }
 }
-impl FhirResource for FhirOrganization {
-    fn nostr_kind(&self) -> u32 {
-        NOSTR_KIND_ORGANIZATION
+impl FhirIdentifiable for FhirOrganization {
+    fn identifiers(&self) -> &[FhirIdentifier] {
+        &self.identifier
     }
-    fn resource_type(&self) -> &str {
-        FHIR_RESOURCE_ORGANIZATION
+}
+impl FhirExtensible for FhirOrganization {
+    fn extensions(&self) -> &Vec<FhirExtension> {
+        &self.extension
     }
-    fn text(&self) -> FhirText {
-        FhirText::new(self.name.clone())
+    fn extensions_mut(&mut self) -> &mut Vec<FhirExtension> {
+        &mut self.extension
     }
 }
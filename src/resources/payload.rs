@@ -0,0 +1,258 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::versions::rename;
+use crate::{
+    consts::{
+        FHIR_RESOURCE_APPOINTMENT, FHIR_RESOURCE_APPOINTMENT_RESPONSE, FHIR_RESOURCE_ORGANIZATION,
+        FHIR_RESOURCE_PRACTITIONER, IGNIS_SCHEMA_VERSION, IGNIS_SCHEMA_VERSION_ELEMENT,
+    },
+    datatypes::{FhirCodeableConcept, FhirExtension},
+    valuesets::{FhirCodedValue, FhirContactEntityType, FhirOrganizationType, FhirSpecialty},
+};
+
+// Note content since schema 2: the resource's JSON wrapped with what a reader
+// needs to decode it, e.g.
+// {"schemaVersion": 3, "fhirVersion": "5.0.0", "contentType": "Practitioner", "resource": {...}}
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IgnisPayload {
+    #[serde(rename = "schemaVersion")]
+    schema_version: u32,
+    // Release of the FHIR model the resource follows
+    #[serde(rename = "fhirVersion")]
+    fhir_version: String,
+    // Resource type of the content
+    #[serde(rename = "contentType")]
+    content_type: String,
+    resource: Value,
+}
+impl IgnisPayload {
+    pub fn new(content_type: &str, fhir_version: &str, resource: Value) -> Self {
+        Self {
+            schema_version: IGNIS_SCHEMA_VERSION,
+            fhir_version: fhir_version.to_string(),
+            content_type: content_type.to_string(),
+            resource,
+        }
+    }
+    pub fn with_schema_version(mut self, schema_version: u32) -> Self {
+        self.schema_version = schema_version;
+        self
+    }
+    // Reads note content of any schema, upgrading older ones to the current
+    // one: bare resource JSON (0), resource JSON led by ignisSchemaVersion (1)
+    // and envelopes (2 onward). The schema version stays the one the content
+    // was written with. Content from a newer schema is read as it is.
+    pub fn from_content(
+        content: &str,
+        content_type: &str,
+        fhir_version: &str,
+    ) -> Result<Self, String> {
+        let mut json: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        let map = json
+            .as_object_mut()
+            .ok_or("Note content must be a JSON object")?;
+        let mut payload = match map.contains_key("schemaVersion") && map.contains_key("resource") {
+            true => serde_json::from_value::<Self>(json).map_err(|e| e.to_string())?,
+            false => {
+                let schema_version = match map.shift_remove(IGNIS_SCHEMA_VERSION_ELEMENT) {
+                    Some(version) => version
+                        .as_u64()
+                        .and_then(|version| u32::try_from(version).ok())
+                        .ok_or("Invalid payload schema version")?,
+                    None => 0,
+                };
+                Self::new(content_type, fhir_version, json).with_schema_version(schema_version)
+            }
+        };
+        if payload.content_type != content_type {
+            return Err(format!(
+                "Expected a {} payload, found {}",
+                content_type, payload.content_type
+            ));
+        }
+        if !same_release(&payload.fhir_version, fhir_version) {
+            return Err(format!(
+                "{} payload is FHIR {}, expected {}",
+                content_type, payload.fhir_version, fhir_version
+            ));
+        }
+        migrate_payload(
+            &payload.content_type,
+            &mut payload.resource,
+            payload.schema_version,
+        );
+        Ok(payload)
+    }
+    pub fn to_content(&self) -> Result<String, String> {
+        serde_json::to_string(self).map_err(|e| e.to_string())
+    }
+    pub fn get_schema_version(&self) -> u32 {
+        self.schema_version
+    }
+    pub fn get_fhir_version(&self) -> &str {
+        &self.fhir_version
+    }
+    pub fn get_content_type(&self) -> &str {
+        &self.content_type
+    }
+    pub fn get_resource(&self) -> &Value {
+        &self.resource
+    }
+    pub fn into_resource(self) -> Value {
+        self.resource
+    }
+    // Written by a newer client
+    pub fn is_newer_schema(&self) -> bool {
+        self.schema_version > IGNIS_SCHEMA_VERSION
+    }
+}
+
+// "5.0.0" and "5.0.0-ballot" are the same release; patches do not change the model
+fn same_release(found: &str, expected: &str) -> bool {
+    let release = |version: &str| {
+        version
+            .split(['.', '-'])
+            .take(2)
+            .collect::<Vec<_>>()
+            .join(".")
+    };
+    release(found) == release(expected)
+}
+
+// Upgrades a resource's JSON to the current schema, one step at a time:
+// MIGRATIONS[n] turns schema n into schema n + 1
+pub fn migrate_payload(content_type: &str, resource: &mut Value, schema_version: u32) {
+    for migration in MIGRATIONS.iter().skip(schema_version as usize) {
+        migration(content_type, resource);
+    }
+}
+
+const MIGRATIONS: [fn(&str, &mut Value); 3] =
+    [coded_elements, practitioner_telecom, fhir_json_datatypes];
+
+// Schema 1 added the ignisSchemaVersion member along with the datatypes still
+// in use: coded elements became CodeableConcept lists and extensions a list of
+// Extensions. Schema 0 wrote value set enums by variant name, any JSON as the
+// service category and any JSON, or null, as the extension.
+fn coded_elements(content_type: &str, resource: &mut Value) {
+    match content_type {
+        FHIR_RESOURCE_APPOINTMENT => upgrade_appointment(resource),
+        FHIR_RESOURCE_APPOINTMENT_RESPONSE => {
+            if let Some(appointment) = resource.get_mut("appointment") {
+                upgrade_appointment(appointment);
+            }
+            upgrade_extension(resource);
+            // Practitioner names are HumanNames, Organization names text
+            if let Some(actor) = resource.pointer_mut("/actor/type") {
+                match actor.get("name").is_some_and(Value::is_object) {
+                    true => upgrade_extension(actor),
+                    false => upgrade_organization(actor),
+                }
+            }
+        }
+        FHIR_RESOURCE_ORGANIZATION => upgrade_organization(resource),
+        FHIR_RESOURCE_PRACTITIONER => upgrade_extension(resource),
+        _ => {}
+    }
+}
+
+fn upgrade_appointment(appointment: &mut Value) {
+    if let Some(category) = appointment.get_mut("serviceCategory") {
+        *category = concepts(category.take(), |_| None);
+    }
+    if let Some(specialty) = appointment.get_mut("specialty") {
+        *specialty = concepts(specialty.take(), coded::<FhirSpecialty>);
+    }
+    upgrade_extension(appointment);
+}
+
+fn upgrade_organization(organization: &mut Value) {
+    if let Some(org_type) = organization.get_mut("type") {
+        *org_type = concepts(org_type.take(), coded::<FhirOrganizationType>);
+    }
+    if let Some(Value::Array(contacts)) = organization.get_mut("contact") {
+        for purpose in contacts.iter_mut().filter_map(|contact| contact.get_mut("purpose")) {
+            if !purpose.is_null() {
+                *purpose = concept(purpose.take(), coded::<FhirContactEntityType>);
+            }
+        }
+    }
+    upgrade_extension(organization);
+}
+
+// Null becomes no extensions; anything that is not a list of Extensions is
+// kept whole as a legacy extension
+fn upgrade_extension(resource: &mut Value) {
+    if let Some(extension) = resource.get_mut("extension") {
+        *extension = match extension.take() {
+            Value::Null => json!([]),
+            value if serde_json::from_value::<Vec<FhirExtension>>(value.clone()).is_ok() => value,
+            value => json!([FhirExtension::legacy(value)]),
+        };
+    }
+}
+
+// The value set enum whose variant is called `name`
+fn coded<T: FhirCodedValue + DeserializeOwned>(name: &str) -> Option<FhirCodeableConcept> {
+    serde_json::from_value::<T>(json!(name))
+        .ok()
+        .map(FhirCodeableConcept::from)
+}
+
+fn concepts(value: Value, decode: fn(&str) -> Option<FhirCodeableConcept>) -> Value {
+    match value {
+        Value::Null => json!([]),
+        Value::Array(items) => items
+            .into_iter()
+            .map(|item| concept(item, decode))
+            .collect(),
+        value => json!([concept(value, decode)]),
+    }
+}
+
+// Enum names are decoded, other text kept as the concept's text. JSON that is
+// not already a CodeableConcept is kept as text too.
+fn concept(value: Value, decode: fn(&str) -> Option<FhirCodeableConcept>) -> Value {
+    match value {
+        Value::String(text) => match decode(&text) {
+            Some(concept) => json!(concept),
+            None => json!({ "text": text }),
+        },
+        Value::Object(map) if map.contains_key("coding") || map.contains_key("text") => {
+            Value::Object(map)
+        }
+        value => json!({ "text": value.to_string() }),
+    }
+}
+
+// Schema 2 writes Practitioner contact points as `telecom`, the FHIR element,
+// rather than `type`
+fn practitioner_telecom(content_type: &str, resource: &mut Value) {
+    match content_type {
+        FHIR_RESOURCE_PRACTITIONER => rename_telecom(resource),
+        // The actor reference embeds the resource it points to
+        FHIR_RESOURCE_APPOINTMENT_RESPONSE => {
+            if let Some(actor) = resource.pointer_mut("/actor/type") {
+                // Practitioner names are HumanNames, Organization names text
+                if actor.get("name").is_some_and(Value::is_object) {
+                    rename_telecom(actor);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn rename_telecom(practitioner: &mut Value) {
+    if let Some(map) = practitioner.as_object_mut() {
+        if !map.contains_key("telecom") {
+            rename(map, "type", "telecom");
+        }
+    }
+}
+
+// Schema 3 writes datatypes as FHIR JSON: value set codes instead of variant
+// names, and members without a value left out instead of written as null or
+// an empty list. Both forms are read, so schema 2 resources need no change.
+fn fhir_json_datatypes(_content_type: &str, _resource: &mut Value) {}
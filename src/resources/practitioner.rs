@@ -1,59 +1,177 @@
 use crate::{
     consts::{FHIR_RESOURCE_PRACTITIONER, NOSTR_KIND_PRACTITIONER},
-    datatypes::{FhirContactPoint, FhirHumanName},
-    fhir_trait::{FhirResource, FhirText},
-    valuesets::FhirContactPointSystem,
+    datatypes::{
+        deserialize_extensions, FhirContactPoint, FhirExtensible, FhirExtension, FhirHumanName,
+        FhirUnknownElements,
+    },
+    fhir_trait::{FhirIdentifiable, FhirIdentifier, FhirResource},
+    identifiers::FhirIdentifierRegistry,
+    locale::FhirLocalization,
+    narrative::FhirNarrative,
+    resources::operation_outcome::FhirOperationOutcome,
+    valuesets::{FhirContactPointSystem, FhirIdentifierType, FhirIssueType},
 };
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use serde_json::Value;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FhirResource)]
+#[fhir(
+    resource_type = FHIR_RESOURCE_PRACTITIONER,
+    kind = NOSTR_KIND_PRACTITIONER,
+    narrative_with = text_name,
+    text_with = narrative,
+    validate_with = validate_rules
+)]
 pub struct FhirPractitioner {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    identifier: Vec<FhirIdentifier>,
     name: FhirHumanName,
-    #[serde(rename = "type")]
+    #[serde(rename = "telecom", default, skip_serializing_if = "Vec::is_empty")]
     contact: Vec<FhirContactPoint>,
-    extension: Option<Value>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_extensions",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    extension: Vec<FhirExtension>,
+    #[serde(flatten)]
+    #[fhir(unknown)]
+    unknown: FhirUnknownElements,
 }
 impl FhirPractitioner {
-    pub fn new(name: FhirHumanName, contact: Vec<FhirContactPoint>) -> Self {
-        Self {
+    // Identifiers are checked against the validators for their system
+    pub fn new(
+        identifier: Vec<FhirIdentifier>,
+        name: FhirHumanName,
+        contact: Vec<FhirContactPoint>,
+    ) -> Result<Self, String> {
+        Self::new_with(
+            identifier,
             name,
             contact,
-            extension: None,
+            FhirIdentifierRegistry::defaults(),
+        )
+    }
+    pub fn new_with(
+        identifier: Vec<FhirIdentifier>,
+        name: FhirHumanName,
+        contact: Vec<FhirContactPoint>,
+        registry: &FhirIdentifierRegistry,
+    ) -> Result<Self, String> {
+        let mut practitioner = Self {
+            identifier: vec![],
+            name,
+            contact,
+            extension: vec![],
+            unknown: FhirUnknownElements::new(),
+        };
+        for identifier in identifier {
+            practitioner.add_identifier_with(identifier, registry)?;
         }
+        Ok(practitioner)
+    }
+    // Checks the identifier against the validators for its system before adding it
+    pub fn add_identifier(&mut self, identifier: FhirIdentifier) -> Result<(), String> {
+        self.add_identifier_with(identifier, FhirIdentifierRegistry::defaults())
+    }
+    pub fn add_identifier_with(
+        &mut self,
+        identifier: FhirIdentifier,
+        registry: &FhirIdentifierRegistry,
+    ) -> Result<(), String> {
+        registry.validate(&identifier)?;
+        self.identifier.push(identifier);
+        Ok(())
+    }
+    pub fn get_identifiers(&self) -> &Vec<FhirIdentifier> {
+        &self.identifier
+    }
+    pub fn get_license(&self) -> Option<&FhirIdentifier> {
+        self.find_identifier_by_type(FhirIdentifierType::MedicalLicense)
     }
     pub fn text_name(&self) -> String {
-        self.name.text.to_string()
+        self.name.display()
     }
-    pub fn get_email(&self) -> Option<&FhirContactPoint> {
-        self.contact
+    // Name and license, with a table of contact points
+    pub fn narrative(&self, localization: &FhirLocalization, language: &str) -> FhirNarrative {
+        let label = |label| localization.label(label, language);
+        let contacts = self
+            .contact
             .iter()
-            .find(|contact| contact.system == FhirContactPointSystem::Email)
+            .map(|contact| {
+                vec![
+                    contact
+                        .system
+                        .as_ref()
+                        .map(|system| localization.display_of(system, language))
+                        .unwrap_or_default()
+                        .to_string(),
+                    contact.value.clone(),
+                    contact
+                        .contact_use
+                        .as_ref()
+                        .map(|contact_use| localization.display_of(contact_use, language))
+                        .unwrap_or_default()
+                        .to_string(),
+                ]
+            })
+            .collect();
+        FhirNarrative::new()
+            .with_heading(&self.text_name())
+            .with_row(
+                label("License"),
+                self.get_license()
+                    .map(FhirIdentifier::get_value)
+                    .unwrap_or_default(),
+            )
+            .with_table(&[label("System"), label("Value"), label("Use")], contacts)
+    }
+    pub fn formal_name(&self, language: &str) -> String {
+        self.name.format_formal(language)
+    }
+    pub fn sortable_name(&self) -> String {
+        self.name.format_sortable()
+    }
+    // Most preferred active email, by rank
+    pub fn get_email(&self) -> Option<&FhirContactPoint> {
+        FhirContactPoint::preferred(&self.contact, FhirContactPointSystem::Email)
     }
     pub fn get_phone(&self) -> Option<&FhirContactPoint> {
-        self.contact
-            .iter()
-            .find(|contact| contact.system == FhirContactPointSystem::Phone)
+        FhirContactPoint::preferred(&self.contact, FhirContactPointSystem::Phone)
     }
-    pub fn get_extension<T>(&self) -> Option<T>
-    where
-        T: for<'de> DeserializeOwned,
-    {
-        let value = self.extension.as_ref().unwrap();
-        Some(serde_json::from_value(value.clone()).unwrap())
+    pub fn get_contacts(&self, system: FhirContactPointSystem) -> Vec<&FhirContactPoint> {
+        FhirContactPoint::ranked(&self.contact, system)
     }
-    pub fn extend(&mut self, extension: Value) {
-        self.extension = Some(extension);
+    fn validate_rules(&self) -> FhirOperationOutcome {
+        let mut outcome = FhirOperationOutcome::new();
+        if self.text_name().trim().is_empty() {
+            outcome.error(
+                FhirIssueType::Required,
+                "Practitioner.name",
+                "Practitioner has no name",
+            );
+        }
+        if let Some(period) = &self.name.period {
+            outcome.check(
+                FhirIssueType::Invariant,
+                "Practitioner.name.period",
+                period.validate(),
+            );
+        }
+        outcome.check_identifiers("Practitioner.identifier", &self.identifier);
+        outcome.check_contact_points("Practitioner.telecom", &self.contact);
+        outcome
     }
 }
-impl FhirResource for FhirPractitioner {
-    fn nostr_kind(&self) -> u32 {
-        NOSTR_KIND_PRACTITIONER
+impl FhirIdentifiable for FhirPractitioner {
+    fn identifiers(&self) -> &[FhirIdentifier] {
+        &self.identifier
     }
-    fn resource_type(&self) -> &str {
-        FHIR_RESOURCE_PRACTITIONER
+}
+impl FhirExtensible for FhirPractitioner {
+    fn extensions(&self) -> &Vec<FhirExtension> {
+        &self.extension
     }
-    fn text(&self) -> FhirText {
-        FhirText::new(self.name.text.clone())
+    fn extensions_mut(&mut self) -> &mut Vec<FhirExtension> {
+        &mut self.extension
     }
 }
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use nostro2::notes::SignedNote;
+use serde_json::Value;
+
+use crate::fhir_trait::{FhirResource, IgnisResource};
+
+// Resource type and Nostr kind known without an instance, implemented by
+// `#[derive(FhirResource)]`
+pub trait FhirResourceDefinition: FhirResource {
+    const RESOURCE_TYPE: &'static str;
+    const NOSTR_KIND: u32;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FhirResourceDescriptor {
+    resource_type: &'static str,
+    nostr_kind: u32,
+    decode: fn(&SignedNote) -> Result<Value, String>,
+}
+impl FhirResourceDescriptor {
+    pub fn of<T: FhirResourceDefinition>() -> Self {
+        Self {
+            resource_type: T::RESOURCE_TYPE,
+            nostr_kind: T::NOSTR_KIND,
+            decode: |signed_note| {
+                IgnisResource::<T>::from_signed_note(signed_note, None)?.get_fhir_json()
+            },
+        }
+    }
+    pub fn get_resource_type(&self) -> &'static str {
+        self.resource_type
+    }
+    pub fn get_nostr_kind(&self) -> u32 {
+        self.nostr_kind
+    }
+    // FHIR JSON for a note of this kind
+    pub fn decode(&self, signed_note: &SignedNote) -> Result<Value, String> {
+        (self.decode)(signed_note)
+    }
+}
+
+// A resource submitted by `#[derive(FhirResource)]` for
+// `FhirResourceRegistry::with_defaults` to pick up
+pub struct FhirResourceRegistration(fn() -> FhirResourceDescriptor);
+impl FhirResourceRegistration {
+    pub const fn new(descriptor: fn() -> FhirResourceDescriptor) -> Self {
+        Self(descriptor)
+    }
+    pub fn descriptor(&self) -> FhirResourceDescriptor {
+        (self.0)()
+    }
+}
+inventory::collect!(FhirResourceRegistration);
+
+// Resources keyed by Nostr kind, so notes from a relay can be read without
+// knowing their Rust type in advance
+#[derive(Debug, Clone, Default)]
+pub struct FhirResourceRegistry {
+    resources: HashMap<u32, FhirResourceDescriptor>,
+}
+impl FhirResourceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    // Every resource deriving FhirResource in the program: the crate's own,
+    // those enabled through cargo features and the application's. When two
+    // share a kind, the one whose resource type sorts first is kept.
+    pub fn with_defaults() -> Self {
+        let mut descriptors: Vec<FhirResourceDescriptor> =
+            inventory::iter::<FhirResourceRegistration>
+                .into_iter()
+                .map(FhirResourceRegistration::descriptor)
+                .collect();
+        descriptors.sort_by_key(|descriptor| (descriptor.nostr_kind, descriptor.resource_type));
+        let mut registry = Self::new();
+        for descriptor in descriptors {
+            registry
+                .resources
+                .entry(descriptor.nostr_kind)
+                .or_insert(descriptor);
+        }
+        registry
+    }
+    // Each kind maps to one resource, so a second registration for a kind is rejected
+    pub fn register<T: FhirResourceDefinition>(&mut self) -> Result<(), String> {
+        let descriptor = FhirResourceDescriptor::of::<T>();
+        if let Some(existing) = self.resources.get(&descriptor.nostr_kind) {
+            return Err(format!(
+                "Nostr kind {} is already registered for {}",
+                descriptor.nostr_kind, existing.resource_type
+            ));
+        }
+        self.resources.insert(descriptor.nostr_kind, descriptor);
+        Ok(())
+    }
+    pub fn get(&self, nostr_kind: u32) -> Option<&FhirResourceDescriptor> {
+        self.resources.get(&nostr_kind)
+    }
+    pub fn get_by_type(&self, resource_type: &str) -> Vec<&FhirResourceDescriptor> {
+        self.resources
+            .values()
+            .filter(|descriptor| descriptor.resource_type == resource_type)
+            .collect()
+    }
+    pub fn is_registered(&self, nostr_kind: u32) -> bool {
+        self.resources.contains_key(&nostr_kind)
+    }
+    pub fn decode(&self, signed_note: &SignedNote) -> Result<Value, String> {
+        self.get(signed_note.get_kind())
+            .ok_or(format!(
+                "No resource registered for kind {}",
+                signed_note.get_kind()
+            ))?
+            .decode(signed_note)
+    }
+}
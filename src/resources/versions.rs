@@ -0,0 +1,747 @@
+use serde_json::{json, Map, Value};
+
+use super::operation_outcome::FhirOperationOutcome;
+use crate::valuesets::{FhirIssueSeverity, FhirIssueType};
+
+// https://www.hl7.org/fhir/versions.html
+// Resources are modelled on R5; R4 is produced and read by converting the JSON
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FhirVersion {
+    R4,
+    #[default]
+    R5,
+}
+impl FhirVersion {
+    pub fn fhir_version(&self) -> &'static str {
+        match self {
+            Self::R4 => "4.0.1",
+            Self::R5 => "5.0.0",
+        }
+    }
+    // https://www.hl7.org/fhir/versions.html#extensions
+    // Extension holding an element of this version that the other one lacks
+    pub fn cross_version_url(&self, path: &str) -> String {
+        let major = match self {
+            Self::R4 => "4.0",
+            Self::R5 => "5.0",
+        };
+        format!(
+            "http://hl7.org/fhir/{}/StructureDefinition/extension-{}",
+            major, path
+        )
+    }
+}
+
+// A converted value and the issues found converting it. Elements moved to a
+// cross-version extension are reported as information, dropped ones as warnings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FhirConversion<V> {
+    value: V,
+    outcome: FhirOperationOutcome,
+}
+impl<V> FhirConversion<V> {
+    pub fn new(value: V, outcome: FhirOperationOutcome) -> Self {
+        Self { value, outcome }
+    }
+    pub fn get_value(&self) -> &V {
+        &self.value
+    }
+    pub fn get_outcome(&self) -> &FhirOperationOutcome {
+        &self.outcome
+    }
+    pub fn into_value(self) -> V {
+        self.value
+    }
+    // Elements kept in extensions are reported as information and not lost
+    pub fn is_lossless(&self) -> bool {
+        self.outcome
+            .get_issues()
+            .iter()
+            .all(|issue| issue.get_severity() == FhirIssueSeverity::Information)
+    }
+}
+
+// R5 elements without an R4 equivalent that fit in an extension, with the
+// type of the extension value
+const R5_EXTENSIONS: [(&str, &str, &str); 9] = [
+    ("Appointment", "recurrenceId", "PositiveInt"),
+    ("Appointment", "occurrenceChanged", "Boolean"),
+    ("Appointment", "cancellationDate", "DateTime"),
+    ("AppointmentResponse", "proposedNewTime", "Boolean"),
+    ("AppointmentResponse", "recurring", "Boolean"),
+    ("AppointmentResponse", "occurrenceDate", "Date"),
+    ("AppointmentResponse", "recurrenceId", "PositiveInt"),
+    ("Organization", "description", "Markdown"),
+    ("Practitioner", "deceased[x]", ""),
+];
+// R5 elements without an R4 equivalent that cannot be carried over
+const R5_ONLY: [(&str, &str); 10] = [
+    ("Appointment", "class"),
+    ("Appointment", "virtualService"),
+    ("Appointment", "recurrenceTemplate"),
+    ("Appointment", "originatingAppointment"),
+    ("Appointment", "previousAppointment"),
+    ("Appointment", "subject"),
+    ("Appointment", "replaces"),
+    ("Appointment", "account"),
+    ("Appointment", "priority"),
+    ("Organization", "qualification"),
+];
+
+// Converts FHIR JSON in this crate's R5 form, such as the output of
+// `IgnisResource::get_fhir_json`, to `version`
+pub fn to_fhir_version(json: Value, version: FhirVersion) -> FhirConversion<Value> {
+    let mut outcome = FhirOperationOutcome::new();
+    let json = match (json, version) {
+        (json, FhirVersion::R5) => json,
+        (Value::Object(mut map), FhirVersion::R4) => {
+            let resource_type = resource_type(&map);
+            if !r5_to_r4(&mut map, &resource_type, &resource_type, &mut outcome) {
+                not_converted(&resource_type, &mut outcome);
+            }
+            Value::Object(map)
+        }
+        (json, FhirVersion::R4) => json,
+    };
+    FhirConversion::new(json, outcome)
+}
+
+// Converts FHIR JSON of `version` to this crate's R5 form, ready to be read
+// into a resource
+pub fn from_fhir_version(json: Value, version: FhirVersion) -> FhirConversion<Value> {
+    let mut outcome = FhirOperationOutcome::new();
+    let json = match (json, version) {
+        (json, FhirVersion::R5) => json,
+        (Value::Object(mut map), FhirVersion::R4) => {
+            let resource_type = resource_type(&map);
+            if !r4_to_r5(&mut map, &resource_type, &resource_type, &mut outcome) {
+                not_converted(&resource_type, &mut outcome);
+            }
+            Value::Object(map)
+        }
+        (json, FhirVersion::R4) => json,
+    };
+    FhirConversion::new(json, outcome)
+}
+
+fn resource_type(map: &Map<String, Value>) -> String {
+    map.get("resourceType")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn not_converted(resource_type: &str, outcome: &mut FhirOperationOutcome) {
+    outcome.information(
+        FhirIssueType::NotSupported,
+        resource_type,
+        format!(
+            "No version conversion for {}; returned as is",
+            resource_type
+        ),
+    );
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(text) => text.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        _ => false,
+    }
+}
+
+fn drop_element(
+    map: &mut Map<String, Value>,
+    element: &str,
+    path: &str,
+    version: FhirVersion,
+    outcome: &mut FhirOperationOutcome,
+) {
+    if let Some(value) = map.shift_remove(element) {
+        if !is_empty(&value) {
+            outcome.warning(
+                FhirIssueType::NotSupported,
+                &format!("{}.{}", path, element),
+                format!("No {} equivalent; dropped", version_name(version)),
+            );
+        }
+    }
+}
+
+fn version_name(version: FhirVersion) -> &'static str {
+    match version {
+        FhirVersion::R4 => "R4",
+        FhirVersion::R5 => "R5",
+    }
+}
+
+fn push_extension(map: &mut Map<String, Value>, extension: Value) {
+    match map.get_mut("extension") {
+        Some(Value::Array(extensions)) => extensions.push(extension),
+        _ => {
+            map.insert("extension".to_string(), Value::Array(vec![extension]));
+        }
+    }
+}
+
+// Moves an element into a cross-version extension. `value_type` is empty for
+// choice elements, whose JSON key already names the type.
+fn element_to_extension(
+    map: &mut Map<String, Value>,
+    resource_type: &str,
+    path: &str,
+    element: &str,
+    value_type: &str,
+    outcome: &mut FhirOperationOutcome,
+) {
+    let url = FhirVersion::R5.cross_version_url(&format!(
+        "{}.{}",
+        resource_type,
+        element.trim_end_matches("[x]")
+    ));
+    let entries: Vec<(String, Value)> = match element.strip_suffix("[x]") {
+        Some(base) => {
+            let keys: Vec<String> = map
+                .keys()
+                .filter(|key| {
+                    key.strip_prefix(base)
+                        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()))
+                })
+                .cloned()
+                .collect();
+            keys.into_iter()
+                .filter_map(|key| {
+                    let value = map.shift_remove(&key)?;
+                    Some((format!("value{}", &key[base.len()..]), value))
+                })
+                .collect()
+        }
+        None => map
+            .shift_remove(element)
+            .map(|value| (format!("value{}", value_type), value))
+            .into_iter()
+            .collect(),
+    };
+    for (key, value) in entries {
+        if is_empty(&value) {
+            continue;
+        }
+        let mut extension = Map::new();
+        extension.insert("url".to_string(), Value::String(url.clone()));
+        extension.insert(key, value);
+        push_extension(map, Value::Object(extension));
+        outcome.information(
+            FhirIssueType::NotSupported,
+            &format!("{}.{}", path, element),
+            format!("No R4 equivalent; kept in extension {}", url),
+        );
+    }
+}
+
+// Restores elements that an R5 to R4 conversion moved into extensions
+fn extension_to_element(map: &mut Map<String, Value>, resource_type: &str) {
+    let (index, Some(Value::Array(extensions))) = take(map, "extension") else {
+        return;
+    };
+    let mut kept = vec![];
+    for extension in extensions {
+        let restored = R5_EXTENSIONS
+            .iter()
+            .filter(|(resource, _, _)| *resource == resource_type)
+            .find_map(|(_, element, _)| {
+                let url = FhirVersion::R5.cross_version_url(&format!(
+                    "{}.{}",
+                    resource_type,
+                    element.trim_end_matches("[x]")
+                ));
+                if extension.get("url").and_then(Value::as_str) != Some(url.as_str()) {
+                    return None;
+                }
+                let (key, value) = extension
+                    .as_object()?
+                    .iter()
+                    .find(|(key, _)| key.starts_with("value"))?;
+                let key = match element.strip_suffix("[x]") {
+                    Some(base) => format!("{}{}", base, &key["value".len()..]),
+                    None => element.to_string(),
+                };
+                Some((key, value.clone()))
+            });
+        match restored {
+            Some((key, value)) => {
+                map.insert(key, value);
+            }
+            None => kept.push(extension),
+        }
+    }
+    put_array(map, index, "extension", kept);
+}
+
+// R5 CodeableReference lists split into the R4 concept and reference lists
+fn split_codeable_references(items: Vec<Value>) -> (Vec<Value>, Vec<Value>) {
+    let mut concepts = vec![];
+    let mut references = vec![];
+    for item in items {
+        if let Some(concept) = item.get("concept") {
+            concepts.push(concept.clone());
+        }
+        if let Some(reference) = item.get("reference") {
+            references.push(reference.clone());
+        }
+    }
+    (concepts, references)
+}
+
+// Removes an element, returning where it was so its replacement can take its place
+fn take(map: &mut Map<String, Value>, element: &str) -> (usize, Option<Value>) {
+    let index = map
+        .keys()
+        .position(|key| key == element)
+        .unwrap_or(map.len());
+    (index, map.shift_remove(element))
+}
+
+fn take_array(map: &mut Map<String, Value>, element: &str) -> (usize, Vec<Value>) {
+    let (index, value) = take(map, element);
+    let items = match value {
+        Some(Value::Array(items)) => items,
+        Some(Value::Null) | None => vec![],
+        Some(item) => vec![item],
+    };
+    (index, items)
+}
+
+// Inserts at `index`, returning the index after the new element
+fn put(map: &mut Map<String, Value>, index: usize, element: &str, value: Value) -> usize {
+    let index = index.min(map.len());
+    map.shift_insert(index, element.to_string(), value);
+    index + 1
+}
+
+fn put_array(
+    map: &mut Map<String, Value>,
+    index: usize,
+    element: &str,
+    items: Vec<Value>,
+) -> usize {
+    match items.is_empty() {
+        true => index,
+        false => put(map, index, element, Value::Array(items)),
+    }
+}
+
+// Adds a resource to `contained`, which follows the narrative
+fn contain(
+    map: &mut Map<String, Value>,
+    resource_type: &str,
+    id: &str,
+    resource: Map<String, Value>,
+) {
+    let mut contained = Map::new();
+    contained.insert(
+        "resourceType".to_string(),
+        Value::String(resource_type.to_string()),
+    );
+    contained.insert("id".to_string(), Value::String(id.to_string()));
+    contained.extend(resource);
+    match map.get_mut("contained") {
+        Some(Value::Array(resources)) => resources.push(Value::Object(contained)),
+        _ => {
+            let index = [
+                "resourceType",
+                "id",
+                "meta",
+                "implicitRules",
+                "language",
+                "text",
+            ]
+            .iter()
+            .filter_map(|element| map.keys().position(|key| key == element))
+            .max()
+            .map_or(0, |index| index + 1);
+            put(map, index, "contained", json!([contained]));
+        }
+    }
+}
+
+// Takes out the contained resource a "#id" reference points to, without its
+// resource type and id
+fn uncontain(map: &mut Map<String, Value>, reference: &Value) -> Option<Map<String, Value>> {
+    let id = reference.get("reference")?.as_str()?.strip_prefix('#')?;
+    let resources = map.get_mut("contained")?.as_array_mut()?;
+    let position = resources
+        .iter()
+        .position(|resource| resource.get("id").and_then(Value::as_str) == Some(id))?;
+    let Value::Object(mut resource) = resources.remove(position) else {
+        return None;
+    };
+    if resources.is_empty() {
+        map.shift_remove("contained");
+    }
+    resource.shift_remove("resourceType");
+    resource.shift_remove("id");
+    Some(resource)
+}
+
+// The type a reference names, from its literal ("Practitioner/123")
+fn literal_type(reference: &Map<String, Value>) -> Option<&str> {
+    let literal = reference.get("reference")?.as_str()?;
+    let (resource_type, _) = literal.split_once('/')?;
+    let is_type = resource_type.starts_with(|c: char| c.is_ascii_uppercase())
+        && resource_type.chars().all(|c| c.is_ascii_alphanumeric());
+    is_type.then_some(resource_type)
+}
+
+// This crate's references embed the resource they point to as their `type`,
+// which FHIR defines as the uri of the resource type
+fn reference_type_to_uri(
+    reference: &mut Map<String, Value>,
+    path: &str,
+    outcome: &mut FhirOperationOutcome,
+) {
+    let (index, Some(Value::Object(target))) = take(reference, "type") else {
+        return;
+    };
+    let resource_type = literal_type(reference)
+        .or(target.get("resourceType").and_then(Value::as_str))
+        .map(str::to_string);
+    if let Some(resource_type) = resource_type {
+        put(reference, index, "type", Value::String(resource_type));
+    }
+    outcome.warning(
+        FhirIssueType::NotSupported,
+        &format!("{}.type", path),
+        "R4 Reference.type is the resource type; the embedded resource is dropped",
+    );
+}
+
+fn reference_type_from_uri(
+    reference: &mut Map<String, Value>,
+    path: &str,
+    outcome: &mut FhirOperationOutcome,
+) {
+    let (index, Some(resource_type)) = take(reference, "type") else {
+        return;
+    };
+    let Value::String(resource_type) = resource_type else {
+        put(reference, index, "type", resource_type);
+        return;
+    };
+    if literal_type(reference) != Some(resource_type.as_str()) {
+        outcome.warning(
+            FhirIssueType::NotSupported,
+            &format!("{}.type", path),
+            format!(
+                "References hold the resource they point to, not a type; {} is dropped",
+                resource_type
+            ),
+        );
+    }
+}
+
+pub(super) fn rename(map: &mut Map<String, Value>, from: &str, to: &str) {
+    if let (index, Some(value)) = take(map, from) {
+        put(map, index, to, value);
+    }
+}
+
+// Returns false for resources it has no rules for. `path` is the FHIRPath of
+// the object, which differs from the resource type for nested resources.
+fn r5_to_r4(
+    map: &mut Map<String, Value>,
+    resource_type: &str,
+    path: &str,
+    outcome: &mut FhirOperationOutcome,
+) -> bool {
+    let r4 = FhirVersion::R4;
+    match resource_type {
+        "Appointment" => {
+            rename(map, "cancellationReason", "cancelationReason");
+            let (index, service_types) = take_array(map, "serviceType");
+            let (concepts, references) = split_codeable_references(service_types);
+            if !references.is_empty() {
+                outcome.warning(
+                    FhirIssueType::NotSupported,
+                    &format!("{}.serviceType", path),
+                    "R4 serviceType has no references; dropped",
+                );
+            }
+            put_array(map, index, "serviceType", concepts);
+            let (index, reasons) = take_array(map, "reason");
+            let (concepts, references) = split_codeable_references(reasons);
+            let index = put_array(map, index, "reasonCode", concepts);
+            put_array(map, index, "reasonReference", references);
+            let (index, notes) = take_array(map, "note");
+            let comments: Vec<&str> = notes
+                .iter()
+                .filter_map(|note| note.get("text").and_then(Value::as_str))
+                .collect();
+            if !comments.is_empty() {
+                put(map, index, "comment", Value::String(comments.join("\n")));
+            }
+            if notes
+                .iter()
+                .any(|note| note.as_object().is_some_and(|note| note.len() > 1))
+            {
+                outcome.warning(
+                    FhirIssueType::NotSupported,
+                    &format!("{}.note", path),
+                    "R4 comment only keeps the note text",
+                );
+            }
+            let (index, instructions) = take_array(map, "patientInstruction");
+            let count = instructions.len();
+            let (concepts, _) = split_codeable_references(instructions);
+            let texts: Vec<&str> = concepts
+                .iter()
+                .filter_map(|concept| concept.get("text").and_then(Value::as_str))
+                .collect();
+            if !texts.is_empty() {
+                put(
+                    map,
+                    index,
+                    "patientInstruction",
+                    Value::String(texts.join("\n")),
+                );
+            }
+            if texts.len() < count {
+                outcome.warning(
+                    FhirIssueType::NotSupported,
+                    &format!("{}.patientInstruction", path),
+                    "R4 patientInstruction only keeps instruction text",
+                );
+            }
+        }
+        "AppointmentResponse" => {
+            // R4 references the appointment, so the embedded one is contained
+            if let (index, Some(Value::Object(mut appointment))) = take(map, "appointment") {
+                let appointment_path = format!("{}.appointment", path);
+                r5_to_r4(&mut appointment, "Appointment", &appointment_path, outcome);
+                contain(map, "Appointment", "appointment", appointment);
+                put(
+                    map,
+                    index,
+                    "appointment",
+                    json!({ "reference": "#appointment" }),
+                );
+                outcome.information(
+                    FhirIssueType::NotSupported,
+                    &appointment_path,
+                    "R4 references the appointment; kept as contained Appointment #appointment",
+                );
+            }
+            if let Some(Value::Object(actor)) = map.get_mut("actor") {
+                reference_type_to_uri(actor, &format!("{}.actor", path), outcome);
+            }
+            // false is the R5 default and needs no extension
+            if map.get("proposedNewTime") == Some(&Value::Bool(false)) {
+                map.shift_remove("proposedNewTime");
+            }
+        }
+        "Organization" => {
+            for (index, contact) in map
+                .get_mut("contact")
+                .and_then(Value::as_array_mut)
+                .into_iter()
+                .flatten()
+                .enumerate()
+            {
+                let Some(contact) = contact.as_object_mut() else {
+                    continue;
+                };
+                let contact_path = format!("{}.contact[{}]", path, index);
+                // R4 contact.name is a single HumanName
+                let name = match take(contact, "name") {
+                    (index, Some(Value::String(name))) => Some((index, json!({ "text": name }))),
+                    (index, Some(Value::Array(mut names))) if !names.is_empty() => {
+                        if names.len() > 1 {
+                            outcome.warning(
+                                FhirIssueType::NotSupported,
+                                &format!("{}.name", contact_path),
+                                "R4 contact has a single name; only the first is kept",
+                            );
+                        }
+                        Some((index, names.remove(0)))
+                    }
+                    (index, Some(name)) if !is_empty(&name) => Some((index, name)),
+                    _ => None,
+                };
+                if let Some((index, name)) = name {
+                    put(contact, index, "name", name);
+                }
+                drop_element(contact, "period", &contact_path, r4, outcome);
+                drop_element(contact, "organization", &contact_path, r4, outcome);
+            }
+        }
+        "Practitioner" => {
+            let (index, communication) = take_array(map, "communication");
+            if communication
+                .iter()
+                .any(|item| item.get("preferred").is_some())
+            {
+                outcome.warning(
+                    FhirIssueType::NotSupported,
+                    &format!("{}.communication.preferred", path),
+                    "No R4 equivalent; dropped",
+                );
+            }
+            let languages = communication
+                .into_iter()
+                .filter_map(|item| item.get("language").cloned())
+                .collect();
+            put_array(map, index, "communication", languages);
+        }
+        _ => return false,
+    }
+    for (resource, element, value_type) in R5_EXTENSIONS {
+        if resource == resource_type {
+            element_to_extension(map, resource_type, path, element, value_type, outcome);
+        }
+    }
+    for (resource, element) in R5_ONLY {
+        if resource == resource_type {
+            drop_element(map, element, path, r4, outcome);
+        }
+    }
+    true
+}
+
+fn r4_to_r5(
+    map: &mut Map<String, Value>,
+    resource_type: &str,
+    path: &str,
+    outcome: &mut FhirOperationOutcome,
+) -> bool {
+    let r5 = FhirVersion::R5;
+    match resource_type {
+        "Appointment" => {
+            extension_to_element(map, resource_type);
+            rename(map, "cancelationReason", "cancellationReason");
+            let (index, service_types) = take_array(map, "serviceType");
+            let service_types = service_types
+                .into_iter()
+                .map(|concept| json!({ "concept": concept }))
+                .collect();
+            put_array(map, index, "serviceType", service_types);
+            let (index, concepts) = take_array(map, "reasonCode");
+            let (_, references) = take_array(map, "reasonReference");
+            let reasons = concepts
+                .into_iter()
+                .map(|concept| json!({ "concept": concept }))
+                .chain(
+                    references
+                        .into_iter()
+                        .map(|reference| json!({ "reference": reference })),
+                )
+                .collect();
+            put_array(map, index, "reason", reasons);
+            if let (index, Some(comment)) = take(map, "comment") {
+                put(map, index, "note", json!([{ "text": comment }]));
+            }
+            if let (index, Some(instruction)) = take(map, "patientInstruction") {
+                put(
+                    map,
+                    index,
+                    "patientInstruction",
+                    json!([{ "concept": { "text": instruction } }]),
+                );
+            }
+            // R4 priority is a number, R5 a CodeableConcept
+            drop_element(map, "priority", path, r5, outcome);
+        }
+        "AppointmentResponse" => {
+            extension_to_element(map, resource_type);
+            // This crate's model embeds the appointment a response is for
+            let appointment_path = format!("{}.appointment", path);
+            let (index, reference) = take(map, "appointment");
+            match reference
+                .as_ref()
+                .and_then(|reference| uncontain(map, reference))
+            {
+                Some(mut appointment) => {
+                    r4_to_r5(&mut appointment, "Appointment", &appointment_path, outcome);
+                    put(map, index, "appointment", Value::Object(appointment));
+                }
+                None => {
+                    if let Some(reference) = reference {
+                        put(map, index, "appointment", reference);
+                    }
+                    outcome.warning(
+                        FhirIssueType::NotSupported,
+                        &appointment_path,
+                        "Only a contained Appointment can be embedded in the response",
+                    );
+                }
+            }
+            if let Some(Value::Object(actor)) = map.get_mut("actor") {
+                reference_type_from_uri(actor, &format!("{}.actor", path), outcome);
+            }
+            map.entry("proposedNewTime").or_insert(Value::Bool(false));
+        }
+        "Organization" => {
+            extension_to_element(map, resource_type);
+            for contact in map
+                .get_mut("contact")
+                .and_then(Value::as_array_mut)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_object_mut)
+            {
+                if let (index, Some(name)) = take(contact, "name") {
+                    put(
+                        contact,
+                        index,
+                        "name",
+                        Value::String(human_name_text(&name)),
+                    );
+                }
+            }
+            // R5 moved the organization's own telecom and address into contact
+            let (_, telecom) = take_array(map, "telecom");
+            let (_, addresses) = take_array(map, "address");
+            if !telecom.is_empty() || !addresses.is_empty() {
+                let (index, mut contacts) = take_array(map, "contact");
+                let mut first = Map::new();
+                put_array(&mut first, 0, "telecom", telecom);
+                let mut addresses = addresses.into_iter();
+                if let Some(address) = addresses.next() {
+                    first.insert("address".to_string(), address);
+                }
+                contacts.push(Value::Object(first));
+                contacts.extend(addresses.map(|address| json!({ "address": address })));
+                put(map, index, "contact", Value::Array(contacts));
+            }
+        }
+        "Practitioner" => {
+            extension_to_element(map, resource_type);
+            let (index, communication) = take_array(map, "communication");
+            let communication = communication
+                .into_iter()
+                .map(|language| json!({ "language": language }))
+                .collect();
+            put_array(map, index, "communication", communication);
+        }
+        _ => return false,
+    }
+    true
+}
+
+// Contact names are plain text in this crate's model
+fn human_name_text(name: &Value) -> String {
+    if let Some(text) = name.get("text").and_then(Value::as_str) {
+        return text.to_string();
+    }
+    let mut parts: Vec<&str> = name
+        .get("given")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    if let Some(family) = name.get("family").and_then(Value::as_str) {
+        parts.push(family);
+    }
+    parts.join(" ")
+}
@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::terminology_json;
+
+use crate::{datatypes::FhirCoding, valuesets::FhirCodedValue};
+
+// https://www.hl7.org/fhir/codesystem.html
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirCodeSystem {
+    pub url: String,
+    pub version: Option<String>,
+    pub name: Option<String>,
+    pub status: String,
+    pub content: String,
+    pub language: Option<String>,
+    #[serde(default)]
+    pub concept: Vec<FhirCodeSystemConcept>,
+}
+impl FhirCodeSystem {
+    pub fn to_fhir_json(&self) -> Result<Value, String> {
+        terminology_json("CodeSystem", self)
+    }
+    pub fn new(url: String, name: Option<String>, concept: Vec<FhirCodeSystemConcept>) -> Self {
+        Self {
+            url,
+            version: None,
+            name,
+            status: "active".to_string(),
+            content: "complete".to_string(),
+            language: Some("en".to_string()),
+            concept,
+        }
+    }
+    // Builds the code system of the enum's first value; enums whose values span
+    // several systems only contribute the values that belong to that one.
+    pub fn from_coded_value<T: FhirCodedValue>(name: &str) -> Option<Self> {
+        let values = T::all();
+        let url = values.first()?.fhir_system().to_string();
+        Self::from_coded_value_in::<T>(&url, name)
+    }
+    pub fn from_coded_value_in<T: FhirCodedValue>(url: &str, name: &str) -> Option<Self> {
+        let concept: Vec<FhirCodeSystemConcept> = T::all()
+            .iter()
+            .filter(|value| value.fhir_system() == url)
+            .map(|value| {
+                FhirCodeSystemConcept::new(value.fhir_code(), value.fhir_display().to_string())
+            })
+            .collect();
+        if concept.is_empty() {
+            return None;
+        }
+        Some(Self::new(url.to_string(), Some(name.to_string()), concept))
+    }
+    pub fn find_concept(&self, code: &str) -> Option<&FhirCodeSystemConcept> {
+        FhirCodeSystemConcept::find_in(&self.concept, code)
+    }
+    pub fn find_concept_mut(&mut self, code: &str) -> Option<&mut FhirCodeSystemConcept> {
+        FhirCodeSystemConcept::find_in_mut(&mut self.concept, code)
+    }
+    pub fn all_concepts(&self) -> Vec<&FhirCodeSystemConcept> {
+        let mut concepts = vec![];
+        for concept in &self.concept {
+            concept.collect_into(&mut concepts);
+        }
+        concepts
+    }
+    pub fn display(&self, code: &str, language: Option<&str>) -> Option<String> {
+        let concept = self.find_concept(code)?;
+        match language {
+            Some(language)
+                if !language_matches(self.language.as_deref().unwrap_or("en"), language) =>
+            {
+                concept
+                    .designation_for(language)
+                    .map(|designation| designation.value.clone())
+                    .or_else(|| concept.display.clone())
+            }
+            _ => concept.display.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirCodeSystemConcept {
+    pub code: String,
+    pub display: Option<String>,
+    pub definition: Option<String>,
+    #[serde(default)]
+    pub designation: Vec<FhirConceptDesignation>,
+    #[serde(default)]
+    pub concept: Vec<FhirCodeSystemConcept>,
+}
+impl FhirCodeSystemConcept {
+    pub fn new(code: String, display: String) -> Self {
+        Self {
+            code,
+            display: Some(display),
+            definition: None,
+            designation: vec![],
+            concept: vec![],
+        }
+    }
+    pub fn add_designation(&mut self, language: &str, value: String) {
+        self.designation
+            .retain(|designation| designation.language.as_deref() != Some(language));
+        self.designation
+            .push(FhirConceptDesignation::new(language, value));
+    }
+    pub fn designation_for(&self, language: &str) -> Option<&FhirConceptDesignation> {
+        self.designation
+            .iter()
+            .find(|designation| designation.language.as_deref() == Some(language))
+            .or_else(|| {
+                self.designation.iter().find(|designation| {
+                    designation
+                        .language
+                        .as_deref()
+                        .is_some_and(|lang| language_matches(lang, language))
+                })
+            })
+    }
+    pub fn matches_display(&self, display: &str) -> bool {
+        self.display.as_deref() == Some(display)
+            || self
+                .designation
+                .iter()
+                .any(|designation| designation.value == display)
+    }
+    pub fn descendants(&self) -> Vec<&FhirCodeSystemConcept> {
+        let mut concepts = vec![];
+        for child in &self.concept {
+            child.collect_into(&mut concepts);
+        }
+        concepts
+    }
+    fn collect_into<'a>(&'a self, concepts: &mut Vec<&'a FhirCodeSystemConcept>) {
+        concepts.push(self);
+        for child in &self.concept {
+            child.collect_into(concepts);
+        }
+    }
+    fn find_in<'a>(concepts: &'a [Self], code: &str) -> Option<&'a Self> {
+        concepts.iter().find_map(|concept| {
+            if concept.code == code {
+                Some(concept)
+            } else {
+                Self::find_in(&concept.concept, code)
+            }
+        })
+    }
+    fn find_in_mut<'a>(concepts: &'a mut [Self], code: &str) -> Option<&'a mut Self> {
+        for concept in concepts.iter_mut() {
+            if concept.code == code {
+                return Some(concept);
+            }
+            if let Some(found) = Self::find_in_mut(&mut concept.concept, code) {
+                return Some(found);
+            }
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirConceptDesignation {
+    pub language: Option<String>,
+    #[serde(rename = "use")]
+    pub designation_use: Option<FhirCoding>,
+    pub value: String,
+}
+impl FhirConceptDesignation {
+    pub fn new(language: &str, value: String) -> Self {
+        Self {
+            language: Some(language.to_string()),
+            designation_use: None,
+            value,
+        }
+    }
+}
+
+// "es" matches "es-VE" and vice versa, so regional tags fall back to the base language
+pub(crate) fn language_matches(available: &str, requested: &str) -> bool {
+    let base = |tag: &str| tag.split('-').next().unwrap_or_default().to_lowercase();
+    available.eq_ignore_ascii_case(requested) || base(available) == base(requested)
+}
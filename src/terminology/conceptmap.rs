@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::terminology_json;
+
+use crate::{
+    consts::{IGNIS_CODESYSTEM_SPECIALTY, IGNIS_CONCEPTMAP_SPECIALTY_TO_SNOMED, SNOMED_CT_SYSTEM},
+    valuesets::FhirSpecialty,
+};
+
+// https://www.hl7.org/fhir/conceptmap.html
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirConceptMap {
+    pub url: String,
+    pub version: Option<String>,
+    pub name: Option<String>,
+    pub status: String,
+    #[serde(default)]
+    pub group: Vec<FhirConceptMapGroup>,
+}
+impl FhirConceptMap {
+    pub fn to_fhir_json(&self) -> Result<Value, String> {
+        terminology_json("ConceptMap", self)
+    }
+    // Maps the crate's local specialty codes to the nearest SNOMED CT concepts
+    pub fn specialty_to_snomed() -> Self {
+        let element = FhirSpecialty::iter()
+            .filter(|specialty| specialty.is_local())
+            .map(|specialty| {
+                let nearest = specialty.nearest_snomed();
+                let mut target = vec![FhirConceptMapTarget::new(
+                    nearest,
+                    "source-is-narrower-than-target",
+                )];
+                if specialty == FhirSpecialty::GynecologyMastology {
+                    target[0].relationship = "related-to".to_string();
+                    target.push(FhirConceptMapTarget::new(
+                        FhirSpecialty::Gynecology,
+                        "source-is-narrower-than-target",
+                    ));
+                }
+                FhirConceptMapElement {
+                    code: specialty.code_string(),
+                    display: Some(specialty.display().to_string()),
+                    target,
+                }
+            })
+            .collect();
+        Self {
+            url: IGNIS_CONCEPTMAP_SPECIALTY_TO_SNOMED.to_string(),
+            version: None,
+            name: Some("IgnisSpecialtyToSnomed".to_string()),
+            status: "active".to_string(),
+            group: vec![FhirConceptMapGroup {
+                source: IGNIS_CODESYSTEM_SPECIALTY.to_string(),
+                target: SNOMED_CT_SYSTEM.to_string(),
+                element,
+            }],
+        }
+    }
+    // ConceptMap/$translate
+    pub fn translate(&self, system: &str, code: &str) -> Vec<(String, &FhirConceptMapTarget)> {
+        self.group
+            .iter()
+            .filter(|group| group.source == system)
+            .flat_map(|group| {
+                group
+                    .element
+                    .iter()
+                    .filter(|element| element.code == code)
+                    .flat_map(|element| element.target.iter())
+                    .map(|target| (group.target.clone(), target))
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirConceptMapGroup {
+    pub source: String,
+    pub target: String,
+    #[serde(default)]
+    pub element: Vec<FhirConceptMapElement>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirConceptMapElement {
+    pub code: String,
+    pub display: Option<String>,
+    #[serde(default)]
+    pub target: Vec<FhirConceptMapTarget>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirConceptMapTarget {
+    pub code: String,
+    pub display: Option<String>,
+    pub relationship: String,
+    pub comment: Option<String>,
+}
+impl FhirConceptMapTarget {
+    fn new(specialty: FhirSpecialty, relationship: &str) -> Self {
+        Self {
+            code: specialty.code_string(),
+            display: Some(specialty.display().to_string()),
+            relationship: relationship.to_string(),
+            comment: None,
+        }
+    }
+}
@@ -0,0 +1,531 @@
+mod codesystem;
+mod conceptmap;
+mod valueset;
+pub(crate) use codesystem::language_matches;
+pub use codesystem::{FhirCodeSystem, FhirCodeSystemConcept, FhirConceptDesignation};
+pub use conceptmap::{
+    FhirConceptMap, FhirConceptMapElement, FhirConceptMapGroup, FhirConceptMapTarget,
+};
+pub use valueset::{
+    FhirValueSet, FhirValueSetCompose, FhirValueSetConcept, FhirValueSetContains,
+    FhirValueSetExpansion, FhirValueSetFilter, FhirValueSetInclude,
+};
+
+use std::{collections::HashMap, sync::OnceLock};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::consts::{IGNIS_CODESYSTEM_SPECIALTY, SNOMED_CT_SYSTEM};
+use crate::locale::FhirLocalization;
+use crate::valuesets::{
+    FhirAddressType, FhirAddressUse, FhirAppointmentResponseStatus, FhirAppointmentStatus,
+    FhirCodedValue, FhirContactEntityType, FhirContactPointSystem, FhirContactPointUse,
+    FhirIdentifierType, FhirIdentifierUse, FhirIssueSeverity, FhirIssueType, FhirNameUse,
+    FhirOrganizationType, FhirQuantityComparator, FhirSpecialty,
+};
+
+// Value set canonical for the specialty codes, as published by HL7
+pub const FHIR_VALUESET_PRACTICE_CODES: &str = "http://hl7.org/fhir/ValueSet/c80-practice-codes";
+
+// A terminology resource's JSON led by its resourceType. Unset members are
+// left out: FHIR servers reject nulls and empty arrays.
+fn terminology_json<T: Serialize>(resource_type: &str, resource: &T) -> Result<Value, String> {
+    let mut json = json!({ "resourceType": resource_type });
+    let mut resource = serde_json::to_value(resource).map_err(|e| e.to_string())?;
+    strip_unset(&mut resource);
+    if let (Some(json), Value::Object(map)) = (json.as_object_mut(), resource) {
+        json.extend(map);
+    }
+    Ok(json)
+}
+
+fn strip_unset(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.values_mut().for_each(strip_unset);
+            map.retain(|_, value| match value {
+                Value::Null => false,
+                Value::Array(items) => !items.is_empty(),
+                _ => true,
+            });
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_unset),
+        _ => {}
+    }
+}
+
+// https://www.hl7.org/fhir/codesystem-operation-lookup.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FhirLookupResult {
+    pub name: String,
+    pub version: Option<String>,
+    pub display: Option<String>,
+    pub designation: Vec<FhirConceptDesignation>,
+}
+
+// https://www.hl7.org/fhir/valueset-operation-validate-code.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FhirValidateCodeResult {
+    pub result: bool,
+    pub display: Option<String>,
+    pub message: Option<String>,
+}
+impl FhirValidateCodeResult {
+    fn invalid(message: String) -> Self {
+        Self {
+            result: false,
+            display: None,
+            message: Some(message),
+        }
+    }
+}
+
+// In-memory terminology store. CodeSystems and ValueSets can be loaded from
+// FHIR JSON at runtime; `with_defaults` seeds it with the compiled-in enums.
+#[derive(Debug, Clone, Default)]
+pub struct FhirTerminology {
+    code_systems: HashMap<String, FhirCodeSystem>,
+    value_sets: HashMap<String, FhirValueSet>,
+    concept_maps: HashMap<String, FhirConceptMap>,
+}
+impl FhirTerminology {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_defaults() -> Self {
+        let mut terminology = Self::new();
+        terminology.register_coded_value::<FhirNameUse>("NameUse");
+        terminology.register_coded_value::<FhirOrganizationType>("OrganizationType");
+        terminology.register_coded_value::<FhirAddressType>("AddressType");
+        terminology.register_coded_value::<FhirAddressUse>("AddressUse");
+        terminology.register_coded_value::<FhirContactPointUse>("ContactPointUse");
+        terminology.register_coded_value::<FhirContactPointSystem>("ContactPointSystem");
+        terminology.register_coded_value::<FhirContactEntityType>("ContactEntityType");
+        terminology.register_coded_value::<FhirAppointmentStatus>("AppointmentStatus");
+        terminology
+            .register_coded_value::<FhirAppointmentResponseStatus>("AppointmentResponseStatus");
+        terminology.register_coded_value::<FhirQuantityComparator>("QuantityComparator");
+        terminology.register_coded_value::<FhirIdentifierUse>("IdentifierUse");
+        terminology.register_coded_value::<FhirIdentifierType>("IdentifierType");
+        terminology.register_coded_value::<FhirIssueSeverity>("IssueSeverity");
+        terminology.register_coded_value::<FhirIssueType>("IssueType");
+        terminology.register_specialties();
+        terminology.add_localization(FhirLocalization::defaults());
+        terminology
+    }
+    // Shared terminology with the compiled-in value sets, used by resource validation
+    pub fn defaults() -> &'static Self {
+        static DEFAULTS: OnceLock<FhirTerminology> = OnceLock::new();
+        DEFAULTS.get_or_init(Self::with_defaults)
+    }
+    // Registers an enum as a CodeSystem plus a ValueSet containing all of its codes.
+    // The ValueSet canonical is the CodeSystem url, as HL7 does for its own enumerations.
+    pub fn register_coded_value<T: FhirCodedValue>(&mut self, name: &str) {
+        if let Some(code_system) = FhirCodeSystem::from_coded_value::<T>(name) {
+            let url = code_system.url.clone();
+            self.add_value_set(FhirValueSet::all_of_system(
+                url.clone(),
+                Some(name.to_string()),
+                url,
+            ));
+            self.add_code_system(code_system);
+        }
+    }
+    fn register_specialties(&mut self) {
+        let Some(mut snomed) =
+            FhirCodeSystem::from_coded_value_in::<FhirSpecialty>(SNOMED_CT_SYSTEM, "SNOMED CT")
+        else {
+            return;
+        };
+        snomed.content = "fragment".to_string();
+        let Some(local) = local_specialty_code_system() else {
+            return;
+        };
+        self.add_value_set(FhirValueSet::new(
+            FHIR_VALUESET_PRACTICE_CODES.to_string(),
+            Some("PracticeSettingCodeValueSet".to_string()),
+            FhirValueSetCompose {
+                include: vec![
+                    FhirValueSetInclude::system(snomed.url.clone()),
+                    FhirValueSetInclude::system(local.url.clone()),
+                ],
+                exclude: vec![],
+            },
+        ));
+        self.add_code_system(snomed);
+        self.add_code_system(local);
+        self.add_concept_map(FhirConceptMap::specialty_to_snomed());
+    }
+    // Adds the catalogs' displays as designations of the concepts they translate
+    pub fn add_localization(&mut self, localization: &FhirLocalization) {
+        for catalog in localization.get_catalogs() {
+            for (system, code, display) in catalog.displays() {
+                self.add_designation(system, code, catalog.get_language(), display.to_string());
+            }
+        }
+    }
+    pub fn add_code_system(&mut self, code_system: FhirCodeSystem) {
+        self.code_systems
+            .insert(code_system.url.clone(), code_system);
+    }
+    pub fn add_value_set(&mut self, value_set: FhirValueSet) {
+        self.value_sets.insert(value_set.url.clone(), value_set);
+    }
+    pub fn add_concept_map(&mut self, concept_map: FhirConceptMap) {
+        self.concept_maps
+            .insert(concept_map.url.clone(), concept_map);
+    }
+    pub fn add_designation(
+        &mut self,
+        system: &str,
+        code: &str,
+        language: &str,
+        value: String,
+    ) -> bool {
+        match self
+            .code_systems
+            .get_mut(system)
+            .and_then(|code_system| code_system.find_concept_mut(code))
+        {
+            Some(concept) => {
+                concept.add_designation(language, value);
+                true
+            }
+            None => false,
+        }
+    }
+    pub fn get_code_system(&self, url: &str) -> Option<&FhirCodeSystem> {
+        self.code_systems.get(url)
+    }
+    pub fn get_value_set(&self, url: &str) -> Option<&FhirValueSet> {
+        self.value_sets.get(url)
+    }
+    pub fn get_concept_map(&self, url: &str) -> Option<&FhirConceptMap> {
+        self.concept_maps.get(url)
+    }
+    // ConceptMap/$translate across every loaded map, as (target system, target)
+    pub fn translate(&self, system: &str, code: &str) -> Vec<(String, FhirConceptMapTarget)> {
+        self.concept_maps
+            .values()
+            .flat_map(|concept_map| concept_map.translate(system, code))
+            .map(|(target_system, target)| (target_system, target.clone()))
+            .collect()
+    }
+    // Loads a CodeSystem, ValueSet, ConceptMap or a Bundle of them from FHIR JSON
+    pub fn load_json(&mut self, json: &str) -> Result<(), String> {
+        let value: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        self.load_value(value)
+    }
+    pub fn load_value(&mut self, value: Value) -> Result<(), String> {
+        match value.get("resourceType").and_then(Value::as_str) {
+            Some("CodeSystem") => {
+                let code_system = serde_json::from_value(value).map_err(|e| e.to_string())?;
+                self.add_code_system(code_system);
+                Ok(())
+            }
+            Some("ConceptMap") => {
+                let concept_map = serde_json::from_value(value).map_err(|e| e.to_string())?;
+                self.add_concept_map(concept_map);
+                Ok(())
+            }
+            Some("ValueSet") => {
+                let value_set = serde_json::from_value(value).map_err(|e| e.to_string())?;
+                self.add_value_set(value_set);
+                Ok(())
+            }
+            Some("Bundle") => {
+                let entries = value
+                    .get("entry")
+                    .and_then(Value::as_array)
+                    .cloned()
+                    .unwrap_or_default();
+                for entry in entries {
+                    if let Some(resource) = entry.get("resource") {
+                        self.load_value(resource.clone())?;
+                    }
+                }
+                Ok(())
+            }
+            Some(other) => Err(format!("Unsupported terminology resource: {}", other)),
+            None => Err("Missing resourceType".to_string()),
+        }
+    }
+    pub fn display(&self, system: &str, code: &str, language: Option<&str>) -> Option<String> {
+        self.code_systems.get(system)?.display(code, language)
+    }
+    // CodeSystem/$lookup
+    pub fn lookup(
+        &self,
+        system: &str,
+        code: &str,
+        language: Option<&str>,
+    ) -> Result<FhirLookupResult, String> {
+        let code_system = self
+            .code_systems
+            .get(system)
+            .ok_or(format!("Unknown code system: {}", system))?;
+        let concept = code_system
+            .find_concept(code)
+            .ok_or(format!("Code {} not found in {}", code, system))?;
+        Ok(FhirLookupResult {
+            name: code_system.name.clone().unwrap_or(system.to_string()),
+            version: code_system.version.clone(),
+            display: code_system.display(code, language),
+            designation: concept.designation.clone(),
+        })
+    }
+    // CodeSystem/$validate-code
+    pub fn validate_code_in_system(
+        &self,
+        system: &str,
+        code: &str,
+        display: Option<&str>,
+    ) -> FhirValidateCodeResult {
+        let Some(code_system) = self.code_systems.get(system) else {
+            return FhirValidateCodeResult::invalid(format!("Unknown code system: {}", system));
+        };
+        let Some(concept) = code_system.find_concept(code) else {
+            return FhirValidateCodeResult::invalid(format!(
+                "Code {} not found in {}",
+                code, system
+            ));
+        };
+        Self::check_display(
+            concept.display.clone(),
+            display,
+            concept.matches_display(display.unwrap_or_default()),
+        )
+    }
+    // ValueSet/$validate-code
+    pub fn validate_code(
+        &self,
+        value_set: &str,
+        system: &str,
+        code: &str,
+        display: Option<&str>,
+    ) -> FhirValidateCodeResult {
+        let expansion = match self.expand(value_set) {
+            Ok(expansion) => expansion,
+            Err(e) => return FhirValidateCodeResult::invalid(e),
+        };
+        let Some(contains) = expansion.iter().find(|contains| {
+            contains.system.as_deref() == Some(system) && contains.code.as_deref() == Some(code)
+        }) else {
+            return FhirValidateCodeResult::invalid(format!(
+                "Code {}#{} is not in value set {}",
+                system, code, value_set
+            ));
+        };
+        let matches = display.is_some_and(|display| {
+            contains.display.as_deref() == Some(display)
+                || contains.designation.iter().any(|d| d.value == display)
+        });
+        Self::check_display(contains.display.clone(), display, matches)
+    }
+    fn check_display(
+        expected: Option<String>,
+        provided: Option<&str>,
+        matches: bool,
+    ) -> FhirValidateCodeResult {
+        match provided {
+            Some(provided) if !matches => FhirValidateCodeResult {
+                result: false,
+                display: expected.clone(),
+                message: Some(format!(
+                    "Display \"{}\" does not match expected \"{}\"",
+                    provided,
+                    expected.unwrap_or_default()
+                )),
+            },
+            _ => FhirValidateCodeResult {
+                result: true,
+                display: expected,
+                message: None,
+            },
+        }
+    }
+    // ValueSet/$expand, flattened
+    pub fn expand(&self, value_set: &str) -> Result<Vec<FhirValueSetContains>, String> {
+        self.expand_with_depth(value_set, 0)
+    }
+    fn expand_with_depth(
+        &self,
+        value_set: &str,
+        depth: usize,
+    ) -> Result<Vec<FhirValueSetContains>, String> {
+        if depth > 16 {
+            return Err(format!("Value set {} imports itself", value_set));
+        }
+        let value_set = self
+            .value_sets
+            .get(value_set)
+            .ok_or(format!("Unknown value set: {}", value_set))?;
+        let Some(compose) = &value_set.compose else {
+            let mut flattened = vec![];
+            if let Some(expansion) = &value_set.expansion {
+                flatten_contains(&expansion.contains, &mut flattened);
+            }
+            return Ok(flattened);
+        };
+        let mut included = vec![];
+        for include in &compose.include {
+            for contains in self.expand_include(include, depth)? {
+                if !included
+                    .iter()
+                    .any(|c: &FhirValueSetContains| same_code(c, &contains))
+                {
+                    included.push(contains);
+                }
+            }
+        }
+        for exclude in &compose.exclude {
+            let excluded = self.expand_include(exclude, depth)?;
+            included.retain(|contains| !excluded.iter().any(|e| same_code(e, contains)));
+        }
+        Ok(included)
+    }
+    fn expand_include(
+        &self,
+        include: &FhirValueSetInclude,
+        depth: usize,
+    ) -> Result<Vec<FhirValueSetContains>, String> {
+        let mut imported: Option<Vec<FhirValueSetContains>> = None;
+        for url in &include.value_set {
+            let expansion = self.expand_with_depth(url, depth + 1)?;
+            imported = Some(match imported {
+                Some(previous) => previous
+                    .into_iter()
+                    .filter(|contains| expansion.iter().any(|e| same_code(e, contains)))
+                    .collect(),
+                None => expansion,
+            });
+        }
+        let Some(system) = &include.system else {
+            return Ok(imported.unwrap_or_default());
+        };
+        let code_system = self.code_systems.get(system);
+        let mut codes: Vec<FhirValueSetContains> = if !include.concept.is_empty() {
+            include
+                .concept
+                .iter()
+                .map(|concept| {
+                    let defined = code_system.and_then(|cs| cs.find_concept(&concept.code));
+                    let mut designation = concept.designation.clone();
+                    if designation.is_empty() {
+                        designation = defined.map(|d| d.designation.clone()).unwrap_or_default();
+                    }
+                    FhirValueSetContains {
+                        system: Some(system.clone()),
+                        code: Some(concept.code.clone()),
+                        display: concept
+                            .display
+                            .clone()
+                            .or(defined.and_then(|d| d.display.clone())),
+                        designation,
+                        contains: vec![],
+                    }
+                })
+                .collect()
+        } else {
+            let code_system =
+                code_system.ok_or(format!("Cannot expand unknown code system: {}", system))?;
+            let mut concepts = code_system.all_concepts();
+            for filter in &include.filter {
+                concepts = apply_filter(code_system, concepts, filter)?;
+            }
+            concepts
+                .into_iter()
+                .map(|concept| contains_from(system, concept))
+                .collect()
+        };
+        if let Some(imported) = imported {
+            codes.retain(|contains| imported.iter().any(|i| same_code(i, contains)));
+        }
+        Ok(codes)
+    }
+}
+
+fn apply_filter<'a>(
+    code_system: &'a FhirCodeSystem,
+    concepts: Vec<&'a FhirCodeSystemConcept>,
+    filter: &FhirValueSetFilter,
+) -> Result<Vec<&'a FhirCodeSystemConcept>, String> {
+    if filter.property != "concept" && filter.property != "code" {
+        return Err(format!("Unsupported filter property: {}", filter.property));
+    }
+    let subsumed = |include_self: bool| -> Result<Vec<&'a str>, String> {
+        let root = code_system
+            .find_concept(&filter.value)
+            .ok_or(format!("Filter code {} not found", filter.value))?;
+        let mut codes: Vec<&str> = root.descendants().iter().map(|c| c.code.as_str()).collect();
+        if include_self {
+            codes.push(root.code.as_str());
+        }
+        Ok(codes)
+    };
+    let allowed: Vec<&str> = match filter.op.as_str() {
+        "=" => vec![filter.value.as_str()],
+        "in" => filter.value.split(',').map(str::trim).collect(),
+        "is-a" => subsumed(true)?,
+        "descendent-of" => subsumed(false)?,
+        "is-not-a" => {
+            let excluded = subsumed(true)?;
+            return Ok(concepts
+                .into_iter()
+                .filter(|concept| !excluded.contains(&concept.code.as_str()))
+                .collect());
+        }
+        op => return Err(format!("Unsupported filter operation: {}", op)),
+    };
+    Ok(concepts
+        .into_iter()
+        .filter(|concept| allowed.contains(&concept.code.as_str()))
+        .collect())
+}
+
+fn contains_from(system: &str, concept: &FhirCodeSystemConcept) -> FhirValueSetContains {
+    FhirValueSetContains {
+        system: Some(system.to_string()),
+        code: Some(concept.code.clone()),
+        display: concept.display.clone(),
+        designation: concept.designation.clone(),
+        contains: vec![],
+    }
+}
+
+fn flatten_contains(contains: &[FhirValueSetContains], flattened: &mut Vec<FhirValueSetContains>) {
+    for entry in contains {
+        if entry.code.is_some() {
+            let mut leaf = entry.clone();
+            leaf.contains = vec![];
+            flattened.push(leaf);
+        }
+        flatten_contains(&entry.contains, flattened);
+    }
+}
+
+fn same_code(a: &FhirValueSetContains, b: &FhirValueSetContains) -> bool {
+    a.system == b.system && a.code == b.code
+}
+
+// The published CodeSystem for the crate's local specialty codes
+pub fn local_specialty_code_system() -> Option<FhirCodeSystem> {
+    let mut code_system = FhirCodeSystem::from_coded_value_in::<FhirSpecialty>(
+        IGNIS_CODESYSTEM_SPECIALTY,
+        "IgnisSpecialty",
+    )?;
+    code_system.version = Some(env!("CARGO_PKG_VERSION").to_string());
+    for catalog in FhirLocalization::defaults().get_catalogs() {
+        for specialty in FhirSpecialty::iter().filter(|specialty| specialty.is_local()) {
+            let Some(display) = catalog.display(IGNIS_CODESYSTEM_SPECIALTY, &specialty.fhir_code())
+            else {
+                continue;
+            };
+            if let Some(concept) = code_system.find_concept_mut(&specialty.fhir_code()) {
+                concept.add_designation(catalog.get_language(), display.to_string());
+            }
+        }
+    }
+    Some(code_system)
+}
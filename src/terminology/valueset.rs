@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{codesystem::FhirConceptDesignation, terminology_json};
+
+// https://www.hl7.org/fhir/valueset.html
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirValueSet {
+    pub url: String,
+    pub version: Option<String>,
+    pub name: Option<String>,
+    pub status: String,
+    pub compose: Option<FhirValueSetCompose>,
+    pub expansion: Option<FhirValueSetExpansion>,
+}
+impl FhirValueSet {
+    pub fn to_fhir_json(&self) -> Result<Value, String> {
+        terminology_json("ValueSet", self)
+    }
+    pub fn new(url: String, name: Option<String>, compose: FhirValueSetCompose) -> Self {
+        Self {
+            url,
+            version: None,
+            name,
+            status: "active".to_string(),
+            compose: Some(compose),
+            expansion: None,
+        }
+    }
+    pub fn all_of_system(url: String, name: Option<String>, system: String) -> Self {
+        Self::new(
+            url,
+            name,
+            FhirValueSetCompose {
+                include: vec![FhirValueSetInclude::system(system)],
+                exclude: vec![],
+            },
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirValueSetCompose {
+    #[serde(default)]
+    pub include: Vec<FhirValueSetInclude>,
+    #[serde(default)]
+    pub exclude: Vec<FhirValueSetInclude>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirValueSetInclude {
+    pub system: Option<String>,
+    pub version: Option<String>,
+    #[serde(default)]
+    pub concept: Vec<FhirValueSetConcept>,
+    #[serde(default)]
+    pub filter: Vec<FhirValueSetFilter>,
+    #[serde(default, rename = "valueSet")]
+    pub value_set: Vec<String>,
+}
+impl FhirValueSetInclude {
+    pub fn system(system: String) -> Self {
+        Self {
+            system: Some(system),
+            version: None,
+            concept: vec![],
+            filter: vec![],
+            value_set: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirValueSetConcept {
+    pub code: String,
+    pub display: Option<String>,
+    #[serde(default)]
+    pub designation: Vec<FhirConceptDesignation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirValueSetFilter {
+    pub property: String,
+    pub op: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirValueSetExpansion {
+    pub timestamp: Option<String>,
+    pub total: Option<u32>,
+    #[serde(default)]
+    pub contains: Vec<FhirValueSetContains>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FhirValueSetContains {
+    pub system: Option<String>,
+    pub code: Option<String>,
+    pub display: Option<String>,
+    #[serde(default)]
+    pub designation: Vec<FhirConceptDesignation>,
+    #[serde(default)]
+    pub contains: Vec<FhirValueSetContains>,
+}
@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+use super::fhir_coded_value;
+
 // https://www.hl7.org/fhir/valueset-name-use.html
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FhirNameUse {
     Usual,
     Official,
@@ -11,8 +13,17 @@ pub enum FhirNameUse {
     Old,
     Maiden,
 }
+fhir_coded_value!(FhirNameUse, code, "http://hl7.org/fhir/name-use", {
+    Usual => ("usual", "Usual"),
+    Official => ("official", "Official"),
+    Temp => ("temp", "Temp"),
+    Nickname => ("nickname", "Nickname"),
+    Anonymous => ("anonymous", "Anonymous"),
+    Old => ("old", "Old"),
+    Maiden => ("maiden", "Name changed for Marriage"),
+});
 // https://www.hl7.org/fhir/valueset-organization-type.html
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub enum FhirOrganizationType {
     Provider,
     Department,
@@ -27,3 +38,17 @@ pub enum FhirOrganizationType {
     NonHealthcareBusiness,
     Other,
 }
+fhir_coded_value!(FhirOrganizationType, "http://terminology.hl7.org/CodeSystem/organization-type", {
+    Provider => ("prov", "Healthcare Provider"),
+    Department => ("dept", "Hospital Department"),
+    Team => ("team", "Organizational team"),
+    Government => ("govt", "Government"),
+    InsuranceCompany => ("ins", "Insurance Company"),
+    Payer => ("pay", "Payer"),
+    EducationalInstitute => ("edu", "Educational Institute"),
+    ReligiousInstitution => ("reli", "Religious Institution"),
+    ClinicalResearchSponsor => ("crs", "Clinical Research Sponsor"),
+    CommunityGroup => ("cg", "Community Group"),
+    NonHealthcareBusiness => ("bus", "Non-Healthcare Business or Corporation"),
+    Other => ("other", "Other"),
+});
@@ -1,13 +1,18 @@
-use serde::{Deserialize, Serialize};
+use super::fhir_coded_value;
 // https://www.hl7.org/fhir/valueset-address-type.html
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FhirAddressType {
     Postal,
     Physical,
     Both,
 }
+fhir_coded_value!(FhirAddressType, code, "http://hl7.org/fhir/address-type", {
+    Postal => ("postal", "Postal"),
+    Physical => ("physical", "Physical"),
+    Both => ("both", "Postal & Physical"),
+});
 // https://www.hl7.org/fhir/valueset-address-use.html
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FhirAddressUse {
     Home,
     Work,
@@ -15,3 +20,10 @@ pub enum FhirAddressUse {
     Old,
     Billing,
 }
+fhir_coded_value!(FhirAddressUse, code, "http://hl7.org/fhir/address-use", {
+    Home => ("home", "Home"),
+    Work => ("work", "Work"),
+    Temporary => ("temp", "Temporary"),
+    Old => ("old", "Old / Incorrect"),
+    Billing => ("billing", "Billing"),
+});
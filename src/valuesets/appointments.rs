@@ -1,6 +1,7 @@
-use serde::{Deserialize, Serialize};
+use super::{fhir_coded_value, FhirCodedValue};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+// https://www.hl7.org/fhir/valueset-appointmentresponse-status.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FhirAppointmentResponseStatus {
     Accepted,       // The participant has accepted the appointment.
     Declined, // The participant has declined the appointment and will not participate in the appointment.
@@ -8,30 +9,23 @@ pub enum FhirAppointmentResponseStatus {
     NeedsAction, // The participant needs to indicate if they accept the appointment by changing this status to one of the other statuses.
     EnteredInError, // This instance should not have been part of this patient's medical record.
 }
+fhir_coded_value!(FhirAppointmentResponseStatus, code, "http://hl7.org/fhir/appointmentresponse-status", {
+    Accepted => ("accepted", "Accepted"),
+    Declined => ("declined", "Declined"),
+    Tentative => ("tentative", "Tentative"),
+    NeedsAction => ("needs-action", "Needs Action"),
+    EnteredInError => ("entered-in-error", "Entered in error"),
+});
 
 impl FhirAppointmentResponseStatus {
-    pub fn to_str(&self) -> &str {
-        match self {
-            FhirAppointmentResponseStatus::Accepted => "Accepted",
-            FhirAppointmentResponseStatus::Declined => "Declined",
-            FhirAppointmentResponseStatus::Tentative => "Tentative",
-            FhirAppointmentResponseStatus::NeedsAction => "NeedsAction",
-            FhirAppointmentResponseStatus::EnteredInError => "EnteredInError",
-        }
-    }
+    #[deprecated(note = "use FhirCodedValue::display_in(\"es\")")]
     pub fn to_spanish_str(&self) -> &str {
-        match self {
-            FhirAppointmentResponseStatus::Accepted => "Aceptado",
-            FhirAppointmentResponseStatus::Declined => "Rechazado",
-            FhirAppointmentResponseStatus::Tentative => "Tentativo",
-            FhirAppointmentResponseStatus::NeedsAction => "Necesita acción",
-            FhirAppointmentResponseStatus::EnteredInError => "Ingresado por error",
-        }
+        self.display_in("es")
     }
 }
 
 // https://www.hl7.org/fhir/valueset-appointmentstatus.html
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FhirAppointmentStatus {
     Proposed,
     Pending,
@@ -44,34 +38,22 @@ pub enum FhirAppointmentStatus {
     CheckedIn,
     Waitlist,
 }
+fhir_coded_value!(FhirAppointmentStatus, code, "http://hl7.org/fhir/appointmentstatus", {
+    Proposed => ("proposed", "Proposed"),
+    Pending => ("pending", "Pending"),
+    Booked => ("booked", "Booked"),
+    Arrived => ("arrived", "Arrived"),
+    Fulfilled => ("fulfilled", "Fulfilled"),
+    Cancelled => ("cancelled", "Cancelled"),
+    Noshow => ("noshow", "No Show"),
+    EnteredInError => ("entered-in-error", "Entered in error"),
+    CheckedIn => ("checked-in", "Checked In"),
+    Waitlist => ("waitlist", "Waitlisted"),
+});
 
 impl FhirAppointmentStatus {
-    pub fn to_str(&self) -> &str {
-        match self {
-            FhirAppointmentStatus::Proposed => "Proposed",
-            FhirAppointmentStatus::Pending => "Pending",
-            FhirAppointmentStatus::Booked => "Booked",
-            FhirAppointmentStatus::Arrived => "Arrived",
-            FhirAppointmentStatus::Fulfilled => "Fulfilled",
-            FhirAppointmentStatus::Cancelled => "Cancelled",
-            FhirAppointmentStatus::Noshow => "Noshow",
-            FhirAppointmentStatus::EnteredInError => "EnteredInError",
-            FhirAppointmentStatus::CheckedIn => "CheckedIn",
-            FhirAppointmentStatus::Waitlist => "Waitlist",
-        }
-    }
+    #[deprecated(note = "use FhirCodedValue::display_in(\"es\")")]
     pub fn to_spanish_str(&self) -> &str {
-        match self {
-            FhirAppointmentStatus::Proposed => "Propuesto",
-            FhirAppointmentStatus::Pending => "Pendiente",
-            FhirAppointmentStatus::Booked => "Reservado",
-            FhirAppointmentStatus::Arrived => "Llegado",
-            FhirAppointmentStatus::Fulfilled => "Cumplido",
-            FhirAppointmentStatus::Cancelled => "Cancelado",
-            FhirAppointmentStatus::Noshow => "No presentado",
-            FhirAppointmentStatus::EnteredInError => "Ingresado por error",
-            FhirAppointmentStatus::CheckedIn => "Registrado",
-            FhirAppointmentStatus::Waitlist => "Lista de espera",
-        }
+        self.display_in("es")
     }
 }
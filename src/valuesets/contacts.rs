@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
+
+use super::fhir_coded_value;
 // https://www.hl7.org/fhir/valueset-contact-point-use.html
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FhirContactPointUse {
     Home,
     Work,
@@ -8,8 +10,15 @@ pub enum FhirContactPointUse {
     Old,
     Mobile,
 }
+fhir_coded_value!(FhirContactPointUse, code, "http://hl7.org/fhir/contact-point-use", {
+    Home => ("home", "Home"),
+    Work => ("work", "Work"),
+    Temp => ("temp", "Temp"),
+    Old => ("old", "Old"),
+    Mobile => ("mobile", "Mobile"),
+});
 // https://www.hl7.org/fhir/valueset-contact-point-system.html
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FhirContactPointSystem {
     Phone,
     Fax,
@@ -19,8 +28,17 @@ pub enum FhirContactPointSystem {
     Sms,
     Other,
 }
+fhir_coded_value!(FhirContactPointSystem, code, "http://hl7.org/fhir/contact-point-system", {
+    Phone => ("phone", "Phone"),
+    Fax => ("fax", "Fax"),
+    Email => ("email", "Email"),
+    Pager => ("pager", "Pager"),
+    Url => ("url", "URL"),
+    Sms => ("sms", "SMS"),
+    Other => ("other", "Other"),
+});
 // https://terminology.hl7.org/5.1.0/ValueSet-contactentity-type.html
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub enum FhirContactEntityType {
     Billing,
     Administrative,
@@ -29,3 +47,11 @@ pub enum FhirContactEntityType {
     Patient,
     Press,
 }
+fhir_coded_value!(FhirContactEntityType, "http://terminology.hl7.org/CodeSystem/contactentity-type", {
+    Billing => ("BILL", "Billing"),
+    Administrative => ("ADMIN", "Administrative"),
+    HumanResource => ("HR", "Human Resource"),
+    Payor => ("PAYOR", "Payor"),
+    Patient => ("PATINF", "Patient"),
+    Press => ("PRESS", "Press"),
+});
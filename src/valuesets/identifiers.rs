@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use super::fhir_coded_value;
+
+// https://www.hl7.org/fhir/valueset-identifier-use.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FhirIdentifierUse {
+    Usual,
+    Official,
+    Temp,
+    Secondary,
+    Old,
+}
+fhir_coded_value!(FhirIdentifierUse, code, "http://hl7.org/fhir/identifier-use", {
+    Usual => ("usual", "Usual"),
+    Official => ("official", "Official"),
+    Temp => ("temp", "Temp"),
+    Secondary => ("secondary", "Secondary"),
+    Old => ("old", "Old"),
+});
+// https://www.hl7.org/fhir/valueset-identifier-type.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FhirIdentifierType {
+    DriversLicense,
+    MedicalLicense,
+    NationalProviderIdentifier,
+    NationalIdentifier,
+    Passport,
+    ProviderNumber,
+    TaxId,
+}
+fhir_coded_value!(FhirIdentifierType, "http://terminology.hl7.org/CodeSystem/v2-0203", {
+    DriversLicense => ("DL", "Driver's license number"),
+    MedicalLicense => ("MD", "Medical License number"),
+    NationalProviderIdentifier => ("NPI", "National provider identifier"),
+    NationalIdentifier => ("NI", "National unique individual identifier"),
+    Passport => ("PPN", "Passport number"),
+    ProviderNumber => ("PRN", "Provider number"),
+    TaxId => ("TAX", "Tax ID number"),
+});
@@ -1,12 +1,87 @@
+mod actors;
+mod addresses;
 mod appointments;
 mod contacts;
+mod identifiers;
+mod outcomes;
+mod quantities;
 mod specialty;
-mod addresses;
-mod actors;
+mod specialty_hierarchy;
+pub use actors::{FhirNameUse, FhirOrganizationType};
+pub use addresses::{FhirAddressType, FhirAddressUse};
 pub use appointments::{FhirAppointmentResponseStatus, FhirAppointmentStatus};
 pub use contacts::{FhirContactEntityType, FhirContactPointSystem, FhirContactPointUse};
+pub use identifiers::{FhirIdentifierType, FhirIdentifierUse};
+pub use outcomes::{FhirIssueSeverity, FhirIssueType};
+pub use quantities::FhirQuantityComparator;
 pub use specialty::FhirSpecialty;
-pub use addresses::{FhirAddressUse, FhirAddressType};
-pub use actors::{FhirNameUse, FhirOrganizationType};
+pub use specialty_hierarchy::FhirSpecialtyGroup;
+
+use crate::locale::FhirLocalization;
 
+// Maps a value set enum to the code system it is drawn from, so it can be
+// exported as a FHIR Coding and read back from one.
+pub trait FhirCodedValue: Sized {
+    fn fhir_system(&self) -> &str;
+    fn fhir_code(&self) -> String;
+    fn fhir_display(&self) -> &str;
+    fn from_fhir_code(code: &str) -> Option<Self>;
+    fn all() -> Vec<Self>;
+    // The display in `language` from the bundled catalogs, else the FHIR display
+    fn display_in(&self, language: &str) -> &str {
+        FhirLocalization::defaults().display_of(self, language)
+    }
+}
 
+// Value sets bound to a `code` element pass `code` to be written as their FHIR
+// code. Notes from earlier versions hold the variant name, which is still read.
+macro_rules! fhir_coded_value {
+    ($name:ident, code, $system:expr, { $($variant:ident => ($code:expr, $display:expr)),* $(,)? }) => {
+        $crate::valuesets::fhir_coded_value!($name, $system, { $($variant => ($code, $display)),* });
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&$crate::valuesets::FhirCodedValue::fhir_code(self))
+            }
+        }
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let code = <String as serde::Deserialize>::deserialize(deserializer)?;
+                match code.as_str() {
+                    $($code | stringify!($variant) => Ok($name::$variant),)*
+                    _ => Err(serde::de::Error::custom(format!(
+                        "Unknown {} code: {}",
+                        stringify!($name),
+                        code
+                    ))),
+                }
+            }
+        }
+    };
+    ($name:ident, $system:expr, { $($variant:ident => ($code:expr, $display:expr)),* $(,)? }) => {
+        impl $crate::valuesets::FhirCodedValue for $name {
+            fn fhir_system(&self) -> &str {
+                $system
+            }
+            fn fhir_code(&self) -> String {
+                match self {
+                    $($name::$variant => $code.to_string(),)*
+                }
+            }
+            fn fhir_display(&self) -> &str {
+                match self {
+                    $($name::$variant => $display,)*
+                }
+            }
+            fn from_fhir_code(code: &str) -> Option<Self> {
+                match code {
+                    $($code => Some($name::$variant),)*
+                    _ => None,
+                }
+            }
+            fn all() -> Vec<Self> {
+                vec![$($name::$variant),*]
+            }
+        }
+    };
+}
+pub(crate) use fhir_coded_value;
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use super::fhir_coded_value;
+
+// https://www.hl7.org/fhir/valueset-issue-severity.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FhirIssueSeverity {
+    Fatal,
+    Error,
+    Warning,
+    Information,
+}
+fhir_coded_value!(FhirIssueSeverity, "http://hl7.org/fhir/issue-severity", {
+    Fatal => ("fatal", "Fatal"),
+    Error => ("error", "Error"),
+    Warning => ("warning", "Warning"),
+    Information => ("information", "Information"),
+});
+// https://www.hl7.org/fhir/valueset-issue-type.html
+// Only the codes raised by resource validation and version conversion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FhirIssueType {
+    Invalid,
+    Structure,
+    Required,
+    Value,
+    Invariant,
+    CodeInvalid,
+    BusinessRule,
+    NotSupported,
+    Informational,
+}
+fhir_coded_value!(FhirIssueType, "http://hl7.org/fhir/issue-type", {
+    Invalid => ("invalid", "Invalid Content"),
+    Structure => ("structure", "Structural Issue"),
+    Required => ("required", "Required element missing"),
+    Value => ("value", "Element value invalid"),
+    Invariant => ("invariant", "Validation rule failed"),
+    CodeInvalid => ("code-invalid", "Invalid Code"),
+    BusinessRule => ("business-rule", "Business Rule Violation"),
+    NotSupported => ("not-supported", "Content not supported"),
+    Informational => ("informational", "Informational Note"),
+});
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use super::fhir_coded_value;
+// https://www.hl7.org/fhir/valueset-quantity-comparator.html
+// Serialized as the FHIR code since it only appears inside Quantity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FhirQuantityComparator {
+    #[serde(rename = "<")]
+    LessThan,
+    #[serde(rename = "<=")]
+    LessOrEqual,
+    #[serde(rename = ">=")]
+    GreaterOrEqual,
+    #[serde(rename = ">")]
+    GreaterThan,
+    #[serde(rename = "ad")]
+    Sufficient,
+}
+fhir_coded_value!(FhirQuantityComparator, "http://hl7.org/fhir/quantity-comparator", {
+    LessThan => ("<", "Less than"),
+    LessOrEqual => ("<=", "Less or Equal to"),
+    GreaterOrEqual => (">=", "Greater or Equal to"),
+    GreaterThan => (">", "Greater than"),
+    Sufficient => ("ad", "Sufficient to achieve this total quantity"),
+});
@@ -1,4 +1,8 @@
 use serde::{Deserialize, Serialize};
+
+use super::FhirCodedValue;
+use crate::consts::{IGNIS_CODESYSTEM_SPECIALTY, SNOMED_CT_SYSTEM};
+use crate::locale::FhirLocalization;
 // https://www.hl7.org/fhir/valueset-c80-practice-codes.html
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FhirSpecialty {
@@ -126,248 +130,350 @@ pub enum FhirSpecialty {
 }
 
 impl FhirSpecialty {
+    pub const ALL: [FhirSpecialty; 121] = [
+        FhirSpecialty::AdultMentalIllness,
+        FhirSpecialty::Anesthetics,
+        FhirSpecialty::AudiologicalMedicine,
+        FhirSpecialty::BloodBankingAndTransfusionMedicine,
+        FhirSpecialty::BurnsCare,
+        FhirSpecialty::Cardiology,
+        FhirSpecialty::ClinicalCytogeneticsAndMolecularGenetics,
+        FhirSpecialty::ClinicalGenetics,
+        FhirSpecialty::ClinicalHematology,
+        FhirSpecialty::ClinicalImmunology,
+        FhirSpecialty::ClinicalMicrobiology,
+        FhirSpecialty::ClinicalNeuroPhysiology,
+        FhirSpecialty::ClinicalOncology,
+        FhirSpecialty::ClinicalPharmacology,
+        FhirSpecialty::ClinicalPhysiology,
+        FhirSpecialty::CommunityMedicine,
+        FhirSpecialty::CriticalCareMedicine,
+        FhirSpecialty::DentalMedicineSpecialties,
+        FhirSpecialty::DentalGeneralDentalPractice,
+        FhirSpecialty::Dermatology,
+        FhirSpecialty::DiabeticMedicine,
+        FhirSpecialty::DiveMedicine,
+        FhirSpecialty::Endocrinology,
+        FhirSpecialty::FamilyPractice,
+        FhirSpecialty::Gastroenterology,
+        FhirSpecialty::GeneralMedicalPractice,
+        FhirSpecialty::GeneralMedicine,
+        FhirSpecialty::GeneralPathology,
+        FhirSpecialty::GeneralPractice,
+        FhirSpecialty::GenitoUrinaryMedicine,
+        FhirSpecialty::GeriatricMedicine,
+        FhirSpecialty::GynecologyMastology,
+        FhirSpecialty::GynecologicalOncology,
+        FhirSpecialty::Gynecology,
+        FhirSpecialty::PerinatalMedicine,
+        FhirSpecialty::Hematopathology,
+        FhirSpecialty::Hepatology,
+        FhirSpecialty::Histopathology,
+        FhirSpecialty::Immunopathology,
+        FhirSpecialty::InfectiousDiseases,
+        FhirSpecialty::InternalMedicine,
+        FhirSpecialty::LearningDisability,
+        FhirSpecialty::MedicalOncology,
+        FhirSpecialty::MedicalOphthalmology,
+        FhirSpecialty::MilitaryMedicine,
+        FhirSpecialty::Nephrology,
+        FhirSpecialty::Neurology,
+        FhirSpecialty::Neuropathology,
+        FhirSpecialty::NuclearMedicine,
+        FhirSpecialty::Obstetrics,
+        FhirSpecialty::ObstetricsAndGynecology,
+        FhirSpecialty::OccupationalMedicine,
+        FhirSpecialty::OphthalmicSurgery,
+        FhirSpecialty::Ophthalmology,
+        FhirSpecialty::OsteopathicManipulativeMedicine,
+        FhirSpecialty::Otolaryngology,
+        FhirSpecialty::PainManagement,
+        FhirSpecialty::PalliativeMedicine,
+        FhirSpecialty::PediatricGeneral,
+        FhirSpecialty::PediatricPsychiatry,
+        FhirSpecialty::PediatricCardiology,
+        FhirSpecialty::PediatricDentistry,
+        FhirSpecialty::PediatricEndocrinology,
+        FhirSpecialty::PediatricGastroenterology,
+        FhirSpecialty::PediatricGenetics,
+        FhirSpecialty::PediatricHematology,
+        FhirSpecialty::PediatricImmunology,
+        FhirSpecialty::PediatricInfectiousDiseases,
+        FhirSpecialty::PediatricNephrology,
+        FhirSpecialty::PediatricOncology,
+        FhirSpecialty::PediatricOphthalmology,
+        FhirSpecialty::PediatricPulmonology,
+        FhirSpecialty::PediatricRheumatology,
+        FhirSpecialty::PediatricSurgery,
+        FhirSpecialty::PediatricSurgeryBoneMarrowTransplantation,
+        FhirSpecialty::PreventiveMedicine,
+        FhirSpecialty::Psychiatry,
+        FhirSpecialty::Psychotherapy,
+        FhirSpecialty::PublicHealthMedicine,
+        FhirSpecialty::PulmonaryMedicine,
+        FhirSpecialty::RadiationOncology,
+        FhirSpecialty::Radiology,
+        FhirSpecialty::InterventionalRadiology,
+        FhirSpecialty::Rehabilitation,
+        FhirSpecialty::RespiteCare,
+        FhirSpecialty::Rheumatology,
+        FhirSpecialty::SleepStudies,
+        FhirSpecialty::SurgeryPreSurgeryConsultation,
+        FhirSpecialty::SurgeryBoneAndMarrowTransplantation,
+        FhirSpecialty::SurgeryBreastSurgery,
+        FhirSpecialty::SurgeryCardiacSurgery,
+        FhirSpecialty::SurgeryCardiothoracicTransplantation,
+        FhirSpecialty::SurgeryColorectalSurgery,
+        FhirSpecialty::SurgeryDentalEndodontics,
+        FhirSpecialty::SurgeryDentalOralAndMaxillofacialSurgery,
+        FhirSpecialty::SurgeryDentalOralSurgery,
+        FhirSpecialty::SurgeryDentalOrthodontics,
+        FhirSpecialty::SurgeryDentalPeriodontalSurgery,
+        FhirSpecialty::SurgeryDentalProstheticDentistry,
+        FhirSpecialty::SurgeryDentistryRestorativeDentistry,
+        FhirSpecialty::SurgeryDentistrySurgical,
+        FhirSpecialty::SurgeryDermatologicSurgery,
+        FhirSpecialty::SurgeryEarNoseAndThroatSurgery,
+        FhirSpecialty::SurgeryGeneral,
+        FhirSpecialty::SurgeryHepatobiliaryAndPancreaticSurgery,
+        FhirSpecialty::SurgeryNeurosurgery,
+        FhirSpecialty::SurgeryPlasticSurgery,
+        FhirSpecialty::SurgeryTransplantationSurgery,
+        FhirSpecialty::SurgeryTraumaAndOrthopedics,
+        FhirSpecialty::SurgeryBariatric,
+        FhirSpecialty::SurgeryEndoscopic,
+        FhirSpecialty::SurgeryVascular,
+        FhirSpecialty::SurgicalOncology,
+        FhirSpecialty::SurgicalAccidentAndEmergency,
+        FhirSpecialty::ThoracicMedicine,
+        FhirSpecialty::Toxicology,
+        FhirSpecialty::TropicalMedicine,
+        FhirSpecialty::UrologicalOncology,
+        FhirSpecialty::Urology,
+        FhirSpecialty::MedicalSpecialtyOtherNotListed,
+        FhirSpecialty::SurgicalSpecialtyOtherNotListed,
+    ];
+    #[deprecated(note = "unknown codes fall back to GeneralPractice, use try_from_code")]
     pub fn from_code(code: u32) -> Self {
-        match code {
-            408467006 => FhirSpecialty::AdultMentalIllness,
-            394577000 => FhirSpecialty::Anesthetics,
-            394578005 => FhirSpecialty::AudiologicalMedicine,
-            421661004 => FhirSpecialty::BloodBankingAndTransfusionMedicine,
-            408462000 => FhirSpecialty::BurnsCare,
-            394579002 => FhirSpecialty::Cardiology,
-            394804000 => FhirSpecialty::ClinicalCytogeneticsAndMolecularGenetics,
-            394580004 => FhirSpecialty::ClinicalGenetics,
-            394803006 => FhirSpecialty::ClinicalHematology,
-            408480009 => FhirSpecialty::ClinicalImmunology,
-            408454008 => FhirSpecialty::ClinicalMicrobiology,
-            394809005 => FhirSpecialty::ClinicalNeuroPhysiology,
-            394592004 => FhirSpecialty::ClinicalOncology,
-            394600006 => FhirSpecialty::ClinicalPharmacology,
-            394601005 => FhirSpecialty::ClinicalPhysiology,
-            394581000 => FhirSpecialty::CommunityMedicine,
-            408478003 => FhirSpecialty::CriticalCareMedicine,
-            394812008 => FhirSpecialty::DentalMedicineSpecialties,
-            408444009 => FhirSpecialty::DentalGeneralDentalPractice,
-            394582007 => FhirSpecialty::Dermatology,
-            408475000 => FhirSpecialty::DiabeticMedicine,
-            410005002 => FhirSpecialty::DiveMedicine,
-            394583002 => FhirSpecialty::Endocrinology,
-            419772000 => FhirSpecialty::FamilyPractice,
-            394584008 => FhirSpecialty::Gastroenterology,
-            408443003 => FhirSpecialty::GeneralMedicalPractice,
-            394802001 => FhirSpecialty::GeneralMedicine,
-            394915009 => FhirSpecialty::GeneralPathology,
-            394814009 => FhirSpecialty::GeneralPractice,
-            394808002 => FhirSpecialty::GenitoUrinaryMedicine,
-            394811001 => FhirSpecialty::GeriatricMedicine,
-            408446006 => FhirSpecialty::GynecologicalOncology,
-            394586005 => FhirSpecialty::Gynecology,
-            394916005 => FhirSpecialty::Hematopathology,
-            408472002 => FhirSpecialty::Hepatology,
-            394597005 => FhirSpecialty::Histopathology,
-            394598000 => FhirSpecialty::Immunopathology,
-            394807007 => FhirSpecialty::InfectiousDiseases,
-            419192003 => FhirSpecialty::InternalMedicine,
-            408468001 => FhirSpecialty::LearningDisability,
-            394593009 => FhirSpecialty::MedicalOncology,
-            394813003 => FhirSpecialty::MedicalOphthalmology,
-            410001006 => FhirSpecialty::MilitaryMedicine,
-            394589003 => FhirSpecialty::Nephrology,
-            394591006 => FhirSpecialty::Neurology,
-            394599008 => FhirSpecialty::Neuropathology,
-            394649004 => FhirSpecialty::NuclearMedicine,
-            408470005 => FhirSpecialty::Obstetrics,
-            394585009 => FhirSpecialty::ObstetricsAndGynecology,
-            394821009 => FhirSpecialty::OccupationalMedicine,
-            422191005 => FhirSpecialty::OphthalmicSurgery,
-            394594003 => FhirSpecialty::Ophthalmology,
-            416304004 => FhirSpecialty::OsteopathicManipulativeMedicine,
-            418960008 => FhirSpecialty::Otolaryngology,
-            394882004 => FhirSpecialty::PainManagement,
-            394806003 => FhirSpecialty::PalliativeMedicine,
-            394588008 => FhirSpecialty::PediatricGeneral,
-            394588006 => FhirSpecialty::PediatricPsychiatry,
-            408459003 => FhirSpecialty::PediatricCardiology,
-            394607009 => FhirSpecialty::PediatricDentistry,
-            419610006 => FhirSpecialty::PediatricEndocrinology,
-            418058008 => FhirSpecialty::PediatricGastroenterology,
-            420208008 => FhirSpecialty::PediatricGenetics,
-            418652005 => FhirSpecialty::PediatricHematology,
-            418535003 => FhirSpecialty::PediatricImmunology,
-            418862001 => FhirSpecialty::PediatricInfectiousDiseases,
-            419365004 => FhirSpecialty::PediatricNephrology,
-            418002000 => FhirSpecialty::PediatricOncology,
-            419983000 => FhirSpecialty::PediatricOphthalmology,
-            419170002 => FhirSpecialty::PediatricPulmonology,
-            419472004 => FhirSpecialty::PediatricRheumatology,
-            394539006 => FhirSpecialty::PediatricSurgery,
-            420112009 => FhirSpecialty::PediatricSurgeryBoneMarrowTransplantation,
-            409968004 => FhirSpecialty::PreventiveMedicine,
-            394587001 => FhirSpecialty::Psychiatry,
-            394913002 => FhirSpecialty::Psychotherapy,
-            408440000 => FhirSpecialty::PublicHealthMedicine,
-            418112009 => FhirSpecialty::PulmonaryMedicine,
-            419815003 => FhirSpecialty::RadiationOncology,
-            394914008 => FhirSpecialty::Radiology,
-            408455009 => FhirSpecialty::InterventionalRadiology,
-            394602003 => FhirSpecialty::Rehabilitation,
-            408447002 => FhirSpecialty::RespiteCare,
-            394810000 => FhirSpecialty::Rheumatology,
-            408450004 => FhirSpecialty::SleepStudies,
-            408469010 => FhirSpecialty::SurgeryPreSurgeryConsultation,
-            408476004 => FhirSpecialty::SurgeryBoneAndMarrowTransplantation,
-            408469009 => FhirSpecialty::SurgeryBreastSurgery,
-            408466002 => FhirSpecialty::SurgeryCardiacSurgery,
-            408471009 => FhirSpecialty::SurgeryCardiothoracicTransplantation,
-            408464004 => FhirSpecialty::SurgeryColorectalSurgery,
-            408441001 => FhirSpecialty::SurgeryDentalEndodontics,
-            408465003 => FhirSpecialty::SurgeryDentalOralAndMaxillofacialSurgery,
-            394605001 => FhirSpecialty::SurgeryDentalOralSurgery,
-            394608004 => FhirSpecialty::SurgeryDentalOrthodontics,
-            408461007 => FhirSpecialty::SurgeryDentalPeriodontalSurgery,
-            408460008 => FhirSpecialty::SurgeryDentalProstheticDentistry,
-            394606000 => FhirSpecialty::SurgeryDentistryRestorativeDentistry,
-            408449004 => FhirSpecialty::SurgeryDentistrySurgical,
-            418018006 => FhirSpecialty::SurgeryDermatologicSurgery,
-            394604002 => FhirSpecialty::SurgeryEarNoseAndThroatSurgery,
-            394609007 => FhirSpecialty::SurgeryGeneral,
-            408474001 => FhirSpecialty::SurgeryHepatobiliaryAndPancreaticSurgery,
-            394610002 => FhirSpecialty::SurgeryNeurosurgery,
-            394611003 => FhirSpecialty::SurgeryPlasticSurgery,
-            408477008 => FhirSpecialty::SurgeryTransplantationSurgery,
-            394801008 => FhirSpecialty::SurgeryTraumaAndOrthopedics,
-            394612005 => FhirSpecialty::Urology,
-            394733009 => FhirSpecialty::MedicalSpecialtyOtherNotListed,
-            394732004 => FhirSpecialty::SurgicalSpecialtyOtherNotListed,
-            999999900 => FhirSpecialty::PerinatalMedicine,
-            999999901 => FhirSpecialty::SurgeryBariatric,
-            999999902 => FhirSpecialty::SurgeryEndoscopic,
-            999999903 => FhirSpecialty::GynecologyMastology,
-            _ => FhirSpecialty::GeneralPractice,
+        Self::try_from_code(code).unwrap_or(FhirSpecialty::GeneralPractice)
+    }
+    pub fn try_from_code(code: u32) -> Result<Self, String> {
+        Self::iter()
+            .find(|specialty| specialty.code() == code)
+            .ok_or(format!("Unknown specialty code: {}", code))
+    }
+    // Matches the name in any bundled language, ignoring case and accents
+    pub fn from_display(name: &str) -> Result<Self, String> {
+        let normalized = normalize(name);
+        Self::iter()
+            .find(|specialty| {
+                specialty
+                    .localized_names()
+                    .iter()
+                    .any(|localized| normalize(localized) == normalized)
+            })
+            .ok_or(format!("Unknown specialty name: {}", name))
+    }
+    pub fn iter() -> impl Iterator<Item = FhirSpecialty> {
+        Self::ALL.into_iter()
+    }
+    // (variant, code, English display, Spanish display)
+    pub fn entries() -> impl Iterator<Item = (FhirSpecialty, u32, &'static str, String)> {
+        Self::iter().map(|specialty| {
+            (
+                specialty,
+                specialty.code(),
+                specialty.display(),
+                specialty.display_in("es").to_string(),
+            )
+        })
+    }
+    // Autocomplete search over the names in every bundled language. Exact matches rank
+    // first, then prefixes, word prefixes, substrings and finally near misses
+    // (small edit distance per word) so typos still find the specialty.
+    pub fn search(query: &str) -> Vec<FhirSpecialty> {
+        let query = normalize(query);
+        if query.is_empty() {
+            return vec![];
         }
+        let mut ranked: Vec<(usize, FhirSpecialty)> = Self::iter()
+            .filter_map(|specialty| {
+                specialty
+                    .localized_names()
+                    .iter()
+                    .filter_map(|name| search_rank(&normalize(name), &query))
+                    .min()
+                    .map(|rank| (rank, specialty))
+            })
+            .collect();
+        ranked.sort_by(|(a_rank, a), (b_rank, b)| {
+            a_rank.cmp(b_rank).then(a.display().cmp(b.display()))
+        });
+        ranked.into_iter().map(|(_, specialty)| specialty).collect()
+    }
+    // The English display followed by its translations in the bundled catalogs
+    fn localized_names(&self) -> Vec<&str> {
+        FhirLocalization::defaults()
+            .languages()
+            .into_iter()
+            .map(|language| FhirLocalization::defaults().display_of(self, language))
+            .collect()
     }
+    #[deprecated(note = "use FhirCodedValue::display_in(\"es\")")]
     pub fn to_spanish_string(&self) -> String {
+        self.display_in("es").to_string()
+    }
+    pub fn display(&self) -> &'static str {
+        match self {
+            FhirSpecialty::AdultMentalIllness => "Adult mental illness",
+            FhirSpecialty::Anesthetics => "Anesthetics",
+            FhirSpecialty::AudiologicalMedicine => "Audiological medicine",
+            FhirSpecialty::BloodBankingAndTransfusionMedicine => "Blood banking and transfusion medicine",
+            FhirSpecialty::BurnsCare => "Burns care",
+            FhirSpecialty::Cardiology => "Cardiology",
+            FhirSpecialty::ClinicalCytogeneticsAndMolecularGenetics => "Clinical cytogenetics and molecular genetics",
+            FhirSpecialty::ClinicalGenetics => "Clinical genetics",
+            FhirSpecialty::ClinicalHematology => "Clinical hematology",
+            FhirSpecialty::ClinicalImmunology => "Clinical immunology",
+            FhirSpecialty::ClinicalMicrobiology => "Clinical microbiology",
+            FhirSpecialty::ClinicalNeuroPhysiology => "Clinical neuro-physiology",
+            FhirSpecialty::ClinicalOncology => "Clinical oncology",
+            FhirSpecialty::ClinicalPharmacology => "Clinical pharmacology",
+            FhirSpecialty::ClinicalPhysiology => "Clinical physiology",
+            FhirSpecialty::CommunityMedicine => "Community medicine",
+            FhirSpecialty::CriticalCareMedicine => "Critical care medicine",
+            FhirSpecialty::DentalMedicineSpecialties => "Dental medicine specialties",
+            FhirSpecialty::DentalGeneralDentalPractice => "Dental-General dental practice",
+            FhirSpecialty::Dermatology => "Dermatology",
+            FhirSpecialty::DiabeticMedicine => "Diabetic medicine",
+            FhirSpecialty::DiveMedicine => "Dive medicine",
+            FhirSpecialty::Endocrinology => "Endocrinology",
+            FhirSpecialty::FamilyPractice => "Family practice",
+            FhirSpecialty::Gastroenterology => "Gastroenterology",
+            FhirSpecialty::GeneralMedicalPractice => "General medical practice",
+            FhirSpecialty::GeneralMedicine => "General medicine",
+            FhirSpecialty::GeneralPathology => "General pathology",
+            FhirSpecialty::GeneralPractice => "General practice",
+            FhirSpecialty::GenitoUrinaryMedicine => "Genito-urinary medicine",
+            FhirSpecialty::GeriatricMedicine => "Geriatric medicine",
+            FhirSpecialty::GynecologyMastology => "Gynecology-Mastology",
+            FhirSpecialty::GynecologicalOncology => "Gynecological oncology",
+            FhirSpecialty::Gynecology => "Gynecology",
+            FhirSpecialty::PerinatalMedicine => "Perinatal medicine",
+            FhirSpecialty::Hematopathology => "Hematopathology",
+            FhirSpecialty::Hepatology => "Hepatology",
+            FhirSpecialty::Histopathology => "Histopathology",
+            FhirSpecialty::Immunopathology => "Immunopathology",
+            FhirSpecialty::InfectiousDiseases => "Infectious diseases",
+            FhirSpecialty::InternalMedicine => "Internal medicine",
+            FhirSpecialty::LearningDisability => "Learning disability",
+            FhirSpecialty::MedicalOncology => "Medical oncology",
+            FhirSpecialty::MedicalOphthalmology => "Medical ophthalmology",
+            FhirSpecialty::MilitaryMedicine => "Military medicine",
+            FhirSpecialty::Nephrology => "Nephrology",
+            FhirSpecialty::Neurology => "Neurology",
+            FhirSpecialty::Neuropathology => "Neuropathology",
+            FhirSpecialty::NuclearMedicine => "Nuclear medicine",
+            FhirSpecialty::Obstetrics => "Obstetrics",
+            FhirSpecialty::ObstetricsAndGynecology => "Obstetrics and gynecology",
+            FhirSpecialty::OccupationalMedicine => "Occupational medicine",
+            FhirSpecialty::OphthalmicSurgery => "Ophthalmic surgery",
+            FhirSpecialty::Ophthalmology => "Ophthalmology",
+            FhirSpecialty::OsteopathicManipulativeMedicine => "Osteopathic manipulative medicine",
+            FhirSpecialty::Otolaryngology => "Otolaryngology",
+            FhirSpecialty::PainManagement => "Pain management",
+            FhirSpecialty::PalliativeMedicine => "Palliative medicine",
+            FhirSpecialty::PediatricGeneral => "Pediatric specialty",
+            FhirSpecialty::PediatricPsychiatry => "Pediatric (Child and adolescent) psychiatry",
+            FhirSpecialty::PediatricCardiology => "Pediatric cardiology",
+            FhirSpecialty::PediatricDentistry => "Pediatric dentistry",
+            FhirSpecialty::PediatricEndocrinology => "Pediatric endocrinology",
+            FhirSpecialty::PediatricGastroenterology => "Pediatric gastroenterology",
+            FhirSpecialty::PediatricGenetics => "Pediatric genetics",
+            FhirSpecialty::PediatricHematology => "Pediatric hematology",
+            FhirSpecialty::PediatricImmunology => "Pediatric immunology",
+            FhirSpecialty::PediatricInfectiousDiseases => "Pediatric infectious diseases",
+            FhirSpecialty::PediatricNephrology => "Pediatric nephrology",
+            FhirSpecialty::PediatricOncology => "Pediatric oncology",
+            FhirSpecialty::PediatricOphthalmology => "Pediatric ophthalmology",
+            FhirSpecialty::PediatricPulmonology => "Pediatric pulmonology",
+            FhirSpecialty::PediatricRheumatology => "Pediatric rheumatology",
+            FhirSpecialty::PediatricSurgery => "Pediatric surgery",
+            FhirSpecialty::PediatricSurgeryBoneMarrowTransplantation => "Pediatric bone marrow transplantation",
+            FhirSpecialty::PreventiveMedicine => "Preventive medicine",
+            FhirSpecialty::Psychiatry => "Psychiatry",
+            FhirSpecialty::Psychotherapy => "Psychotherapy",
+            FhirSpecialty::PublicHealthMedicine => "Public health medicine",
+            FhirSpecialty::PulmonaryMedicine => "Pulmonary medicine",
+            FhirSpecialty::RadiationOncology => "Radiation oncology",
+            FhirSpecialty::Radiology => "Radiology",
+            FhirSpecialty::InterventionalRadiology => "Radiology-Interventional radiology",
+            FhirSpecialty::Rehabilitation => "Rehabilitation",
+            FhirSpecialty::RespiteCare => "Respite care",
+            FhirSpecialty::Rheumatology => "Rheumatology",
+            FhirSpecialty::SleepStudies => "Sleep studies",
+            FhirSpecialty::SurgeryPreSurgeryConsultation => "Surgery-Pre-surgery consultation",
+            FhirSpecialty::SurgeryBoneAndMarrowTransplantation => "Surgery-Bone and marrow transplantation",
+            FhirSpecialty::SurgeryBreastSurgery => "Surgery-Breast surgery",
+            FhirSpecialty::SurgeryCardiacSurgery => "Surgery-Cardiac surgery",
+            FhirSpecialty::SurgeryCardiothoracicTransplantation => "Surgery-Cardiothoracic transplantation",
+            FhirSpecialty::SurgeryColorectalSurgery => "Surgery-Colorectal surgery",
+            FhirSpecialty::SurgeryDentalEndodontics => "Surgery-Dental-Endodontics",
+            FhirSpecialty::SurgeryDentalOralAndMaxillofacialSurgery => "Surgery-Dental-Oral and maxillofacial surgery",
+            FhirSpecialty::SurgeryDentalOralSurgery => "Surgery-Dental-Oral surgery",
+            FhirSpecialty::SurgeryDentalOrthodontics => "Surgery-Dental-Orthodontics",
+            FhirSpecialty::SurgeryDentalPeriodontalSurgery => "Surgery-Dental-Periodontal surgery",
+            FhirSpecialty::SurgeryDentalProstheticDentistry => "Surgery-Dental-Prosthetic dentistry (Prosthodontics)",
+            FhirSpecialty::SurgeryDentistryRestorativeDentistry => "Surgery-Dentistry-Restorative dentistry",
+            FhirSpecialty::SurgeryDentistrySurgical => "Surgery-Dentistry--surgical",
+            FhirSpecialty::SurgeryDermatologicSurgery => "Surgery-Dermatologic surgery",
+            FhirSpecialty::SurgeryEarNoseAndThroatSurgery => "Surgery-Ear, nose and throat surgery",
+            FhirSpecialty::SurgeryGeneral => "Surgery-general",
+            FhirSpecialty::SurgeryHepatobiliaryAndPancreaticSurgery => "Surgery-Hepatobiliary and pancreatic surgery",
+            FhirSpecialty::SurgeryNeurosurgery => "Surgery-Neurosurgery",
+            FhirSpecialty::SurgeryPlasticSurgery => "Surgery-Plastic surgery",
+            FhirSpecialty::SurgeryTransplantationSurgery => "Surgery-Transplantation surgery",
+            FhirSpecialty::SurgeryTraumaAndOrthopedics => "Surgery-Trauma and orthopedics",
+            FhirSpecialty::SurgeryBariatric => "Surgery-Bariatric",
+            FhirSpecialty::SurgeryEndoscopic => "Surgery-Endoscopic",
+            FhirSpecialty::SurgeryVascular => "Surgery-Vascular",
+            FhirSpecialty::SurgicalOncology => "Surgical oncology",
+            FhirSpecialty::SurgicalAccidentAndEmergency => "Surgical-Accident & emergency",
+            FhirSpecialty::ThoracicMedicine => "Thoracic medicine",
+            FhirSpecialty::Toxicology => "Toxicology",
+            FhirSpecialty::TropicalMedicine => "Tropical medicine",
+            FhirSpecialty::UrologicalOncology => "Urological oncology",
+            FhirSpecialty::Urology => "Urology",
+            FhirSpecialty::MedicalSpecialtyOtherNotListed => "Medical specialty--OTHER--NOT LISTED",
+            FhirSpecialty::SurgicalSpecialtyOtherNotListed => "Surgical specialty--OTHER--NOT LISTED",
+        }
+    }
+    // Codes in the 9999999xx range are not SNOMED CT concepts; they belong to
+    // the crate's own code system and map to SNOMED through a ConceptMap.
+    pub fn is_local(&self) -> bool {
+        matches!(
+            self,
+            FhirSpecialty::PerinatalMedicine
+                | FhirSpecialty::SurgeryBariatric
+                | FhirSpecialty::SurgeryEndoscopic
+                | FhirSpecialty::GynecologyMastology
+        )
+    }
+    pub fn system(&self) -> &'static str {
+        if self.is_local() {
+            IGNIS_CODESYSTEM_SPECIALTY
+        } else {
+            SNOMED_CT_SYSTEM
+        }
+    }
+    // Closest SNOMED CT specialty for a local code, itself for SNOMED codes
+    pub fn nearest_snomed(&self) -> FhirSpecialty {
         match self {
-            FhirSpecialty::AdultMentalIllness => "Enfermedad Mental Adulta".to_string(),
-            FhirSpecialty::Anesthetics => "Anestesiología".to_string(),
-            FhirSpecialty::AudiologicalMedicine => "Medicina Audiologica".to_string(),
-            FhirSpecialty::BloodBankingAndTransfusionMedicine => "Banco de Sangre y Medicina de Transfusión".to_string(),
-            FhirSpecialty::BurnsCare => "Cuidado de Quemaduras".to_string(),
-            FhirSpecialty::Cardiology => "Cardiología".to_string(),
-            FhirSpecialty::ClinicalCytogeneticsAndMolecularGenetics => "Citogenética Clínica y Genética Molecular".to_string(),
-            FhirSpecialty::ClinicalGenetics => "Genética Clínica".to_string(),
-            FhirSpecialty::ClinicalHematology => "Hematología Clínica".to_string(),
-            FhirSpecialty::ClinicalImmunology => "Inmunología Clínica".to_string(),
-            FhirSpecialty::ClinicalMicrobiology => "Microbiología Clínica".to_string(),
-            FhirSpecialty::ClinicalNeuroPhysiology => "Neurofisiología Clínica".to_string(),
-            FhirSpecialty::ClinicalOncology => "Oncología Clínica".to_string(),
-            FhirSpecialty::ClinicalPharmacology => "Farmacología Clínica".to_string(),
-            FhirSpecialty::ClinicalPhysiology => "Fisiología Clínica".to_string(),
-            FhirSpecialty::CommunityMedicine => "Medicina Comunitaria".to_string(),
-            FhirSpecialty::CriticalCareMedicine => "Medicina de Cuidados Críticos".to_string(),
-            FhirSpecialty::DentalMedicineSpecialties => "Especialidades en Medicina Dental".to_string(),
-            FhirSpecialty::DentalGeneralDentalPractice => "Práctica Dental General".to_string(),
-            FhirSpecialty::Dermatology => "Dermatología".to_string(),
-            FhirSpecialty::DiabeticMedicine => "Medicina Diabética".to_string(),
-            FhirSpecialty::DiveMedicine => "Medicina de Buceo".to_string(),
-            FhirSpecialty::Endocrinology => "Endocrinología".to_string(),
-            FhirSpecialty::FamilyPractice => "Medicina Familiar".to_string(),
-            FhirSpecialty::Gastroenterology => "Gastroenterología".to_string(),
-            FhirSpecialty::GeneralMedicalPractice => "Práctica Médica General".to_string(),
-            FhirSpecialty::GeneralMedicine => "Medicina General".to_string(),
-            FhirSpecialty::GeneralPathology => "Patología General".to_string(),
-            FhirSpecialty::GeneralPractice => "Práctica General".to_string(),
-            FhirSpecialty::GenitoUrinaryMedicine => "Medicina Genito-Urinaria".to_string(),
-            FhirSpecialty::GeriatricMedicine => "Medicina Geriátrica".to_string(),
-            FhirSpecialty::GynecologicalOncology => "Oncología Ginecológica".to_string(),
-            FhirSpecialty::Gynecology => "Ginecología".to_string(),
-            FhirSpecialty::Hematopathology => "Hematopatología".to_string(),
-            FhirSpecialty::Hepatology => "Hepatología".to_string(),
-            FhirSpecialty::Histopathology => "Histopatología".to_string(),
-            FhirSpecialty::Immunopathology => "Inmunopatología".to_string(),
-            FhirSpecialty::InfectiousDiseases => "Enfermedades Infecciosas".to_string(),
-            FhirSpecialty::InternalMedicine => "Medicina Interna".to_string(),
-            FhirSpecialty::LearningDisability => "Discapacidad de Aprendizaje".to_string(),
-            FhirSpecialty::MedicalOncology => "Oncología Médica".to_string(),
-            FhirSpecialty::MedicalOphthalmology => "Oftalmología Médica".to_string(),
-            FhirSpecialty::MilitaryMedicine => "Medicina Militar".to_string(),
-            FhirSpecialty::Nephrology => "Nefrología".to_string(),
-            FhirSpecialty::Neurology => "Neurología".to_string(),
-            FhirSpecialty::Neuropathology => "Neuropatología".to_string(),
-            FhirSpecialty::NuclearMedicine => "Medicina Nuclear".to_string(),
-            FhirSpecialty::Obstetrics => "Obstetricia".to_string(),
-            FhirSpecialty::ObstetricsAndGynecology => "Obstetricia y Ginecología".to_string(),
-            FhirSpecialty::OccupationalMedicine => "Medicina Ocupacional".to_string(),
-            FhirSpecialty::OphthalmicSurgery => "Cirugía Oftalmológica".to_string(),
-            FhirSpecialty::Ophthalmology => "Oftalmología".to_string(),
-            FhirSpecialty::OsteopathicManipulativeMedicine => "Medicina Osteopática Manipulativa".to_string(),
-            FhirSpecialty::Otolaryngology => "Otorrinolaringología".to_string(),
-            FhirSpecialty::PainManagement => "Algología".to_string(),
-            FhirSpecialty::PalliativeMedicine => "Medicina Paliativa".to_string(),
-            FhirSpecialty::PediatricGeneral => "Pediatría General".to_string(),
-            FhirSpecialty::PediatricPsychiatry => "Psiquiatría Pediátrica".to_string(),
-            FhirSpecialty::PediatricCardiology => "Cardiología Pediátrica".to_string(),
-            FhirSpecialty::PediatricDentistry => "Odontología Pediátrica".to_string(),
-            FhirSpecialty::PediatricEndocrinology => "Endocrinología Pediátrica".to_string(),
-            FhirSpecialty::PediatricGastroenterology => "Gastroenterología Pediátrica".to_string(),
-            FhirSpecialty::PediatricGenetics => "Genética Pediátrica".to_string(),
-            FhirSpecialty::PediatricHematology => "Hematología Pediátrica".to_string(),
-            FhirSpecialty::PediatricImmunology => "Inmunología Pediátrica".to_string(),
-            FhirSpecialty::PediatricInfectiousDiseases => "Enfermedades Infecciosas Pediátricas".to_string(),
-            FhirSpecialty::PediatricNephrology => "Nefrología Pediátrica".to_string(),
-            FhirSpecialty::PediatricOncology => "Oncología Pediátrica".to_string(),
-            FhirSpecialty::PediatricOphthalmology => "Oftalmología Pediátrica".to_string(),
-            FhirSpecialty::PediatricPulmonology => "Neumología Pediátrica".to_string(),
-            FhirSpecialty::PediatricRheumatology => "Reumatología Pediátrica".to_string(),
-            FhirSpecialty::PediatricSurgery => "Cirugía Pediátrica".to_string(),
-            FhirSpecialty::PediatricSurgeryBoneMarrowTransplantation => "Cirugía Pediátrica de Trasplante de Médula Ósea".to_string(),
-            FhirSpecialty::PreventiveMedicine => "Medicina Preventiva".to_string(),
-            FhirSpecialty::Psychiatry => "Psiquiatría".to_string(),
-            FhirSpecialty::Psychotherapy => "Psicología".to_string(),
-            FhirSpecialty::PublicHealthMedicine => "Medicina de Salud Pública".to_string(),
-            FhirSpecialty::PulmonaryMedicine => "Neumología".to_string(),
-            FhirSpecialty::RadiationOncology => "Oncología Radioterápica".to_string(),
-            FhirSpecialty::Radiology => "Radiología".to_string(),
-            FhirSpecialty::InterventionalRadiology => "Radiología Intervencionista".to_string(),
-            FhirSpecialty::Rehabilitation => "Rehabilitación".to_string(),
-            FhirSpecialty::RespiteCare => "Cuidado de Respiro".to_string(),
-            FhirSpecialty::Rheumatology => "Reumatología".to_string(),
-            FhirSpecialty::SleepStudies => "Estudios del Sueño".to_string(),
-            FhirSpecialty::SurgeryPreSurgeryConsultation => "Consulta Prequirúrgica".to_string(),
-            FhirSpecialty::SurgeryBoneAndMarrowTransplantation => "Cirugía de Trasplante de Hueso y Médula".to_string(),
-            FhirSpecialty::SurgeryBreastSurgery => "Cirugía de Mama".to_string(),
-            FhirSpecialty::SurgeryCardiacSurgery => "Cirugía Cardíaca".to_string(),
-            FhirSpecialty::SurgeryCardiothoracicTransplantation => "Trasplante Cardio-Torácico".to_string(),
-            FhirSpecialty::SurgeryColorectalSurgery => "Cirugía Colorrectal".to_string(),
-            FhirSpecialty::SurgeryDentalEndodontics => "Endodoncia Dental".to_string(),
-            FhirSpecialty::SurgeryDentalOralAndMaxillofacialSurgery => "Cirugía Oral y Maxilofacial Dental".to_string(),
-            FhirSpecialty::SurgeryDentalOralSurgery => "Cirugía Oral Dental".to_string(),
-            FhirSpecialty::SurgeryDentalOrthodontics => "Ortodoncia Dental".to_string(),
-            FhirSpecialty::SurgeryDentalPeriodontalSurgery => "Cirugía Periodontal Dental".to_string(),
-            FhirSpecialty::SurgeryDentalProstheticDentistry => "Prótesis Dental".to_string(),
-            FhirSpecialty::SurgeryDentistryRestorativeDentistry => "Odontología Restauradora".to_string(),
-            FhirSpecialty::SurgeryDentistrySurgical => "Cirugía Dental".to_string(),
-            FhirSpecialty::SurgeryDermatologicSurgery => "Cirugía Dermatológica".to_string(),
-            FhirSpecialty::SurgeryEarNoseAndThroatSurgery => "Cirugía de Oído, Nariz y Garganta".to_string(),
-            FhirSpecialty::SurgeryGeneral => "Cirugía General".to_string(),
-            FhirSpecialty::SurgeryHepatobiliaryAndPancreaticSurgery => "Cirugía Hepatobiliar y Pancreática".to_string(),
-            FhirSpecialty::SurgeryNeurosurgery => "Neurocirugía".to_string(),
-            FhirSpecialty::SurgeryPlasticSurgery => "Cirugía Plástica".to_string(),
-            FhirSpecialty::SurgeryTransplantationSurgery => "Cirugía de Trasplante".to_string(),
-            FhirSpecialty::SurgeryTraumaAndOrthopedics => "Ortopedia".to_string(),
-            FhirSpecialty::SurgeryVascular => "Cirugía Vascular".to_string(),
-            FhirSpecialty::Urology => "Urología".to_string(),
-            FhirSpecialty::MedicalSpecialtyOtherNotListed => "Otra Especialidad Médica No Listada".to_string(),
-            FhirSpecialty::SurgicalSpecialtyOtherNotListed => "Otra Especialidad Quirúrgica No Listada".to_string(),
-            FhirSpecialty::ThoracicMedicine => "Medicina Torácica".to_string(),
-            FhirSpecialty::Toxicology => "Toxicología".to_string(),
-            FhirSpecialty::TropicalMedicine => "Medicina Tropical".to_string(),
-            FhirSpecialty::UrologicalOncology => "Oncología Urológica".to_string(),
-            FhirSpecialty::PerinatalMedicine => "Perinatología".to_string(),
-            FhirSpecialty::SurgicalOncology => "Cirugia Oncología".to_string(),
-            FhirSpecialty::SurgicalAccidentAndEmergency => "Cirugia de Emergencia".to_string(),
-            FhirSpecialty::GynecologyMastology => "Ginecología Mastología".to_string(),
-            FhirSpecialty::SurgeryBariatric => "Cirugía Bariátrica".to_string(),
-            FhirSpecialty::SurgeryEndoscopic => "Cirugia Endoscópica".to_string(),
+            FhirSpecialty::PerinatalMedicine => FhirSpecialty::Obstetrics,
+            FhirSpecialty::SurgeryBariatric => FhirSpecialty::SurgeryGeneral,
+            FhirSpecialty::SurgeryEndoscopic => FhirSpecialty::SurgeryGeneral,
+            FhirSpecialty::GynecologyMastology => FhirSpecialty::SurgeryBreastSurgery,
+            _ => *self,
         }
     }
     // Method to get the code associated with the enum variant
@@ -383,3 +489,91 @@ impl FhirSpecialty {
     }
 }
 
+
+impl FhirCodedValue for FhirSpecialty {
+    fn fhir_system(&self) -> &str {
+        self.system()
+    }
+    fn fhir_code(&self) -> String {
+        self.code_string()
+    }
+    fn fhir_display(&self) -> &str {
+        self.display()
+    }
+    fn from_fhir_code(code: &str) -> Option<Self> {
+        Self::try_from_code(code.parse::<u32>().ok()?).ok()
+    }
+    fn all() -> Vec<Self> {
+        Self::ALL.to_vec()
+    }
+}
+
+impl TryFrom<u32> for FhirSpecialty {
+    type Error = String;
+    fn try_from(code: u32) -> Result<Self, Self::Error> {
+        Self::try_from_code(code)
+    }
+}
+
+fn normalize(text: &str) -> String {
+    let folded: String = text
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'ä' | 'â' | 'ã' => 'a',
+            'é' | 'è' | 'ë' | 'ê' => 'e',
+            'í' | 'ì' | 'ï' | 'î' => 'i',
+            'ó' | 'ò' | 'ö' | 'ô' | 'õ' => 'o',
+            'ú' | 'ù' | 'ü' | 'û' => 'u',
+            'ñ' => 'n',
+            'ç' => 'c',
+            c if c.is_alphanumeric() => c,
+            _ => ' ',
+        })
+        .collect();
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn search_rank(name: &str, query: &str) -> Option<usize> {
+    if name == query {
+        return Some(0);
+    }
+    if name.starts_with(query) {
+        return Some(1);
+    }
+    if name.split(' ').any(|word| word.starts_with(query)) {
+        return Some(2);
+    }
+    if name.contains(query) {
+        return Some(3);
+    }
+    let query_words: Vec<&str> = query.split(' ').collect();
+    let fuzzy = query_words.iter().all(|query_word| {
+        // Any word is one edit away from a one or two letter prefix, so short
+        // query words must match exactly
+        let tolerance = match query_word.chars().count() {
+            0..=2 => 0,
+            3..=6 => 1,
+            _ => 2,
+        };
+        name.split(' ').any(|word| {
+            let prefix: String = word.chars().take(query_word.chars().count()).collect();
+            edit_distance(&prefix, query_word) <= tolerance
+        })
+    });
+    fuzzy.then_some(4)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
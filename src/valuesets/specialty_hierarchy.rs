@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+
+use super::FhirSpecialty;
+use crate::locale::FhirLocalization;
+
+// Parent/child relationships between specialties. SNOMED CT subsumption is
+// followed where both concepts are in the value set; 394732004 and 394733009
+// are the SNOMED "Surgical specialty" and "Medical specialty" concepts that
+// the value set labels as OTHER--NOT LISTED. The 9999999xx codes are attached
+// to their closest specialty as a local grouping.
+impl FhirSpecialty {
+    pub fn parent(&self) -> Option<FhirSpecialty> {
+        use FhirSpecialty::*;
+        match self {
+            MedicalSpecialtyOtherNotListed
+            | SurgicalSpecialtyOtherNotListed
+            | DentalMedicineSpecialties
+            | PediatricGeneral
+            | GeneralPractice
+            | ObstetricsAndGynecology => None,
+            // Surgery
+            SurgeryGeneral
+            | SurgeryPreSurgeryConsultation
+            | SurgeryBoneAndMarrowTransplantation
+            | SurgeryBreastSurgery
+            | SurgeryCardiacSurgery
+            | SurgeryCardiothoracicTransplantation
+            | SurgeryColorectalSurgery
+            | SurgeryDermatologicSurgery
+            | SurgeryEarNoseAndThroatSurgery
+            | SurgeryHepatobiliaryAndPancreaticSurgery
+            | SurgeryNeurosurgery
+            | SurgeryPlasticSurgery
+            | SurgeryTransplantationSurgery
+            | SurgeryTraumaAndOrthopedics
+            | SurgeryVascular
+            | SurgicalOncology
+            | SurgicalAccidentAndEmergency
+            | OphthalmicSurgery
+            | Urology
+            | BurnsCare => Some(SurgicalSpecialtyOtherNotListed),
+            SurgeryBariatric | SurgeryEndoscopic => Some(SurgeryGeneral),
+            // Dentistry
+            DentalGeneralDentalPractice | PediatricDentistry | SurgeryDentistrySurgical => {
+                Some(DentalMedicineSpecialties)
+            }
+            SurgeryDentalEndodontics
+            | SurgeryDentalOralAndMaxillofacialSurgery
+            | SurgeryDentalOralSurgery
+            | SurgeryDentalOrthodontics
+            | SurgeryDentalPeriodontalSurgery
+            | SurgeryDentalProstheticDentistry
+            | SurgeryDentistryRestorativeDentistry => Some(SurgeryDentistrySurgical),
+            // Pediatrics
+            PediatricPsychiatry
+            | PediatricCardiology
+            | PediatricEndocrinology
+            | PediatricGastroenterology
+            | PediatricGenetics
+            | PediatricHematology
+            | PediatricImmunology
+            | PediatricInfectiousDiseases
+            | PediatricNephrology
+            | PediatricOncology
+            | PediatricOphthalmology
+            | PediatricPulmonology
+            | PediatricRheumatology
+            | PediatricSurgery => Some(PediatricGeneral),
+            PediatricSurgeryBoneMarrowTransplantation => Some(PediatricSurgery),
+            // Obstetrics and gynecology
+            Obstetrics | Gynecology => Some(ObstetricsAndGynecology),
+            PerinatalMedicine => Some(Obstetrics),
+            GynecologyMastology | GynecologicalOncology => Some(Gynecology),
+            // Medicine
+            MedicalOncology | RadiationOncology => Some(ClinicalOncology),
+            UrologicalOncology => Some(Urology),
+            Hematopathology | Histopathology | Immunopathology | Neuropathology => {
+                Some(GeneralPathology)
+            }
+            InterventionalRadiology | NuclearMedicine => Some(Radiology),
+            MedicalOphthalmology => Some(Ophthalmology),
+            AdultMentalIllness | Psychotherapy | LearningDisability => Some(Psychiatry),
+            ClinicalCytogeneticsAndMolecularGenetics => Some(ClinicalGenetics),
+            DiabeticMedicine => Some(Endocrinology),
+            Hepatology => Some(Gastroenterology),
+            FamilyPractice | GeneralMedicalPractice => Some(GeneralPractice),
+            _ => Some(MedicalSpecialtyOtherNotListed),
+        }
+    }
+    pub fn children(&self) -> Vec<FhirSpecialty> {
+        Self::iter()
+            .filter(|specialty| specialty.parent() == Some(*self))
+            .collect()
+    }
+    pub fn ancestors(&self) -> Vec<FhirSpecialty> {
+        let mut ancestors = vec![];
+        let mut current = self.parent();
+        while let Some(parent) = current {
+            ancestors.push(parent);
+            current = parent.parent();
+        }
+        ancestors
+    }
+    pub fn descendants(&self) -> Vec<FhirSpecialty> {
+        Self::iter()
+            .filter(|specialty| specialty.ancestors().contains(self))
+            .collect()
+    }
+    // Subsumption test: true if self is `other` or one of its descendants
+    pub fn is_a(&self, other: FhirSpecialty) -> bool {
+        *self == other || self.ancestors().contains(&other)
+    }
+    pub fn in_group(&self, group: FhirSpecialtyGroup) -> bool {
+        use FhirSpecialty::*;
+        match group {
+            FhirSpecialtyGroup::Surgery => {
+                self.is_a(SurgicalSpecialtyOtherNotListed)
+                    || self.is_a(PediatricSurgery)
+                    || self.is_a(SurgeryDentistrySurgical)
+            }
+            FhirSpecialtyGroup::Oncology => matches!(
+                self,
+                ClinicalOncology
+                    | MedicalOncology
+                    | RadiationOncology
+                    | SurgicalOncology
+                    | UrologicalOncology
+                    | GynecologicalOncology
+                    | PediatricOncology
+            ),
+            FhirSpecialtyGroup::Pediatrics => self.is_a(PediatricGeneral),
+            FhirSpecialtyGroup::Dentistry => self.is_a(DentalMedicineSpecialties),
+            FhirSpecialtyGroup::WomensHealth => {
+                self.is_a(ObstetricsAndGynecology) || *self == SurgeryBreastSurgery
+            }
+            FhirSpecialtyGroup::Pathology => {
+                self.is_a(GeneralPathology) || *self == ClinicalMicrobiology
+            }
+            FhirSpecialtyGroup::Imaging => self.is_a(Radiology),
+            FhirSpecialtyGroup::MentalHealth => {
+                self.is_a(Psychiatry) || *self == PediatricPsychiatry
+            }
+            FhirSpecialtyGroup::PrimaryCare => {
+                self.is_a(GeneralPractice)
+                    || matches!(self, GeneralMedicine | InternalMedicine | PreventiveMedicine)
+            }
+        }
+    }
+    pub fn groups(&self) -> Vec<FhirSpecialtyGroup> {
+        FhirSpecialtyGroup::ALL
+            .into_iter()
+            .filter(|group| self.in_group(*group))
+            .collect()
+    }
+}
+
+// Cross-cutting groupings for directory listings, where a specialty can appear
+// under more than one heading (Surgical oncology is both Surgery and Oncology).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FhirSpecialtyGroup {
+    Surgery,
+    Oncology,
+    Pediatrics,
+    Dentistry,
+    WomensHealth,
+    Pathology,
+    Imaging,
+    MentalHealth,
+    PrimaryCare,
+}
+impl FhirSpecialtyGroup {
+    pub const ALL: [FhirSpecialtyGroup; 9] = [
+        FhirSpecialtyGroup::Surgery,
+        FhirSpecialtyGroup::Oncology,
+        FhirSpecialtyGroup::Pediatrics,
+        FhirSpecialtyGroup::Dentistry,
+        FhirSpecialtyGroup::WomensHealth,
+        FhirSpecialtyGroup::Pathology,
+        FhirSpecialtyGroup::Imaging,
+        FhirSpecialtyGroup::MentalHealth,
+        FhirSpecialtyGroup::PrimaryCare,
+    ];
+    pub fn members(&self) -> Vec<FhirSpecialty> {
+        FhirSpecialty::iter()
+            .filter(|specialty| specialty.in_group(*self))
+            .collect()
+    }
+    pub fn to_str(&self) -> &str {
+        match self {
+            FhirSpecialtyGroup::Surgery => "Surgery",
+            FhirSpecialtyGroup::Oncology => "Oncology",
+            FhirSpecialtyGroup::Pediatrics => "Pediatrics",
+            FhirSpecialtyGroup::Dentistry => "Dentistry",
+            FhirSpecialtyGroup::WomensHealth => "Women's Health",
+            FhirSpecialtyGroup::Pathology => "Pathology",
+            FhirSpecialtyGroup::Imaging => "Imaging",
+            FhirSpecialtyGroup::MentalHealth => "Mental Health",
+            FhirSpecialtyGroup::PrimaryCare => "Primary Care",
+        }
+    }
+    // The group name in `language` from the bundled catalogs, else in English
+    pub fn display_in(&self, language: &str) -> &str {
+        FhirLocalization::defaults().label(self.to_str(), language)
+    }
+}
@@ -0,0 +1,198 @@
+use serde::{
+    de::{
+        value::BorrowedStrDeserializer, DeserializeOwned, DeserializeSeed, Error as _,
+        IntoDeserializer, MapAccess, SeqAccess, Visitor,
+    },
+    forward_to_deserialize_any, Deserializer,
+};
+use serde_json::{map::Iter, Error, Value};
+
+// Deserializes FHIR JSON read from XML without a StructureDefinition. The
+// target type decides what the document could not: strings are parsed when
+// a number or boolean is expected, a single element is read as a list of one,
+// and a list of one as a single element. Lists left out for being empty read
+// as empty.
+pub(crate) fn from_json<T: DeserializeOwned>(json: &Value) -> Result<T, String> {
+    T::deserialize(Lenient(json)).map_err(|e| e.to_string())
+}
+
+struct Lenient<'a>(&'a Value);
+
+static NULL: Value = Value::Null;
+
+macro_rules! lenient_parse {
+    ($($method:ident => $visit:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                match self.0 {
+                    Value::String(text) => {
+                        visitor.$visit(text.trim().parse::<$ty>().map_err(Error::custom)?)
+                    }
+                    value => value.$method(visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for Lenient<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Object(map) => visitor.visit_map(LenientMap {
+                entries: map.iter(),
+                missing: vec![],
+                value: None,
+            }),
+            Value::Array(items) => visitor.visit_seq(LenientSeq(items.iter())),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    lenient_parse!(
+        deserialize_bool => visit_bool(bool),
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_f32 => visit_f32(f32),
+        deserialize_f64 => visit_f64(f64),
+    );
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    // Empty strings are left out of FHIR documents
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Null => visitor.visit_borrowed_str(""),
+            value => value.deserialize_str(visitor),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Array(items) => visitor.visit_seq(LenientSeq(items.iter())),
+            Value::Null => visitor.visit_seq(LenientSeq([].iter())),
+            value => visitor.visit_seq(LenientSeq(std::slice::from_ref(value).iter())),
+        }
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Array(items) if items.len() == 1 => Lenient(&items[0]).deserialize_map(visitor),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    // Fields missing from the document are given as null
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Array(items) if items.len() == 1 => {
+                Lenient(&items[0]).deserialize_struct(name, fields, visitor)
+            }
+            Value::Object(map) => visitor.visit_map(LenientMap {
+                entries: map.iter(),
+                missing: fields
+                    .iter()
+                    .filter(|field| !map.contains_key(**field))
+                    .copied()
+                    .collect(),
+                value: None,
+            }),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::String(text) => visitor.visit_enum(text.as_str().into_deserializer()),
+            value => value.deserialize_enum(name, variants, visitor),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        char bytes byte_buf unit unit_struct tuple tuple_struct identifier ignored_any
+    }
+}
+
+struct LenientSeq<'a>(std::slice::Iter<'a, Value>);
+impl<'de> SeqAccess<'de> for LenientSeq<'de> {
+    type Error = Error;
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        self.0
+            .next()
+            .map(|item| seed.deserialize(Lenient(item)))
+            .transpose()
+    }
+}
+
+struct LenientMap<'a> {
+    entries: Iter<'a>,
+    missing: Vec<&'static str>,
+    value: Option<&'a Value>,
+}
+impl<'de> MapAccess<'de> for LenientMap<'de> {
+    type Error = Error;
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(BorrowedStrDeserializer::new(key))
+                    .map(Some)
+            }
+            None => match self.missing.pop() {
+                Some(field) => {
+                    self.value = Some(&NULL);
+                    seed.deserialize(BorrowedStrDeserializer::new(field))
+                        .map(Some)
+                }
+                None => Ok(None),
+            },
+        }
+    }
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| Error::custom("value without a key"))?;
+        seed.deserialize(Lenient(value))
+    }
+}
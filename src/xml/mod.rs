@@ -0,0 +1,202 @@
+mod lenient;
+mod reader;
+pub use reader::{xml_to_json, xml_to_json_with};
+
+use quick_xml::escape::escape;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+pub(crate) use lenient::from_json;
+
+// https://www.hl7.org/fhir/xml.html
+pub const FHIR_XML_NAMESPACE: &str = "http://hl7.org/fhir";
+pub const XHTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
+
+// Elements every resource starts with, ahead of its extensions
+const RESOURCE_ELEMENTS: [&str; 6] = [
+    "id",
+    "meta",
+    "implicitRules",
+    "language",
+    "text",
+    "contained",
+];
+// Extensions come before any other child element
+const EXTENSION_ELEMENTS: [&str; 2] = ["extension", "modifierExtension"];
+
+// Writes FHIR JSON such as `IgnisResource::get_fhir_json` as FHIR XML. The
+// root element is named after `resourceType`; elements keep the order of the
+// JSON object, which for this crate's resources is the FHIR definition order.
+pub fn json_to_xml(json: &Value) -> Result<String, String> {
+    let resource_type = json
+        .get("resourceType")
+        .and_then(Value::as_str)
+        .ok_or("FHIR JSON has no resourceType")?;
+    let Value::Object(map) = json else {
+        return Err("FHIR JSON must be an object".to_string());
+    };
+    let mut xml = String::new();
+    write_object(&mut xml, resource_type, map, 0, true)?;
+    Ok(xml)
+}
+
+// Writes a datatype, or any other serializable element, as `<element>`
+pub fn to_fhir_xml<T: Serialize>(element: &str, value: &T) -> Result<String, String> {
+    let json = serde_json::to_value(value).map_err(|e| e.to_string())?;
+    let mut xml = String::new();
+    match &json {
+        Value::Object(map) => write_object(&mut xml, element, map, 0, true)?,
+        _ => write_element(&mut xml, element, &json, None, 0)?,
+    }
+    Ok(xml)
+}
+
+// Reads a datatype or resource from FHIR XML. The root element name is not
+// checked. Hand-written types have no schema to tell a repeated element from
+// a single one, or a number from a string, so both are accepted either way.
+pub fn from_fhir_xml<T: DeserializeOwned>(xml: &str) -> Result<T, String> {
+    from_json(&xml_to_json(xml)?)
+}
+
+fn indent(xml: &mut String, depth: usize) {
+    xml.push_str(&"  ".repeat(depth));
+}
+
+fn write_object(
+    xml: &mut String,
+    name: &str,
+    map: &Map<String, Value>,
+    depth: usize,
+    root: bool,
+) -> Result<(), String> {
+    let resource = map.contains_key("resourceType");
+    indent(xml, depth);
+    xml.push('<');
+    xml.push_str(name);
+    if root {
+        xml.push_str(&format!(" xmlns=\"{}\"", FHIR_XML_NAMESPACE));
+    }
+    // Element ids and extension urls are attributes; a resource id and the url
+    // of any other element, such as an Attachment, are elements
+    let attributes: &[&str] = match resource {
+        true => &[],
+        false if EXTENSION_ELEMENTS.contains(&name) => &["id", "url"],
+        false => &["id"],
+    };
+    for attribute in attributes {
+        if let Some(Value::String(value)) = map.get(*attribute) {
+            xml.push_str(&format!(" {}=\"{}\"", attribute, escape(value.as_str())));
+        }
+    }
+    let leading: &[&str] = match resource {
+        true => &RESOURCE_ELEMENTS,
+        false => &[],
+    };
+    let mut children: Vec<&str> = vec![];
+    for key in leading.iter().chain(EXTENSION_ELEMENTS.iter()) {
+        if map.contains_key(*key) || map.contains_key(&format!("_{}", key)) {
+            children.push(key);
+        }
+    }
+    for key in map.keys() {
+        let key = key.strip_prefix('_').unwrap_or(key);
+        if key != "resourceType" && !attributes.contains(&key) && !children.contains(&key) {
+            children.push(key);
+        }
+    }
+    let mut content = String::new();
+    for child in children {
+        let extras = map.get(&format!("_{}", child));
+        let value = map.get(child).unwrap_or(&Value::Null);
+        write_element(&mut content, child, value, extras, depth + 1)?;
+    }
+    match content.is_empty() {
+        true => xml.push_str("/>\n"),
+        false => {
+            xml.push_str(">\n");
+            xml.push_str(&content);
+            indent(xml, depth);
+            xml.push_str(&format!("</{}>\n", name));
+        }
+    }
+    Ok(())
+}
+
+// `extras` is the `_element` entry FHIR JSON uses for the id and extensions
+// of a primitive
+fn write_element(
+    xml: &mut String,
+    name: &str,
+    value: &Value,
+    extras: Option<&Value>,
+    depth: usize,
+) -> Result<(), String> {
+    match value {
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                let extras = extras.and_then(|extras| extras.get(index));
+                write_element(xml, name, item, extras, depth)?;
+            }
+        }
+        // Contained resources are wrapped in an element named after their type
+        Value::Object(map) if map.contains_key("resourceType") => {
+            let resource_type = map
+                .get("resourceType")
+                .and_then(Value::as_str)
+                .ok_or("resourceType must be a string")?;
+            indent(xml, depth);
+            xml.push_str(&format!("<{}>\n", name));
+            write_object(xml, resource_type, map, depth + 1, false)?;
+            indent(xml, depth);
+            xml.push_str(&format!("</{}>\n", name));
+        }
+        Value::Object(map) => write_object(xml, name, map, depth, false)?,
+        // The narrative is XHTML and goes in as markup
+        Value::String(div) if name == "div" => {
+            indent(xml, depth);
+            match div.contains("xmlns") {
+                true => xml.push_str(div),
+                false => xml.push_str(&div.replacen(
+                    "<div",
+                    &format!("<div xmlns=\"{}\"", XHTML_NAMESPACE),
+                    1,
+                )),
+            }
+            xml.push('\n');
+        }
+        Value::Null | Value::String(_) | Value::Number(_) | Value::Bool(_) => {
+            let primitive = match value {
+                Value::Null => None,
+                Value::String(text) => Some(text.clone()),
+                value => Some(value.to_string()),
+            };
+            let extras = extras.and_then(Value::as_object);
+            if primitive.is_none() && extras.is_none() {
+                return Ok(());
+            }
+            indent(xml, depth);
+            xml.push('<');
+            xml.push_str(name);
+            if let Some(Value::String(id)) = extras.and_then(|extras| extras.get("id")) {
+                xml.push_str(&format!(" id=\"{}\"", escape(id.as_str())));
+            }
+            if let Some(primitive) = primitive {
+                xml.push_str(&format!(" value=\"{}\"", escape(primitive.as_str())));
+            }
+            let mut content = String::new();
+            if let Some(extension) = extras.and_then(|extras| extras.get("extension")) {
+                write_element(&mut content, "extension", extension, None, depth + 1)?;
+            }
+            match content.is_empty() {
+                true => xml.push_str("/>\n"),
+                false => {
+                    xml.push_str(">\n");
+                    xml.push_str(&content);
+                    indent(xml, depth);
+                    xml.push_str(&format!("</{}>\n", name));
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,328 @@
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+use serde_json::{Map, Number, Value};
+
+use crate::resources::FhirElementDefinition;
+
+struct XmlElement {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<XmlElement>,
+    // Markup of an XHTML narrative `div`, kept as written
+    xhtml: Option<String>,
+}
+impl XmlElement {
+    fn new(start: &BytesStart) -> Result<Self, String> {
+        let mut attributes = vec![];
+        for attribute in start.attributes() {
+            let attribute = attribute.map_err(|e| e.to_string())?;
+            let key = attribute.key.as_ref();
+            if key == b"xmlns" || key.starts_with(b"xmlns:") {
+                continue;
+            }
+            attributes.push((
+                String::from_utf8_lossy(attribute.key.local_name().as_ref()).to_string(),
+                attribute
+                    .unescape_value()
+                    .map_err(|e| e.to_string())?
+                    .to_string(),
+            ));
+        }
+        Ok(Self {
+            name: String::from_utf8_lossy(start.local_name().as_ref()).to_string(),
+            attributes,
+            children: vec![],
+            xhtml: None,
+        })
+    }
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn parse(xml: &str) -> Result<XmlElement, String> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+    let mut open: Vec<XmlElement> = vec![];
+    loop {
+        let position = reader.buffer_position() as usize;
+        let (element, closed) = match reader.read_event().map_err(|e| e.to_string())? {
+            Event::Start(start) if start.local_name().as_ref() == b"div" => {
+                let mut element = XmlElement::new(&start)?;
+                reader
+                    .read_to_end(start.name())
+                    .map_err(|e| e.to_string())?;
+                let end = reader.buffer_position() as usize;
+                element.xhtml = Some(xml[position..end].trim().to_string());
+                (element, true)
+            }
+            Event::Empty(start) if start.local_name().as_ref() == b"div" => {
+                let mut element = XmlElement::new(&start)?;
+                let end = reader.buffer_position() as usize;
+                element.xhtml = Some(xml[position..end].trim().to_string());
+                (element, true)
+            }
+            Event::Start(start) => (XmlElement::new(&start)?, false),
+            Event::Empty(start) => (XmlElement::new(&start)?, true),
+            Event::End(_) => (open.pop().ok_or("Unexpected closing tag")?, true),
+            Event::Eof => return Err("Unexpected end of XML".to_string()),
+            // FHIR elements carry their content in attributes and children
+            _ => continue,
+        };
+        match (closed, open.last_mut()) {
+            (false, _) => open.push(element),
+            (true, Some(parent)) => parent.children.push(element),
+            (true, None) => return Ok(element),
+        }
+    }
+}
+
+// Elements that repeat wherever they appear, written as arrays even when
+// there is only one
+const REPEATING: [&str; 10] = [
+    "extension",
+    "modifierExtension",
+    "identifier",
+    "coding",
+    "given",
+    "prefix",
+    "suffix",
+    "line",
+    "telecom",
+    "contained",
+];
+
+// What is known about an element while reading it
+#[derive(Clone, Copy)]
+enum Context<'a> {
+    // Path in the resource's StructureDefinition
+    Path(&'a str),
+    // FHIR datatype
+    Type(&'a str),
+    Unknown,
+}
+
+// Children of datatypes that are not strings in JSON, or lead to one
+fn datatype_element(datatype: &str, name: &str) -> Option<&'static str> {
+    match (datatype, name) {
+        (_, "extension" | "modifierExtension") => Some("Extension"),
+        ("Quantity" | "SimpleQuantity" | "Age" | "Count" | "Distance" | "Duration", "value") => {
+            Some("decimal")
+        }
+        ("Money", "value") => Some("decimal"),
+        ("Coding", "userSelected") => Some("boolean"),
+        ("ContactPoint", "rank") => Some("positiveInt"),
+        ("CodeableConcept", "coding") => Some("Coding"),
+        ("Identifier", "type") => Some("CodeableConcept"),
+        ("Range", "low" | "high") => Some("Quantity"),
+        ("Ratio", "numerator" | "denominator") => Some("Quantity"),
+        _ => None,
+    }
+}
+
+// The type of a choice element such as valueBoolean, for the types that matter
+fn choice_type(name: &str) -> Option<&'static str> {
+    [
+        ("Boolean", "boolean"),
+        ("Integer", "integer"),
+        ("PositiveInt", "positiveInt"),
+        ("UnsignedInt", "unsignedInt"),
+        ("Decimal", "decimal"),
+        ("CodeableConcept", "CodeableConcept"),
+        ("Coding", "Coding"),
+        ("ContactPoint", "ContactPoint"),
+        ("Quantity", "Quantity"),
+        ("Duration", "Duration"),
+        ("Range", "Range"),
+        ("Ratio", "Ratio"),
+        ("Money", "Money"),
+        ("Age", "Age"),
+    ]
+    .into_iter()
+    .find(|(suffix, _)| {
+        name.strip_suffix(suffix)
+            .is_some_and(|base| base.ends_with(|c: char| c.is_ascii_lowercase()))
+    })
+    .map(|(_, fhir_type)| fhir_type)
+}
+
+// Context of a child element and, when the definition says, whether it repeats
+fn child_context<'a>(
+    elements: &'a [FhirElementDefinition],
+    parent: Context<'a>,
+    name: &str,
+) -> (Context<'a>, Option<bool>) {
+    if let Context::Path(path) = parent {
+        let found = elements.iter().find_map(|element| {
+            let element_name = element.path.strip_prefix(path)?.strip_prefix('.')?;
+            if element_name == name {
+                return Some((element, element.types.first().copied()));
+            }
+            // value[x] is written as valueQuantity, valueString...
+            let suffix = name.strip_prefix(element_name.strip_suffix("[x]")?)?;
+            element
+                .types
+                .iter()
+                .find(|fhir_type| fhir_type.eq_ignore_ascii_case(suffix))
+                .map(|fhir_type| (element, Some(*fhir_type)))
+        });
+        if let Some((element, fhir_type)) = found {
+            let context = match fhir_type {
+                Some(reference) if reference.starts_with('#') => Context::Path(&reference[1..]),
+                Some("BackboneElement" | "Element") | None => Context::Path(element.path),
+                Some(fhir_type) => Context::Type(fhir_type),
+            };
+            return (context, Some(element.max != Some(1)));
+        }
+    }
+    let fhir_type = match parent {
+        Context::Type(datatype) => datatype_element(datatype, name),
+        _ => None,
+    }
+    .or_else(|| datatype_element("", name))
+    .or_else(|| choice_type(name));
+    (fhir_type.map_or(Context::Unknown, Context::Type), None)
+}
+
+fn primitive(value: &str, context: Context) -> Value {
+    match context {
+        Context::Type("boolean") => match value {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ => Value::String(value.to_string()),
+        },
+        Context::Type("integer" | "positiveInt" | "unsignedInt" | "decimal") => {
+            match value.parse::<Number>() {
+                Ok(number) => Value::Number(number),
+                Err(_) => Value::String(value.to_string()),
+            }
+        }
+        _ => Value::String(value.to_string()),
+    }
+}
+
+fn is_resource_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+fn resource_to_json(element: &XmlElement, elements: &[FhirElementDefinition]) -> Value {
+    let mut map = Map::new();
+    map.insert(
+        "resourceType".to_string(),
+        Value::String(element.name.clone()),
+    );
+    let context = match elements.is_empty() {
+        true => Context::Unknown,
+        false => Context::Path(&element.name),
+    };
+    if let Value::Object(content) = element_to_json(element, elements, context) {
+        map.extend(content);
+    }
+    Value::Object(map)
+}
+
+fn element_to_json(
+    element: &XmlElement,
+    elements: &[FhirElementDefinition],
+    context: Context,
+) -> Value {
+    if let Some(xhtml) = &element.xhtml {
+        return Value::String(xhtml.clone());
+    }
+    if let Some(value) = element.attribute("value") {
+        return primitive(value, context);
+    }
+    // <contained><Patient>...</Patient></contained>
+    if let [child] = element.children.as_slice() {
+        if element.attributes.is_empty() && is_resource_name(&child.name) {
+            return resource_to_json(child, &[]);
+        }
+    }
+    let mut map = Map::new();
+    for (name, value) in &element.attributes {
+        map.insert(name.clone(), Value::String(value.clone()));
+    }
+    let mut names: Vec<&str> = vec![];
+    for child in &element.children {
+        if !names.contains(&child.name.as_str()) {
+            names.push(&child.name);
+        }
+    }
+    for name in names {
+        let group: Vec<&XmlElement> = element
+            .children
+            .iter()
+            .filter(|child| child.name == name)
+            .collect();
+        let (child_context, repeats) = child_context(elements, context, name);
+        let repeats = repeats.unwrap_or_else(|| group.len() > 1 || REPEATING.contains(&name));
+        let mut values: Vec<Value> = group
+            .iter()
+            .map(|child| element_to_json(child, elements, child_context))
+            .collect();
+        let mut extras: Vec<Value> = group
+            .iter()
+            .map(|child| primitive_extras(child, elements))
+            .collect();
+        let value = match repeats {
+            true => Value::Array(values),
+            false => values.remove(0),
+        };
+        map.insert(name.to_string(), value);
+        if extras.iter().any(|extras| !extras.is_null()) {
+            let extras = match repeats {
+                true => Value::Array(extras),
+                false => extras.remove(0),
+            };
+            map.insert(format!("_{}", name), extras);
+        }
+    }
+    Value::Object(map)
+}
+
+// The id and extensions of a primitive go under "_element" in JSON
+fn primitive_extras(element: &XmlElement, elements: &[FhirElementDefinition]) -> Value {
+    if element.attribute("value").is_none() {
+        return Value::Null;
+    }
+    let mut extras = Map::new();
+    if let Some(id) = element.attribute("id") {
+        extras.insert("id".to_string(), Value::String(id.to_string()));
+    }
+    let extension: Vec<Value> = element
+        .children
+        .iter()
+        .filter(|child| child.name == "extension")
+        .map(|child| element_to_json(child, elements, Context::Type("Extension")))
+        .collect();
+    if !extension.is_empty() {
+        extras.insert("extension".to_string(), Value::Array(extension));
+    }
+    match extras.is_empty() {
+        true => Value::Null,
+        false => Value::Object(extras),
+    }
+}
+
+// Reads FHIR XML into FHIR JSON. A resource root becomes an object with its
+// `resourceType`; any other root element is read as a datatype. Without a
+// StructureDefinition, elements are arrays when they repeat in the document
+// and primitives are strings unless a choice type or datatype says otherwise.
+pub fn xml_to_json(xml: &str) -> Result<Value, String> {
+    xml_to_json_with(xml, &[])
+}
+
+// Same as `xml_to_json`, reading arrays and primitive types from the elements
+// of the resource's StructureDefinition
+pub fn xml_to_json_with(xml: &str, elements: &[FhirElementDefinition]) -> Result<Value, String> {
+    let root = parse(xml)?;
+    Ok(match is_resource_name(&root.name) {
+        true => resource_to_json(&root, elements),
+        false => element_to_json(&root, elements, Context::Unknown),
+    })
+}
@@ -0,0 +1,216 @@
+use ignis_nostr::{
+    datatypes::{validate_country, FhirAddress, FhirGeolocation},
+    valuesets::{FhirAddressType, FhirAddressUse},
+};
+use serde_json::json;
+
+#[test]
+fn addresses_are_written_without_unset_members() {
+    let address =
+        FhirAddress::new(FhirAddressUse::Work, FhirAddressType::Physical).with_city("Caracas");
+    assert_eq!(
+        serde_json::to_value(&address).unwrap(),
+        json!({"use": "work", "type": "physical", "city": "Caracas"})
+    );
+    let read: FhirAddress = serde_json::from_value(json!({"city": "Caracas"})).unwrap();
+    assert!(read.address_use.is_none());
+    assert_eq!(read.city.as_deref(), Some("Caracas"));
+}
+
+fn parts(address: &FhirAddress) -> Vec<Option<String>> {
+    vec![
+        Some(address.line.join(" / ")),
+        address.district.clone(),
+        address.city.clone(),
+        address.state.clone(),
+        address.postal_code.clone(),
+        address.country.clone(),
+    ]
+}
+
+fn some(values: [&str; 6]) -> Vec<Option<String>> {
+    values
+        .iter()
+        .map(|value| (!value.is_empty()).then(|| value.to_string()))
+        .collect()
+}
+
+#[test]
+fn countries_are_stored_as_alpha_2_codes() {
+    assert_eq!(validate_country("ve"), Ok("VE"));
+    assert_eq!(validate_country(" VEN "), Ok("VE"));
+    assert_eq!(validate_country("BRA"), Ok("BR"));
+    assert_eq!(
+        validate_country("XX"),
+        Err("Unknown ISO 3166 country code: XX".to_string())
+    );
+    assert!(validate_country("Venezuela").is_err());
+    let address = FhirAddress::new(FhirAddressUse::Home, FhirAddressType::Postal)
+        .with_country("col")
+        .unwrap();
+    assert_eq!(address.country.as_deref(), Some("CO"));
+}
+
+#[test]
+fn postal_codes_follow_the_country_format() {
+    let address = |country: &str, postal_code: &str| {
+        FhirAddress::new(FhirAddressUse::Work, FhirAddressType::Both)
+            .with_postal_code(postal_code)
+            .with_country(country)
+            .unwrap()
+    };
+    for (country, postal_code) in [
+        ("VE", "1010"),
+        ("CO", "110111"),
+        ("MX", "06600"),
+        ("US", "62704"),
+        ("US", "62704-1234"),
+        ("ES", "28013"),
+        ("BR", "01310-100"),
+        ("BR", "01310100"),
+        // Countries without rules accept any postal code
+        ("AR", "C1002"),
+    ] {
+        assert!(
+            address(country, postal_code).validate().is_ok(),
+            "{}",
+            postal_code
+        );
+    }
+    for (country, postal_code) in [
+        ("VE", "101"),
+        ("MX", "0660"),
+        ("US", "6270"),
+        ("BR", "0131-100"),
+    ] {
+        assert!(
+            address(country, postal_code).validate().is_err(),
+            "{}",
+            postal_code
+        );
+    }
+    assert_eq!(
+        address("MX", "ABCDE").validate().unwrap_err(),
+        "Invalid postal code for Mexico: ABCDE"
+    );
+}
+
+#[test]
+fn labels_are_laid_out_for_the_country() {
+    let address = FhirAddress::new(FhirAddressUse::Work, FhirAddressType::Both)
+        .with_line("Av. Francisco de Miranda")
+        .with_city("Caracas")
+        .with_state("Distrito Capital")
+        .with_postal_code("1060")
+        .with_country("VE")
+        .unwrap();
+    assert_eq!(
+        address.format_label("es"),
+        "Av. Francisco de Miranda\nCaracas 1060, Distrito Capital\nVenezuela"
+    );
+    let address = FhirAddress::new(FhirAddressUse::Work, FhirAddressType::Both)
+        .with_line("Av. Paulista, 1578")
+        .with_city("São Paulo")
+        .with_state("SP")
+        .with_postal_code("01310-200")
+        .with_country("BR")
+        .unwrap();
+    assert_eq!(
+        address.format_label("pt"),
+        "Av. Paulista, 1578\nSão Paulo - SP\n01310-200\nBrasil"
+    );
+    let address = FhirAddress::new(FhirAddressUse::Work, FhirAddressType::Both)
+        .with_line("Calle de Alcalá 1")
+        .with_city("Madrid")
+        .with_state("Madrid")
+        .with_postal_code("28014")
+        .with_country("ES")
+        .unwrap()
+        .with_generated_text("en");
+    assert_eq!(address.text, "Calle de Alcalá 1, 28014 Madrid, Spain");
+    assert_eq!(address.display("en"), address.text);
+}
+
+#[test]
+fn free_text_is_parsed_by_country() {
+    let address = FhirAddress::parse(
+        "Av. Francisco de Miranda, Urb. Los Palos Grandes, Caracas 1060, Distrito Capital",
+        "VE",
+    )
+    .unwrap();
+    assert_eq!(
+        parts(&address),
+        some([
+            "Av. Francisco de Miranda",
+            "Urb. Los Palos Grandes",
+            "Caracas",
+            "Distrito Capital",
+            "1060",
+            "VE"
+        ])
+    );
+
+    let address = FhirAddress::parse("123 Main St, Springfield IL 62704, USA", "VE").unwrap();
+    assert_eq!(
+        parts(&address),
+        some(["123 Main St", "", "Springfield", "IL", "62704", "US"])
+    );
+
+    let address = FhirAddress::parse(
+        "Av. Paulista, 1578\nSão Paulo - SP\n01310-200\nBrasil",
+        "VE",
+    )
+    .unwrap();
+    assert_eq!(
+        parts(&address),
+        some([
+            "Av. Paulista / 1578",
+            "",
+            "São Paulo",
+            "SP",
+            "01310-200",
+            "BR"
+        ])
+    );
+
+    let address = FhirAddress::parse(
+        "Insurgentes Sur 1234, Col. Del Valle, C.P. 03100, Ciudad de México, CDMX, México",
+        "VE",
+    )
+    .unwrap();
+    assert_eq!(
+        parts(&address),
+        some([
+            "Insurgentes Sur 1234",
+            "Col. Del Valle",
+            "Ciudad de México",
+            "CDMX",
+            "03100",
+            "MX"
+        ])
+    );
+}
+
+#[test]
+fn free_text_without_supported_rules_is_rejected() {
+    assert!(FhirAddress::parse("  ,\n ", "VE").is_err());
+    assert_eq!(
+        FhirAddress::parse("Florida 100, Buenos Aires", "AR").unwrap_err(),
+        "Address parsing is not supported for AR"
+    );
+    assert!(FhirAddress::parse("Calle 1", "ZZ").is_err());
+}
+
+#[test]
+fn geolocation_is_kept_as_an_extension() {
+    let address = FhirAddress::new(FhirAddressUse::Work, FhirAddressType::Physical)
+        .with_geolocation(FhirGeolocation::new(10.5, -66.9).unwrap());
+    let json = serde_json::to_value(&address).unwrap();
+    assert_eq!(
+        json["extension"][0]["url"],
+        "http://hl7.org/fhir/StructureDefinition/geolocation"
+    );
+    let read: FhirAddress = serde_json::from_value(json).unwrap();
+    assert_eq!(read.get_geolocation().unwrap().latitude.value(), 10.5);
+    assert!(FhirGeolocation::new(91.0, 0.0).is_err());
+}
@@ -0,0 +1,143 @@
+use ignis_nostr::{
+    datatypes::FhirInstant,
+    locale::{FhirLocaleCatalog, FhirLocalization},
+    resources::appointment::FhirAppointment,
+    valuesets::{FhirAppointmentStatus, FhirSpecialty},
+};
+
+fn appointment(start: &str, end: &str) -> FhirAppointment {
+    FhirAppointment::new(
+        FhirAppointmentStatus::Booked,
+        vec![],
+        FhirSpecialty::Anesthetics,
+        FhirInstant::parse(start).unwrap(),
+        FhirInstant::parse(end).unwrap(),
+        None,
+        vec![],
+    )
+}
+
+#[test]
+fn schedule_ending_the_same_local_day_shows_the_end_time() {
+    let appointment = appointment("2024-03-04T13:30:00Z", "2024-03-04T14:30:00Z");
+    assert_eq!(
+        appointment
+            .format_schedule("America/Caracas", "es")
+            .unwrap(),
+        "lunes, 4 de marzo de 2024, 09:30 -04 - 10:30 -04"
+    );
+}
+
+#[test]
+fn schedule_ending_another_local_day_shows_the_end_date() {
+    let appointment = appointment("2024-03-04T22:30:00Z", "2024-03-06T10:30:00Z");
+    assert_eq!(
+        appointment
+            .format_schedule("America/Caracas", "es")
+            .unwrap(),
+        "lunes, 4 de marzo de 2024, 18:30 -04 - miércoles, 6 de marzo de 2024, 06:30 -04"
+    );
+}
+
+#[test]
+fn schedule_compares_dates_in_the_local_zone() {
+    // 22:30 and 02:30 UTC fall on different UTC dates but the same Caracas one
+    let appointment = appointment("2024-03-04T22:30:00Z", "2024-03-05T02:30:00Z");
+    assert_eq!(
+        appointment
+            .format_schedule("America/Caracas", "es")
+            .unwrap(),
+        "lunes, 4 de marzo de 2024, 18:30 -04 - 22:30 -04"
+    );
+}
+
+#[test]
+fn schedule_names_come_from_the_locale_catalogs() {
+    let appointment = appointment("2024-03-04T22:30:00Z", "2024-03-06T10:30:00Z");
+    let localization = FhirLocalization::with_defaults().with_catalog(
+        FhirLocaleCatalog::new("es-VE")
+            .with_label("Monday", "Lunes")
+            .with_label("March", "Marzo"),
+    );
+    assert_eq!(
+        appointment
+            .format_schedule_with("America/Caracas", &localization, "es-VE")
+            .unwrap(),
+        "Lunes, 4 de Marzo de 2024, 18:30 -04 - miércoles, 6 de Marzo de 2024, 06:30 -04"
+    );
+}
+
+#[test]
+fn english_schedules_use_a_12_hour_clock() {
+    let appointment = appointment("2024-03-04T22:30:00Z", "2024-03-06T10:30:00Z");
+    assert_eq!(
+        appointment
+            .format_schedule("America/Caracas", "en")
+            .unwrap(),
+        "Monday, March 4, 2024, 6:30 PM -04 - Wednesday, March 6, 2024, 6:30 AM -04"
+    );
+}
+
+#[test]
+fn time_patterns_come_from_the_locale_catalogs() {
+    let appointment = appointment("2024-03-04T13:30:00Z", "2024-03-04T14:30:00Z");
+    let localization = FhirLocalization::with_defaults()
+        .with_catalog(
+            FhirLocaleCatalog::new("en-GB")
+                .with_label(
+                    "{weekday}, {month} {day}, {year}, {time}",
+                    "{weekday} {day} {month} {year}, {time}",
+                )
+                .with_label(
+                    "{hour12}:{minute} {period} {zone}",
+                    "{hour}:{minute} {zone}",
+                ),
+        )
+        .with_catalog(
+            FhirLocaleCatalog::new("pt-BR")
+                .with_label("{hour12}:{minute} {period} {zone}", "{hour}h{minute}"),
+        );
+    assert_eq!(
+        appointment
+            .format_schedule_with("Europe/London", &localization, "en-GB")
+            .unwrap(),
+        "Monday 4 March 2024, 13:30 GMT - 14:30 GMT"
+    );
+    assert_eq!(
+        appointment
+            .format_schedule_with("America/Sao_Paulo", &localization, "pt-BR")
+            .unwrap(),
+        "segunda-feira, 4 de março de 2024, 10h30 - 11h30"
+    );
+}
+
+#[test]
+fn narratives_show_the_schedule_in_the_localization_time_zone() {
+    let appointment = appointment("2024-03-04T13:30:00Z", "2024-03-04T14:30:00Z");
+    let xhtml = appointment
+        .narrative(FhirLocalization::defaults(), "es")
+        .to_xhtml();
+    assert!(
+        xhtml.contains("lunes, 4 de marzo de 2024, 13:30 UTC"),
+        "{}",
+        xhtml
+    );
+
+    let localization = FhirLocalization::with_defaults()
+        .with_time_zone("America/Caracas")
+        .unwrap();
+    let xhtml = appointment.narrative(&localization, "es").to_xhtml();
+    assert!(
+        xhtml.contains("lunes, 4 de marzo de 2024, 09:30 -04"),
+        "{}",
+        xhtml
+    );
+    assert!(
+        xhtml.contains("lunes, 4 de marzo de 2024, 10:30 -04"),
+        "{}",
+        xhtml
+    );
+    assert!(FhirLocalization::new()
+        .with_time_zone("Mars/Olympus")
+        .is_err());
+}
@@ -0,0 +1,40 @@
+use ignis_nostr::datatypes::{FhirDecimal, FhirExtension, FhirMoney, FhirQuantity, FhirValue};
+use serde_json::json;
+
+#[test]
+fn decimals_must_be_finite() {
+    for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        assert!(FhirDecimal::new(value).is_err());
+        assert!(FhirQuantity::ucum(value, "mg").is_err());
+        assert!(FhirMoney::new(value, "USD").is_err());
+    }
+    assert_eq!(FhirDecimal::new(1.5).unwrap().value(), 1.5);
+}
+
+#[test]
+fn values_have_total_equality() {
+    let decimal = FhirValue::Decimal(FhirDecimal::new(0.1).unwrap());
+    let quantity = FhirValue::Quantity(FhirQuantity::ucum(5.0, "mg").unwrap());
+    assert_eq!(decimal, decimal.clone());
+    assert_eq!(quantity, quantity.clone());
+}
+
+#[test]
+fn decimal_values_round_trip() {
+    let json = json!({"url": "http://x", "valueDecimal": 36.6});
+    let extension: FhirExtension = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(
+        extension.get_value(),
+        Some(&FhirValue::Decimal(FhirDecimal::new(36.6).unwrap()))
+    );
+    assert_eq!(serde_json::to_value(&extension).unwrap(), json);
+}
+
+#[test]
+fn whole_decimals_are_written_without_a_fraction() {
+    let json = json!({"url": "http://x", "valueDecimal": 72});
+    let extension: FhirExtension = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_string(&extension).unwrap(), json.to_string());
+    let decimal = FhirDecimal::new(-0.25).unwrap();
+    assert_eq!(serde_json::to_string(&decimal).unwrap(), "-0.25");
+}
@@ -0,0 +1,89 @@
+use ignis_nostr::{
+    datatypes::FhirInstant,
+    fhir_trait::{FhirReference, IgnisResource},
+    resources::{
+        appointment::FhirAppointment, appointment_response::FhirAppointmentResponse,
+        practitioner::FhirPractitioner,
+    },
+    valuesets::{
+        FhirAddressUse, FhirAppointmentResponseStatus, FhirAppointmentStatus,
+        FhirContactPointSystem, FhirContactPointUse, FhirIdentifierUse, FhirNameUse, FhirSpecialty,
+    },
+};
+use nostro2::userkeys::UserKeys;
+use serde_json::json;
+
+fn booked_appointment() -> FhirAppointment {
+    FhirAppointment::new(
+        FhirAppointmentStatus::Booked,
+        vec![],
+        FhirSpecialty::Anesthetics,
+        FhirInstant::parse("2024-03-04T13:30:00Z").unwrap(),
+        FhirInstant::parse("2024-03-04T14:30:00Z").unwrap(),
+        None,
+        vec![],
+    )
+}
+
+#[test]
+fn value_sets_bound_to_codes_are_written_as_codes() {
+    assert_eq!(
+        serde_json::to_value(FhirNameUse::Official).unwrap(),
+        json!("official")
+    );
+    assert_eq!(
+        serde_json::to_value(FhirAddressUse::Temporary).unwrap(),
+        json!("temp")
+    );
+    assert_eq!(
+        serde_json::to_value(FhirContactPointSystem::Email).unwrap(),
+        json!("email")
+    );
+    assert_eq!(
+        serde_json::to_value(FhirIdentifierUse::Secondary).unwrap(),
+        json!("secondary")
+    );
+}
+
+#[test]
+fn value_sets_bound_to_codes_read_codes_and_variant_names() {
+    for value in [json!("mobile"), json!("Mobile")] {
+        let contact_use: FhirContactPointUse = serde_json::from_value(value).unwrap();
+        assert_eq!(contact_use, FhirContactPointUse::Mobile);
+    }
+    let error = serde_json::from_value::<FhirContactPointUse>(json!("cell")).unwrap_err();
+    assert_eq!(error.to_string(), "Unknown FhirContactPointUse code: cell");
+}
+
+#[test]
+fn appointment_statuses_are_exported_as_codes() {
+    let keys = UserKeys::generate();
+    let mut appointment = IgnisResource::new(booked_appointment(), None, None);
+    appointment.sign_data(&keys).unwrap();
+    assert_eq!(appointment.get_fhir_json().unwrap()["status"], "booked");
+    assert!(appointment
+        .get_fhir_xml()
+        .unwrap()
+        .contains("<status value=\"booked\"/>"));
+
+    let actor = FhirReference::<FhirPractitioner>::literal("Practitioner/1", "Ana Pérez");
+    let response = FhirAppointmentResponse::new(booked_appointment(), actor, None, vec![]);
+    let mut response = IgnisResource::new(response, None, None);
+    response.sign_data(&keys).unwrap();
+    let json = response.get_fhir_json().unwrap();
+    assert_eq!(json["participantStatus"], "accepted");
+    assert!(response
+        .get_fhir_xml()
+        .unwrap()
+        .contains("<participantStatus value=\"accepted\"/>"));
+}
+
+#[test]
+fn appointment_statuses_read_codes_and_variant_names() {
+    for value in [json!("needs-action"), json!("NeedsAction")] {
+        let status: FhirAppointmentResponseStatus = serde_json::from_value(value).unwrap();
+        assert_eq!(status, FhirAppointmentResponseStatus::NeedsAction);
+    }
+    let status: FhirAppointmentStatus = serde_json::from_value(json!("checked-in")).unwrap();
+    assert_eq!(status, FhirAppointmentStatus::CheckedIn);
+}
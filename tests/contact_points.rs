@@ -0,0 +1,147 @@
+use ignis_nostr::{
+    datatypes::{normalize_phone, FhirContactPoint, FhirDateTime, FhirPeriod},
+    valuesets::{FhirContactPointSystem, FhirContactPointUse},
+};
+use serde_json::json;
+
+#[test]
+fn contact_points_are_written_without_unset_members() {
+    let email = FhirContactPoint::email("ana@example.com").unwrap();
+    assert_eq!(
+        serde_json::to_value(&email).unwrap(),
+        json!({"system": "email", "value": "ana@example.com", "use": "work"})
+    );
+}
+
+#[test]
+fn contact_points_without_a_system_or_use_are_read() {
+    let contact: FhirContactPoint = serde_json::from_value(json!({"value": "x"})).unwrap();
+    assert!(contact.system.is_none());
+    assert!(contact.contact_use.is_none());
+    assert!(contact.is_active());
+    let contacts = [contact];
+    assert!(FhirContactPoint::ranked(&contacts, FhirContactPointSystem::Email).is_empty());
+}
+
+#[test]
+fn national_numbers_are_normalized_to_e164() {
+    for (value, region, expected, mobile) in [
+        ("0412-555.12.34", "VE", "+584125551234", true),
+        ("(0212) 555 1234", "VE", "+582125551234", false),
+        ("300 123 4567", "CO", "+573001234567", true),
+        ("55 1234 5678", "MX", "+525512345678", false),
+        ("1 (415) 555-2671", "US", "+14155552671", false),
+        ("612 345 678", "es", "+34612345678", true),
+        ("(11) 91234-5678", "BR", "+5511912345678", true),
+        ("(11) 3123-4567", "BR", "+551131234567", false),
+        ("6123-4567", "PA", "+50761234567", true),
+    ] {
+        assert_eq!(
+            normalize_phone(value, region),
+            Ok((expected.to_string(), mobile)),
+            "{}",
+            value
+        );
+    }
+}
+
+#[test]
+fn international_numbers_ignore_the_default_region() {
+    assert_eq!(
+        normalize_phone("+58 412 555 1234", "US"),
+        Ok(("+584125551234".to_string(), true))
+    );
+    assert_eq!(
+        normalize_phone("0058 412 555 1234", "US"),
+        Ok(("+584125551234".to_string(), true))
+    );
+    // +1 numbers are reported as US, and calling codes share no prefix
+    assert_eq!(
+        normalize_phone("+1 416 555 0123", "VE"),
+        Ok(("+14165550123".to_string(), false))
+    );
+    assert_eq!(
+        normalize_phone("+593 99 123 4567", "VE"),
+        Ok(("+593991234567".to_string(), true))
+    );
+    // Regions without a dialing plan are kept when their length is plausible
+    assert_eq!(
+        normalize_phone("+49 30 1234567", "VE"),
+        Ok(("+49301234567".to_string(), false))
+    );
+}
+
+#[test]
+fn malformed_numbers_are_rejected() {
+    assert_eq!(
+        normalize_phone("0412-555-12345", "VE").unwrap_err(),
+        "Invalid phone number for region VE: 0412-555-12345"
+    );
+    assert_eq!(
+        normalize_phone("555 1234", "XX").unwrap_err(),
+        "No dialing plan for region XX"
+    );
+    for value in ["0412 CALL NOW", "58+4125551234", "+49 30", ""] {
+        assert!(normalize_phone(value, "VE").is_err(), "{:?}", value);
+    }
+}
+
+#[test]
+fn phones_are_mobile_or_work_contacts() {
+    let phone = FhirContactPoint::phone("0412 555 1234", "VE").unwrap();
+    assert_eq!(phone.value, "+584125551234");
+    assert_eq!(phone.contact_use, Some(FhirContactPointUse::Mobile));
+    let phone = FhirContactPoint::phone("0212 555 1234", "VE").unwrap();
+    assert_eq!(phone.contact_use, Some(FhirContactPointUse::Work));
+}
+
+#[test]
+fn emails_and_urls_are_validated() {
+    let email = FhirContactPoint::email(" Ana.Perez@Example.COM ").unwrap();
+    assert_eq!(email.value, "Ana.Perez@example.com");
+    for value in [
+        "ana",
+        "ana@",
+        "@example.com",
+        "ana@@example.com",
+        "ana perez@example.com",
+    ] {
+        assert!(FhirContactPoint::email(value).is_err(), "{}", value);
+    }
+    let url = FhirContactPoint::url("https://example.org/clinic").unwrap();
+    assert_eq!(url.system, Some(FhirContactPointSystem::Url));
+    assert!(FhirContactPoint::url("javascript:alert(1)").is_err());
+    assert!(FhirContactPoint::url("ftp://example.org").is_err());
+}
+
+#[test]
+fn preferred_contact_points_are_active_and_best_ranked() {
+    let expired = FhirPeriod::new(None, Some(FhirDateTime::parse("2020").unwrap())).unwrap();
+    let contacts = vec![
+        FhirContactPoint::email("old@example.com")
+            .unwrap()
+            .with_use(FhirContactPointUse::Old)
+            .with_rank(1),
+        FhirContactPoint::email("expired@example.com")
+            .unwrap()
+            .with_period(expired)
+            .with_rank(1),
+        FhirContactPoint::email("unranked@example.com").unwrap(),
+        FhirContactPoint::email("second@example.com")
+            .unwrap()
+            .with_rank(2),
+        FhirContactPoint::phone("0412 555 1234", "VE").unwrap(),
+    ];
+    let ranked: Vec<&str> = FhirContactPoint::ranked(&contacts, FhirContactPointSystem::Email)
+        .iter()
+        .map(|contact| contact.value.as_str())
+        .collect();
+    assert_eq!(ranked, vec!["second@example.com", "unranked@example.com"]);
+    assert_eq!(
+        FhirContactPoint::preferred(&contacts, FhirContactPointSystem::Phone)
+            .unwrap()
+            .value,
+        "+584125551234"
+    );
+    assert!(FhirContactPoint::preferred(&contacts, FhirContactPointSystem::Fax).is_none());
+}
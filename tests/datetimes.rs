@@ -0,0 +1,121 @@
+use chrono::Duration;
+use ignis_nostr::datatypes::{FhirDate, FhirDateTime, FhirDateTimePrecision, FhirInstant};
+
+#[test]
+fn dates_keep_their_precision() {
+    for (text, precision) in [
+        ("2024", FhirDateTimePrecision::Year),
+        ("2024-02", FhirDateTimePrecision::Month),
+        ("2024-02-29", FhirDateTimePrecision::Day),
+    ] {
+        let date = FhirDate::parse(text).unwrap();
+        assert_eq!(date.precision(), precision);
+        assert_eq!(date.to_string(), text);
+        let date_time = FhirDateTime::parse(text).unwrap();
+        assert_eq!(date_time.precision(), precision);
+        assert_eq!(date_time.to_string(), text);
+    }
+}
+
+#[test]
+fn malformed_dates_are_rejected() {
+    for text in [
+        "",
+        "24",
+        "2024-2",
+        "2024-02-3",
+        "2023-02-29",
+        "2024-13",
+        "0000",
+        "2024/02/01",
+        "+2024",
+        "2024-02-01T",
+    ] {
+        assert!(FhirDate::parse(text).is_err(), "{}", text);
+    }
+}
+
+#[test]
+fn date_times_with_a_time_need_seconds_and_a_zone() {
+    let value = FhirDateTime::parse("2024-03-04T09:30:00-04:00").unwrap();
+    assert_eq!(value.precision(), FhirDateTimePrecision::Second);
+    assert_eq!(value.to_string(), "2024-03-04T09:30:00-04:00");
+    assert_eq!(
+        FhirDateTime::parse("2024-03-04T09:30:00").unwrap_err(),
+        "FHIR dateTime with a time must include a timezone: 2024-03-04T09:30:00"
+    );
+    for text in [
+        "2024-03-04T09:30Z",
+        "2024-03-04t09:30:00Z",
+        "2024-03-04T09:30:00z",
+        "2024-03-04 09:30:00Z",
+        "2024-03-04T24:00:00Z",
+        "2024-3-04T09:30:00Z",
+    ] {
+        assert!(FhirDateTime::parse(text).is_err(), "{}", text);
+    }
+}
+
+#[test]
+fn fractional_seconds_are_kept() {
+    let value = FhirInstant::parse("2024-03-04T13:30:00.123Z").unwrap();
+    assert_eq!(value.to_string(), "2024-03-04T13:30:00.123Z");
+    let value = FhirInstant::parse("2024-03-04T13:30:00.000000001+00:00").unwrap();
+    assert_eq!(value.to_string(), "2024-03-04T13:30:00.000000001Z");
+}
+
+#[test]
+fn instants_need_full_precision() {
+    for text in ["2024", "2024-03-04", "2024-03-04T13:30:00"] {
+        assert!(FhirInstant::parse(text).is_err(), "{}", text);
+    }
+}
+
+#[test]
+fn values_are_read_and_written_as_json_strings() {
+    let value: FhirDateTime = serde_json::from_str("\"2024-03\"").unwrap();
+    assert_eq!(serde_json::to_string(&value).unwrap(), "\"2024-03\"");
+    assert!(serde_json::from_str::<FhirInstant>("\"2024-03\"").is_err());
+}
+
+#[test]
+fn partial_date_times_cover_their_whole_period() {
+    let month = FhirDateTime::parse("2024-02").unwrap();
+    assert_eq!(
+        month.end_of_precision().to_rfc3339(),
+        "2024-02-29T23:59:59.999999999+00:00"
+    );
+    let year = FhirDateTime::parse("2024").unwrap();
+    assert_eq!(
+        year.end_of_precision().to_rfc3339(),
+        "2024-12-31T23:59:59.999999999+00:00"
+    );
+    assert!(FhirDateTime::parse("2024-01-31").unwrap() < month);
+}
+
+#[test]
+fn durations_keep_the_precision() {
+    let day = FhirDateTime::parse("2024-02-28").unwrap();
+    assert_eq!((day + Duration::days(1)).unwrap().to_string(), "2024-02-29");
+    let month = FhirDateTime::parse("2024-01").unwrap();
+    assert_eq!((month + Duration::days(40)).unwrap().to_string(), "2024-02");
+    let instant = FhirInstant::parse("2024-03-04T13:30:00Z").unwrap();
+    assert_eq!(
+        (instant - Duration::minutes(90)).unwrap().to_string(),
+        "2024-03-04T12:00:00Z"
+    );
+    assert_eq!(
+        FhirInstant::parse("2024-03-04T14:30:00Z").unwrap() - instant,
+        Duration::hours(1)
+    );
+}
+
+#[test]
+fn durations_out_of_range_are_errors() {
+    let instant = FhirInstant::parse("9999-12-31T23:00:00Z").unwrap();
+    assert!((instant + Duration::hours(2)).is_err());
+    assert!(instant.checked_add(Duration::MAX).is_none());
+    let date_time = FhirDateTime::parse("0001").unwrap();
+    assert!((date_time - Duration::days(1)).is_err());
+    assert!(date_time.checked_sub(Duration::MAX).is_none());
+}
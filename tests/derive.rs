@@ -0,0 +1,147 @@
+// `#[derive(FhirResource)]` on resources defined outside the crate. The
+// compile errors for misused attributes are checked in tests/derive/*.rs.
+use ignis_nostr::{
+    consts::NOSTR_KIND_APPOINTMENT_RESPONSE,
+    fhir_trait::{FhirResource, IgnisResource},
+    resources::{FhirResourceDefinition, FhirResourceRegistry},
+};
+use nostro2::userkeys::UserKeys;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FhirResource)]
+#[fhir(resource_type = "Observation", kind = 2100)]
+struct TestObservation {
+    #[fhir(narrative)]
+    title: String,
+    #[fhir(narrative)]
+    note: Option<String>,
+    #[fhir(tag = "t")]
+    category: Vec<String>,
+    #[fhir(tag = "s")]
+    status: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FhirResource)]
+#[fhir(resource_type = "Basic", kind = 2101, narrative_with = summary)]
+struct TestBasic {
+    code: String,
+}
+impl TestBasic {
+    fn summary(&self) -> String {
+        format!("Basic {}", self.code)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FhirResource)]
+#[fhir(resource_type = "Flag", kind = 2102, register = TestFlag<String>)]
+struct TestFlag<T> {
+    #[fhir(narrative)]
+    code: String,
+    subject: T,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FhirResource)]
+#[fhir(resource_type = "List", kind = 2103)]
+struct TestList<T> {
+    #[fhir(narrative)]
+    title: String,
+    entry: Vec<T>,
+}
+
+fn observation() -> TestObservation {
+    TestObservation {
+        title: "Blood pressure".to_string(),
+        note: None,
+        category: vec!["vital-signs".to_string(), "exam".to_string()],
+        status: Some("final".to_string()),
+    }
+}
+
+#[test]
+fn narrative_fields_make_the_display() {
+    assert_eq!(observation().display(), "Blood pressure");
+    let noted = TestObservation {
+        note: Some("seated".to_string()),
+        ..observation()
+    };
+    assert_eq!(noted.display(), "Blood pressure seated");
+    let basic = TestBasic {
+        code: "x".to_string(),
+    };
+    assert_eq!(basic.display(), "Basic x");
+}
+
+#[test]
+fn tag_fields_make_one_tag_per_value() {
+    assert_eq!(
+        observation().tags(),
+        vec![
+            vec!["t".to_string(), "vital-signs".to_string()],
+            vec!["t".to_string(), "exam".to_string()],
+            vec!["s".to_string(), "final".to_string()],
+        ]
+    );
+    let unset = TestObservation {
+        category: vec![],
+        status: None,
+        ..observation()
+    };
+    assert!(unset.tags().is_empty());
+}
+
+fn definition<T: FhirResourceDefinition>() -> (&'static str, u32) {
+    (T::RESOURCE_TYPE, T::NOSTR_KIND)
+}
+
+fn round_trip<T: FhirResourceDefinition + PartialEq + std::fmt::Debug>(resource: T) {
+    let keys = UserKeys::generate();
+    let note = resource.sign_data(&keys).unwrap();
+    let decoded = IgnisResource::<T>::from_signed_note(&note, None).unwrap();
+    assert_eq!(decoded.get_resource(), &resource);
+}
+
+#[test]
+fn generic_resources_keep_their_parameters() {
+    fn bounded<T: Serialize + DeserializeOwned + Clone>(flag: TestFlag<T>) -> u32 {
+        flag.nostr_kind()
+    }
+    let flag = TestFlag {
+        code: "allergy".to_string(),
+        subject: 7u32,
+    };
+    assert_eq!(bounded(flag.clone()), 2102);
+    assert_eq!(definition::<TestFlag<u32>>(), ("Flag", 2102));
+    round_trip(flag);
+    round_trip(TestList {
+        title: "Problems".to_string(),
+        entry: vec!["asthma".to_string()],
+    });
+}
+
+#[test]
+fn derived_resources_are_registered() {
+    let registry = FhirResourceRegistry::with_defaults();
+    for (resource_type, kind) in [
+        definition::<TestObservation>(),
+        definition::<TestBasic>(),
+        definition::<TestFlag<String>>(),
+        ("AppointmentResponse", NOSTR_KIND_APPOINTMENT_RESPONSE),
+    ] {
+        let descriptor = registry.get(kind).unwrap();
+        assert_eq!(descriptor.get_resource_type(), resource_type);
+    }
+    // Generic resources without a register attribute are left out
+    assert!(!registry.is_registered(2103));
+
+    let keys = UserKeys::generate();
+    let note = observation().sign_data(&keys).unwrap();
+    let json = registry.decode(&note).unwrap();
+    assert_eq!(json["resourceType"], "Observation");
+    assert_eq!(json["title"], "Blood pressure");
+}
+
+#[test]
+fn misused_attributes_do_not_compile() {
+    let tests = trybuild::TestCases::new();
+    tests.compile_fail("tests/derive/*.rs");
+}
@@ -0,0 +1,10 @@
+use ignis_nostr::fhir_trait::FhirResource;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, FhirResource)]
+#[fhir(resource_type = "Observation", kind = 2100)]
+enum Observation {
+    Final(String),
+}
+
+fn main() {}
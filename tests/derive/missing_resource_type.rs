@@ -0,0 +1,11 @@
+use ignis_nostr::fhir_trait::FhirResource;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, FhirResource)]
+#[fhir(kind = 2100)]
+struct Observation {
+    #[fhir(narrative)]
+    title: String,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use ignis_nostr::fhir_trait::FhirResource;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, FhirResource)]
+#[fhir(resource_type = "Observation", kind = 2100, narrative_with = summary)]
+struct Observation {
+    #[fhir(narrative)]
+    title: String,
+}
+impl Observation {
+    fn summary(&self) -> String {
+        self.title.clone()
+    }
+}
+
+fn main() {}
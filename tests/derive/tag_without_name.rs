@@ -0,0 +1,13 @@
+use ignis_nostr::fhir_trait::FhirResource;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, FhirResource)]
+#[fhir(resource_type = "Observation", kind = 2100)]
+struct Observation {
+    #[fhir(narrative)]
+    title: String,
+    #[fhir(tag)]
+    category: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use ignis_nostr::fhir_trait::FhirResource;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, FhirResource)]
+#[fhir(resource_type = "Observation", kind = 2100, version = "4.0.1")]
+struct Observation {
+    #[fhir(narrative)]
+    title: String,
+}
+
+fn main() {}
@@ -0,0 +1,42 @@
+use ignis_nostr::{
+    consts::IGNIS_EXTENSION_LEGACY,
+    datatypes::{FhirElement, FhirExtensible},
+};
+use serde_json::{json, Value};
+
+fn element(extension: Value) -> Result<FhirElement, String> {
+    serde_json::from_value(json!({ "extension": extension })).map_err(|e| e.to_string())
+}
+
+#[test]
+fn invalid_extension_lists_are_rejected() {
+    let several_values = json!([{"url": "http://x", "valueString": "a", "valueBoolean": true}]);
+    assert!(element(several_values).is_err());
+    assert!(element(json!([{"valueString": "a"}])).is_err());
+    assert!(element(json!([{"url": "http://x"}, 1])).is_err());
+}
+
+#[test]
+fn valid_extension_lists_round_trip() {
+    let extension = json!([{"url": "http://x", "valueString": "a"}]);
+    let element = element(extension.clone()).unwrap();
+    assert_eq!(
+        element.get_extension("http://x").unwrap().get_url(),
+        "http://x"
+    );
+    assert_eq!(
+        serde_json::to_value(&element).unwrap()["extension"],
+        extension
+    );
+}
+
+#[test]
+fn non_list_values_are_kept_as_legacy_extensions() {
+    let legacy_element = element(json!({"clinic": "Caracas"})).unwrap();
+    let legacy = legacy_element.get_extension(IGNIS_EXTENSION_LEGACY).unwrap();
+    assert_eq!(
+        legacy.get_legacy_value(),
+        Some(json!({"clinic": "Caracas"}))
+    );
+    assert!(element(json!(null)).unwrap().is_empty());
+}
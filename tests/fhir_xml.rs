@@ -0,0 +1,28 @@
+use ignis_nostr::xml::{json_to_xml, to_fhir_xml, xml_to_json};
+use serde_json::json;
+
+#[test]
+fn only_extension_urls_are_attributes() {
+    let patient = json!({
+        "resourceType": "Patient",
+        "extension": [{"url": "http://example.org/ext", "valueString": "x"}],
+        "photo": [{"id": "p1", "contentType": "image/jpeg", "url": "http://example.org/photo.jpg"}],
+    });
+    let xml = json_to_xml(&patient).unwrap();
+    assert!(xml.contains("<extension url=\"http://example.org/ext\">"));
+    assert!(xml.contains("<photo id=\"p1\">"));
+    assert!(xml.contains("<url value=\"http://example.org/photo.jpg\"/>"));
+    assert!(!xml.contains("<photo url="));
+
+    // Without a definition a single photo reads back as an object
+    let photo = &xml_to_json(&xml).unwrap()["photo"];
+    assert_eq!(photo, &patient["photo"][0]);
+}
+
+#[test]
+fn modifier_extension_urls_are_attributes() {
+    let element =
+        json!({"modifierExtension": [{"url": "http://example.org/m", "valueBoolean": true}]});
+    let xml = to_fhir_xml("item", &element).unwrap();
+    assert!(xml.contains("<modifierExtension url=\"http://example.org/m\">"));
+}
@@ -0,0 +1,146 @@
+use ignis_nostr::datatypes::FhirHumanName;
+use serde_json::json;
+
+#[test]
+fn names_are_written_without_empty_parts() {
+    let name = FhirHumanName::parse("Ana Pérez", "es");
+    assert_eq!(
+        serde_json::to_value(&name).unwrap(),
+        json!({"use": "official", "text": "Ana Pérez", "family": "Pérez", "given": ["Ana"]})
+    );
+}
+
+#[test]
+fn family_name_extensions_are_read_and_written() {
+    let json = json!({
+        "family": "van Hentenryck",
+        "_family": {"extension": [{
+            "url": "http://hl7.org/fhir/StructureDefinition/humanname-own-prefix",
+            "valueString": "van"
+        }]},
+        "given": ["Pieter"]
+    });
+    let name: FhirHumanName = serde_json::from_value(json.clone()).unwrap();
+    assert!(name.name_use.is_none());
+    assert_eq!(serde_json::to_value(&name).unwrap(), json);
+}
+
+fn parts(name: &FhirHumanName) -> (Vec<&str>, Vec<&str>, &str, Vec<&str>) {
+    (
+        name.prefix.iter().map(String::as_str).collect(),
+        name.given.iter().map(String::as_str).collect(),
+        name.family.as_str(),
+        name.suffix.iter().map(String::as_str).collect(),
+    )
+}
+
+#[test]
+fn simple_names_keep_only_their_text() {
+    let name = FhirHumanName::new_simple("John Fitzgerald Kennedy".to_string());
+    assert_eq!(name.text, "John Fitzgerald Kennedy");
+    assert!(name.family.is_empty() && name.given.is_empty());
+    assert_eq!(name.display(), "John Fitzgerald Kennedy");
+    assert_eq!(name.format_formal("en"), "John Fitzgerald Kennedy");
+}
+
+#[test]
+fn spanish_and_portuguese_names_have_two_surnames() {
+    let name = FhirHumanName::parse("Dra. Ana María Pérez Gómez", "es");
+    assert_eq!(
+        parts(&name),
+        (vec!["Dra."], vec!["Ana", "María"], "Pérez Gómez", vec![])
+    );
+    assert_eq!(name.primary_surname("es"), "Pérez");
+    assert_eq!(name.format_formal("es"), "Dra. Pérez");
+
+    let name = FhirHumanName::parse("João Pedro Silva Santos", "pt-BR");
+    assert_eq!(
+        parts(&name),
+        (vec![], vec!["João", "Pedro"], "Silva Santos", vec![])
+    );
+    assert_eq!(name.primary_surname("pt-BR"), "Santos");
+}
+
+#[test]
+fn english_names_have_one_surname() {
+    let name = FhirHumanName::parse("John Fitzgerald Kennedy", "en");
+    assert_eq!(
+        parts(&name),
+        (vec![], vec!["John", "Fitzgerald"], "Kennedy", vec![])
+    );
+    assert_eq!(name.format_sortable(), "Kennedy, John Fitzgerald");
+}
+
+#[test]
+fn two_word_names_are_given_and_family_in_any_language() {
+    for language in ["es", "pt", "en"] {
+        let name = FhirHumanName::parse("Ana Pérez", language);
+        assert_eq!(parts(&name), (vec![], vec!["Ana"], "Pérez", vec![]));
+    }
+}
+
+#[test]
+fn family_given_order_is_recognised_by_the_comma() {
+    let name = FhirHumanName::parse("Pérez Gómez, Dra. Ana María", "es");
+    assert_eq!(
+        parts(&name),
+        (vec!["Dra."], vec!["Ana", "María"], "Pérez Gómez", vec![])
+    );
+    let name = FhirHumanName::parse("Kennedy, John", "en");
+    assert_eq!(parts(&name), (vec![], vec!["John"], "Kennedy", vec![]));
+}
+
+#[test]
+fn credentials_after_a_comma_are_suffixes() {
+    let name = FhirHumanName::parse("John Smith, MD", "en");
+    assert_eq!(parts(&name), (vec![], vec!["John"], "Smith", vec!["MD"]));
+    assert_eq!(name.format_full(), "John Smith, MD");
+    let name = FhirHumanName::parse("Dr. Ana Pérez Gómez Jr.", "es");
+    assert_eq!(
+        parts(&name),
+        (vec!["Dr."], vec!["Ana"], "Pérez Gómez", vec!["Jr."])
+    );
+}
+
+#[test]
+fn particles_stay_with_the_surname_that_follows() {
+    let name = FhirHumanName::parse("Juan de la Cruz Pérez", "es");
+    assert_eq!(
+        parts(&name),
+        (vec![], vec!["Juan"], "de la Cruz Pérez", vec![])
+    );
+    assert_eq!(name.surnames("es"), vec!["de la Cruz", "Pérez"]);
+    assert_eq!(name.primary_surname("es"), "de la Cruz");
+
+    let name = FhirHumanName::parse("Ludwig van Beethoven", "en");
+    assert_eq!(
+        parts(&name),
+        (vec![], vec!["Ludwig"], "van Beethoven", vec![])
+    );
+}
+
+#[test]
+fn do_is_a_particle_not_a_suffix() {
+    let name = FhirHumanName::parse("Maria Souza do Carmo", "pt");
+    assert_eq!(
+        parts(&name),
+        (vec![], vec!["Maria"], "Souza do Carmo", vec![])
+    );
+    assert_eq!(name.surnames("pt"), vec!["Souza", "do Carmo"]);
+}
+
+#[test]
+fn conjunctions_join_surnames() {
+    let name = FhirHumanName::parse("José Ortega y Gasset", "es");
+    assert_eq!(
+        parts(&name),
+        (vec![], vec!["José"], "Ortega y Gasset", vec![])
+    );
+    assert_eq!(name.surnames("es"), vec!["Ortega y Gasset"]);
+
+    let name = FhirHumanName::parse("José Ortega y Gasset", "en");
+    assert_eq!(
+        parts(&name),
+        (vec![], vec!["José", "Ortega", "y"], "Gasset", vec![])
+    );
+}
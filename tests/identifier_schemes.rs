@@ -0,0 +1,155 @@
+// Known-valid numbers are published examples or issued identifiers; the
+// invalid ones change a single digit so only the check digit catches them.
+use ignis_nostr::{
+    consts::{FHIR_IDENTIFIER_US_NPI, IGNIS_IDENTIFIER_MX_CURP, IGNIS_IDENTIFIER_VE_RIF},
+    datatypes::FhirHumanName,
+    fhir_trait::{FhirIdentifier, FhirResource},
+    identifiers::{
+        validate_mx_curp, validate_npi, validate_ve_cedula, validate_ve_rif, FhirIdentifierRegistry,
+    },
+    resources::{organization::FhirOrganization, practitioner::FhirPractitioner},
+    valuesets::FhirOrganizationType,
+};
+use serde_json::json;
+
+#[test]
+fn npi_check_digit_uses_the_80840_prefix() {
+    for npi in ["1234567893", "1245319599", "1003000126"] {
+        assert!(validate_npi(npi).is_ok(), "{}", npi);
+    }
+    // Valid Luhn numbers without the prefix
+    for npi in ["1234567897", "1245319593", "1003000120"] {
+        let error = validate_npi(npi).unwrap_err();
+        assert_eq!(error, format!("NPI check digit does not match: {}", npi));
+    }
+    assert!(validate_npi("123456789").is_err());
+    assert!(validate_npi("3234567893").is_err());
+    assert!(validate_npi("12345678a3").is_err());
+}
+
+#[test]
+fn rif_check_digit_is_modulo_11() {
+    // CANTV, SENIAT (check 11 folds to 0) and CANTV without separators
+    for rif in ["J-00124134-5", "G-20000303-0", "J001241345"] {
+        assert!(validate_ve_rif(rif).is_ok(), "{}", rif);
+    }
+    for rif in ["J-00124134-4", "G-20000303-1", "V-00124134-5"] {
+        let error = validate_ve_rif(rif).unwrap_err();
+        assert_eq!(error, format!("RIF check digit does not match: {}", rif));
+    }
+    assert!(validate_ve_rif("X-00124134-5").is_err());
+    assert!(validate_ve_rif("J-0012413-5").is_err());
+}
+
+#[test]
+fn curp_check_digit_covers_the_first_17_characters() {
+    for curp in [
+        "HEGG560427MVZRRL04",
+        "BOXW310820HNERXN09",
+        "hegg560427mvzrrl04",
+    ] {
+        assert!(validate_mx_curp(curp).is_ok(), "{}", curp);
+    }
+    for curp in ["HEGG560427MVZRRL05", "BOXW310820HNERXN08"] {
+        let error = validate_mx_curp(curp).unwrap_err();
+        assert_eq!(error, format!("CURP check digit does not match: {}", curp));
+    }
+    // Impossible birth date, unknown sex and unknown state
+    for curp in [
+        "HEGG561327MVZRRL04",
+        "HEGG560427QVZRRL04",
+        "HEGG560427MXXRRL04",
+    ] {
+        assert_eq!(
+            validate_mx_curp(curp).unwrap_err(),
+            format!("Invalid CURP: {}", curp)
+        );
+    }
+}
+
+#[test]
+fn cedula_accepts_the_usual_separators() {
+    for cedula in ["V-12.345.678", "E-84.123.456", "12345678", "v 1234567"] {
+        assert!(validate_ve_cedula(cedula).is_ok(), "{}", cedula);
+    }
+    for cedula in [
+        "VE-12345678",
+        "X-12345678",
+        "V-0123456",
+        "V-1234",
+        "V-1234567890",
+    ] {
+        assert!(validate_ve_cedula(cedula).is_err(), "{}", cedula);
+    }
+}
+
+#[test]
+fn default_registry_checks_these_schemes() {
+    let registry = FhirIdentifierRegistry::defaults();
+    assert!(registry
+        .validate_value(FHIR_IDENTIFIER_US_NPI, "1234567893")
+        .is_ok());
+    assert!(registry
+        .validate_value(FHIR_IDENTIFIER_US_NPI, "1234567890")
+        .is_err());
+    assert!(registry
+        .validate_value(IGNIS_IDENTIFIER_VE_RIF, "J-00124134-4")
+        .is_err());
+    assert!(registry
+        .validate_value(IGNIS_IDENTIFIER_MX_CURP, "HEGG560427MVZRRL04")
+        .is_ok());
+}
+
+fn npi(value: &str) -> FhirIdentifier {
+    FhirIdentifier::new(FHIR_IDENTIFIER_US_NPI.to_string(), value.to_string())
+}
+
+fn rif(value: &str) -> FhirIdentifier {
+    FhirIdentifier::new(IGNIS_IDENTIFIER_VE_RIF.to_string(), value.to_string())
+}
+
+#[test]
+fn practitioners_are_built_with_valid_identifiers_only() {
+    let name = || FhirHumanName::parse("Ana Pérez", "es");
+    let practitioner = FhirPractitioner::new(vec![npi("1234567893")], name(), vec![]).unwrap();
+    assert_eq!(practitioner.get_identifiers().len(), 1);
+    let error = FhirPractitioner::new(vec![npi("1234567890")], name(), vec![]).unwrap_err();
+    assert!(error.starts_with("Invalid identifier for http://hl7.org/fhir/sid/us-npi"));
+    // Systems without a validator are not checked
+    let registry = FhirIdentifierRegistry::new();
+    assert!(FhirPractitioner::new_with(vec![npi("1234567890")], name(), vec![], &registry).is_ok());
+}
+
+#[test]
+fn organizations_are_built_with_valid_identifiers_only() {
+    let organization = |identifier| {
+        FhirOrganization::new(
+            vec![identifier],
+            "Clínica Ávila".to_string(),
+            FhirOrganizationType::Provider,
+            vec![],
+            "Clínica".to_string(),
+        )
+    };
+    assert!(organization(rif("J-00124134-5")).is_ok());
+    assert!(organization(rif("J-00124134-4")).is_err());
+}
+
+#[test]
+fn validation_reports_identifiers_read_from_json() {
+    let practitioner: FhirPractitioner = serde_json::from_value(json!({
+        "identifier": [
+            {"system": FHIR_IDENTIFIER_US_NPI, "value": "1234567893"},
+            {"system": FHIR_IDENTIFIER_US_NPI, "value": "1234567890"}
+        ],
+        "name": {"text": "Ana Pérez", "family": "Pérez", "given": ["Ana"]}
+    }))
+    .unwrap();
+    let outcome = practitioner.validate();
+    let issues: Vec<_> = outcome
+        .get_issues()
+        .iter()
+        .flat_map(|issue| issue.get_expression().to_vec())
+        .collect();
+    assert_eq!(issues, vec!["Practitioner.identifier[1]"]);
+}
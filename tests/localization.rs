@@ -0,0 +1,132 @@
+use ignis_nostr::{
+    datatypes::{FhirContactPoint, FhirHumanName},
+    fhir_trait::FhirResource,
+    locale::{FhirLocaleCatalog, FhirLocalization},
+    resources::practitioner::FhirPractitioner,
+    valuesets::{
+        FhirAddressType, FhirAddressUse, FhirAppointmentResponseStatus, FhirAppointmentStatus,
+        FhirCodedValue, FhirContactEntityType, FhirContactPointSystem, FhirContactPointUse,
+        FhirIdentifierType, FhirIdentifierUse, FhirIssueSeverity, FhirIssueType, FhirNameUse,
+        FhirOrganizationType, FhirSpecialty,
+    },
+};
+
+// Codes of a value set without a display in one of the bundled languages
+fn untranslated<T: FhirCodedValue>() -> Vec<String> {
+    let localization = FhirLocalization::defaults();
+    T::all()
+        .iter()
+        .flat_map(|value| {
+            ["es", "pt"]
+                .into_iter()
+                .filter(move |language| {
+                    localization
+                        .display(value.fhir_system(), &value.fhir_code(), language)
+                        .is_none()
+                })
+                .map(move |language| format!("{} {}", language, value.fhir_code()))
+        })
+        .collect()
+}
+
+#[test]
+fn every_bundled_value_set_is_translated() {
+    let missing = [
+        untranslated::<FhirAddressType>(),
+        untranslated::<FhirAddressUse>(),
+        untranslated::<FhirAppointmentResponseStatus>(),
+        untranslated::<FhirAppointmentStatus>(),
+        untranslated::<FhirContactEntityType>(),
+        untranslated::<FhirContactPointSystem>(),
+        untranslated::<FhirContactPointUse>(),
+        untranslated::<FhirIdentifierType>(),
+        untranslated::<FhirIdentifierUse>(),
+        untranslated::<FhirIssueSeverity>(),
+        untranslated::<FhirIssueType>(),
+        untranslated::<FhirNameUse>(),
+        untranslated::<FhirOrganizationType>(),
+        untranslated::<FhirSpecialty>(),
+    ]
+    .concat();
+    assert_eq!(missing, Vec::<String>::new());
+}
+
+#[test]
+fn displays_fall_back_to_the_base_language_then_english() {
+    let value = FhirOrganizationType::Provider;
+    assert_eq!(value.display_in("es"), "Proveedor de salud");
+    assert_eq!(value.display_in("es-VE"), "Proveedor de salud");
+    assert_eq!(value.display_in("ES"), "Proveedor de salud");
+    assert_eq!(value.display_in("en"), "Healthcare Provider");
+    assert_eq!(value.display_in("fr"), "Healthcare Provider");
+}
+
+#[test]
+fn deprecated_spanish_helpers_match_the_catalog() {
+    #[allow(deprecated)]
+    let spanish = FhirAppointmentStatus::Booked.to_spanish_str().to_string();
+    assert_eq!(spanish, FhirAppointmentStatus::Booked.display_in("es"));
+}
+
+#[test]
+fn regional_catalogs_override_their_base_language() {
+    let value = FhirContactPointSystem::Phone;
+    let localization = FhirLocalization::with_defaults().with_catalog(
+        FhirLocaleCatalog::new("es-VE").with_display(value.fhir_system(), "phone", "Teléfono fijo"),
+    );
+    assert_eq!(localization.display_of(&value, "es-VE"), "Teléfono fijo");
+    assert_eq!(
+        localization.display_of(&value, "es"),
+        value.display_in("es")
+    );
+    // A catalog for a language already present is merged into it
+    let localization =
+        localization.with_catalog(FhirLocaleCatalog::new("es").with_label("Status", "Situación"));
+    assert_eq!(localization.label("Status", "es"), "Situación");
+    assert_eq!(localization.label("Specialty", "es"), "Especialidad");
+    assert_eq!(localization.label("Unknown label", "es"), "Unknown label");
+    assert_eq!(localization.languages(), vec!["en", "es", "pt", "es-VE"]);
+}
+
+#[test]
+fn catalogs_are_read_from_json() {
+    let catalog: FhirLocaleCatalog = serde_json::from_str(
+        r#"{"language": "pt-BR", "codes": {"http://hl7.org/fhir/appointmentstatus": {"booked": "Marcado"}}}"#,
+    )
+    .unwrap();
+    assert_eq!(catalog.get_language(), "pt-BR");
+    assert_eq!(
+        catalog.display("http://hl7.org/fhir/appointmentstatus", "booked"),
+        Some("Marcado")
+    );
+    assert_eq!(catalog.label("Status"), None);
+    let localization = FhirLocalization::with_defaults().with_catalog(catalog);
+    assert_eq!(
+        localization.display_of(&FhirAppointmentStatus::Booked, "pt-BR"),
+        "Marcado"
+    );
+    assert_eq!(
+        localization.display_of(&FhirAppointmentStatus::Cancelled, "pt-BR"),
+        FhirAppointmentStatus::Cancelled.display_in("pt")
+    );
+}
+
+#[test]
+fn narratives_are_rendered_in_the_requested_language() {
+    let practitioner = FhirPractitioner::new(
+        vec![],
+        FhirHumanName::parse("Ana Pérez", "es"),
+        vec![FhirContactPoint::email("ana@example.com").unwrap()],
+    )
+    .unwrap();
+    let localization = FhirLocalization::defaults();
+    let spanish = practitioner.localized_text(localization, "es");
+    assert!(spanish.get_div().contains("<th>Sistema</th>"));
+    assert!(spanish
+        .get_div()
+        .contains(FhirContactPointSystem::Email.display_in("es")));
+    let english = practitioner.localized_text(localization, "en");
+    assert!(english
+        .get_div()
+        .contains("<th>System</th><th>Value</th><th>Use</th>"));
+}
@@ -0,0 +1,136 @@
+use ignis_nostr::narrative::sanitize_xhtml;
+
+fn sanitized(xhtml: &str) -> String {
+    sanitize_xhtml(xhtml).unwrap()
+}
+
+fn div(inner: &str) -> String {
+    format!(
+        "<div xmlns=\"http://www.w3.org/1999/xhtml\">{}</div>",
+        inner
+    )
+}
+
+#[test]
+fn script_and_data_links_are_removed() {
+    assert_eq!(
+        sanitized("<div><a href=\"javascript:alert(1)\">x</a></div>"),
+        div("<a>x</a>")
+    );
+    assert_eq!(
+        sanitized("<div><img src=\"data:text/html;base64,PHNjcmlwdD4=\" alt=\"x\"/></div>"),
+        div("<img alt=\"x\"/>")
+    );
+    assert_eq!(
+        sanitized("<div><a href=\" JavaScript:alert(1)\">x</a></div>"),
+        div("<a>x</a>")
+    );
+}
+
+#[test]
+fn encoded_and_split_schemes_are_removed() {
+    for href in [
+        "javascript&#58;alert(1)",
+        "javascript&#x3A;alert(1)",
+        "java&#9;script:alert(1)",
+        "&#106;avascript:alert(1)",
+    ] {
+        let xhtml = format!("<div><a href=\"{}\">x</a></div>", href);
+        assert_eq!(sanitized(&xhtml), div("<a>x</a>"), "{}", href);
+    }
+}
+
+#[test]
+fn safe_links_are_kept() {
+    assert_eq!(
+        sanitized("<div><a href=\"https://example.org/a?b=1&amp;c=2\">x</a> <a href=\"#top\">y</a> <a href=\"Patient/1\">z</a></div>"),
+        div("<a href=\"https://example.org/a?b=1&amp;c=2\">x</a> <a href=\"#top\">y</a> <a href=\"Patient/1\">z</a>")
+    );
+    assert_eq!(
+        sanitized("<div><a href=\"mailto:ana@example.com\">x</a></div>"),
+        div("<a href=\"mailto:ana@example.com\">x</a>")
+    );
+}
+
+#[test]
+fn event_attributes_are_removed() {
+    assert_eq!(
+        sanitized("<div onload=\"alert(1)\"><p onclick=\"alert(1)\" OnMouseOver=\"x()\" class=\"c\">x</p></div>"),
+        div("<p class=\"c\">x</p>")
+    );
+}
+
+#[test]
+fn styles_that_load_or_run_anything_are_removed() {
+    for style in [
+        "background: url(https://example.org/x.png)",
+        "BACKGROUND: URL(x)",
+        "width: expression(alert(1))",
+        "behavior: url(x.htc)",
+        "@import 'x.css'",
+    ] {
+        let xhtml = format!("<div><p style=\"{}\">x</p></div>", style);
+        assert_eq!(sanitized(&xhtml), div("<p>x</p>"), "{}", style);
+    }
+    assert_eq!(
+        sanitized("<div><p style=\"color: red\">x</p></div>"),
+        div("<p style=\"color: red\">x</p>")
+    );
+}
+
+#[test]
+fn dropped_elements_are_removed_with_everything_inside() {
+    assert_eq!(
+        sanitized("<div>a<script>alert(1)<p>b</p></script>c</div>"),
+        div("ac")
+    );
+    assert_eq!(
+        sanitized("<div>a<form><div><iframe src=\"https://example.org\"><p>b</p></iframe><br/></div><input/></form>c</div>"),
+        div("ac")
+    );
+    assert_eq!(
+        sanitized("<div><svg><script>alert(1)</script></svg><p>b</p></div>"),
+        div("<p>b</p>")
+    );
+}
+
+#[test]
+fn other_elements_are_unwrapped() {
+    assert_eq!(
+        sanitized("<div><section><p>a</p><marquee>b</marquee></section></div>"),
+        div("<p>a</p>b")
+    );
+}
+
+#[test]
+fn content_after_the_root_div_is_ignored() {
+    assert_eq!(
+        sanitized("<div><p>a</p></div><script>alert(1)</script><p>b</p>"),
+        div("<p>a</p>")
+    );
+}
+
+#[test]
+fn content_without_a_root_div_is_wrapped() {
+    assert_eq!(sanitized("<p>a</p> b"), div("<p>a</p> b"));
+    assert_eq!(
+        sanitized("<div/>"),
+        "<div xmlns=\"http://www.w3.org/1999/xhtml\"/>"
+    );
+}
+
+#[test]
+fn text_is_escaped() {
+    assert_eq!(
+        sanitized("<div>1 &lt; 2 &amp;&nbsp;<![CDATA[<script>]]></div>"),
+        div("1 &lt; 2 &amp;\u{a0}&lt;script&gt;")
+    );
+}
+
+#[test]
+fn unclosed_markup_is_rejected() {
+    assert!(sanitize_xhtml("<div><p>a</p>").is_err());
+    assert!(sanitize_xhtml("<div><p>a</div>").is_err());
+    assert!(sanitize_xhtml("<div><p a=\"1>a</p></div>").is_err());
+    assert!(sanitize_xhtml("<div>a<script>alert(1)</div>").is_err());
+}
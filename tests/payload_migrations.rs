@@ -0,0 +1,206 @@
+// Note content of every historical schema, in tests/fixtures/payloads/v<schema>:
+//   v0  bare resource JSON as the 0.1 release wrote it: value set enums by
+//       variant name, any JSON as serviceCategory and extension, Practitioner
+//       contact points under "type"
+//   v1  the same, led by "ignisSchemaVersion": 1
+//   v2  the IgnisPayload envelope, Practitioner contact points under "telecom"
+//   v3  datatypes as FHIR JSON, what this version signs
+//   v4  a payload from a newer client, with members this version does not model
+use std::fs;
+
+use ignis_nostr::{
+    consts::{IGNIS_SCHEMA_VERSION, NOSTR_KIND_APPOINTMENT_RESPONSE},
+    datatypes::FhirExtensible,
+    fhir_trait::{FhirReference, FhirResource, IgnisResource},
+    resources::{
+        appointment::FhirAppointment, appointment_response::FhirAppointmentResponse,
+        organization::FhirOrganization, practitioner::FhirPractitioner, FhirResourceDefinition,
+        IgnisPayload,
+    },
+    valuesets::{FhirOrganizationType, FhirSpecialty},
+};
+use nostro2::{
+    notes::{Note, SignedNote},
+    userkeys::UserKeys,
+};
+use serde_json::{json, Value};
+
+const SCHEMAS: [&str; 4] = ["v0", "v1", "v2", "v3"];
+
+fn fixture(schema: &str, name: &str) -> String {
+    let path = format!(
+        "{}/tests/fixtures/payloads/{}/{}.json",
+        env!("CARGO_MANIFEST_DIR"),
+        schema,
+        name
+    );
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e))
+}
+
+fn signed_note<T: FhirResourceDefinition>(keys: &UserKeys, content: &str) -> SignedNote {
+    keys.sign_nostr_event(Note::new(&keys.get_public_key(), T::NOSTR_KIND, content))
+}
+
+fn decode<T: FhirResourceDefinition>(schema: &str, name: &str) -> IgnisResource<T> {
+    let keys = UserKeys::generate();
+    let note = signed_note::<T>(&keys, &fixture(schema, name));
+    IgnisResource::<T>::from_signed_note(&note, None)
+        .unwrap_or_else(|e| panic!("{}/{}: {}", schema, name, e))
+}
+
+fn assert_same_in_every_schema<T>(name: &str)
+where
+    T: FhirResourceDefinition + PartialEq + std::fmt::Debug,
+{
+    let current = decode::<T>("v3", name);
+    for (version, schema) in SCHEMAS.iter().enumerate() {
+        let decoded = decode::<T>(schema, name);
+        assert_eq!(decoded.get_resource(), current.get_resource(), "{}", schema);
+        assert_eq!(decoded.get_schema_version(), version as u32);
+        assert!(!decoded.is_newer_schema());
+    }
+}
+
+#[test]
+fn practitioner_decodes_the_same_from_every_schema() {
+    assert_same_in_every_schema::<FhirPractitioner>("practitioner");
+    let practitioner = decode::<FhirPractitioner>("v0", "practitioner");
+    let email = practitioner.get_resource().get_email().unwrap();
+    assert_eq!(email.value, "ana@example.com");
+}
+
+#[test]
+fn appointment_decodes_the_same_from_every_schema() {
+    assert_same_in_every_schema::<FhirAppointment>("appointment");
+}
+
+#[test]
+fn appointment_specialty_and_extension_are_upgraded_from_schema_0() {
+    let appointment = decode::<FhirAppointment>("v0", "appointment");
+    let appointment = appointment.get_resource();
+    assert_eq!(
+        appointment.get_specialty(),
+        Some(FhirSpecialty::Anesthetics)
+    );
+    assert_eq!(
+        appointment.get_service_category()[0].text.as_deref(),
+        Some("General consultation")
+    );
+    assert_eq!(
+        appointment.get_legacy_extension::<Value>(),
+        Some(json!({"room": "3B"}))
+    );
+}
+
+#[test]
+fn organization_decodes_the_same_from_every_schema() {
+    assert_same_in_every_schema::<FhirOrganization>("organization");
+    let organization = decode::<FhirOrganization>("v0", "organization");
+    assert_eq!(
+        organization.get_resource().get_org_type(),
+        Some(FhirOrganizationType::Provider)
+    );
+}
+
+#[test]
+fn schema_0_lists_that_are_not_extensions_become_legacy_extensions() {
+    let keys = UserKeys::generate();
+    let mut practitioner: Value = serde_json::from_str(&fixture("v0", "practitioner")).unwrap();
+    practitioner["extension"] = json!([1, 2]);
+    let note = signed_note::<FhirPractitioner>(&keys, &practitioner.to_string());
+    let practitioner = IgnisResource::<FhirPractitioner>::from_signed_note(&note, None).unwrap();
+    assert_eq!(
+        practitioner
+            .get_resource()
+            .get_legacy_extension::<Vec<u32>>(),
+        Some(vec![1, 2])
+    );
+}
+
+#[test]
+fn appointment_response_decodes_the_same_from_every_schema() {
+    assert_same_in_every_schema::<FhirAppointmentResponse<FhirPractitioner>>(
+        "appointment_response",
+    );
+}
+
+fn assert_signed_again_as_current<T: FhirResourceDefinition>(name: &str) {
+    let keys = UserKeys::generate();
+    let expected: Value = serde_json::from_str(&fixture("v3", name)).unwrap();
+    for schema in SCHEMAS {
+        let mut resource = decode::<T>(schema, name);
+        let note = resource.sign_data(&keys).unwrap();
+        let content: Value = serde_json::from_str(&note.get_content()).unwrap();
+        assert_eq!(content, expected, "{}/{}", schema, name);
+    }
+}
+
+#[test]
+fn schema_0_appointment_responses_are_exported_as_appointment_responses() {
+    let keys = UserKeys::generate();
+    let mut response =
+        decode::<FhirAppointmentResponse<FhirPractitioner>>("v0", "appointment_response");
+    assert_eq!(
+        response.get_fhir_json().unwrap()["resourceType"],
+        "AppointmentResponse"
+    );
+    let note = response.sign_data(&keys).unwrap();
+    assert_eq!(note.get_kind(), NOSTR_KIND_APPOINTMENT_RESPONSE);
+    let content: Value = serde_json::from_str(&note.get_content()).unwrap();
+    assert_eq!(content["contentType"], "AppointmentResponse");
+    let reference = FhirReference::new(response.get_resource().clone(), "1".to_string(), vec![]);
+    assert_eq!(reference.get_literal(), "AppointmentResponse/1");
+}
+
+#[test]
+fn legacy_payloads_are_signed_again_in_the_current_envelope() {
+    assert_signed_again_as_current::<FhirPractitioner>("practitioner");
+    assert_signed_again_as_current::<FhirAppointment>("appointment");
+    assert_signed_again_as_current::<FhirAppointmentResponse<FhirPractitioner>>(
+        "appointment_response",
+    );
+    assert_signed_again_as_current::<FhirOrganization>("organization");
+}
+
+#[test]
+fn fhir_json_uses_telecom_for_legacy_practitioners() {
+    let practitioner = decode::<FhirPractitioner>("v0", "practitioner");
+    let json = practitioner.get_fhir_json().unwrap();
+    assert_eq!(json["telecom"][0]["value"], "ana@example.com");
+    assert!(json.get("type").is_none());
+}
+
+#[test]
+fn newer_payloads_keep_their_unknown_members_and_version() {
+    let keys = UserKeys::generate();
+    let mut practitioner = decode::<FhirPractitioner>("v4", "practitioner");
+    assert!(practitioner.is_newer_schema());
+    assert_eq!(practitioner.get_schema_version(), IGNIS_SCHEMA_VERSION + 1);
+    let unknown = practitioner.get_unknown_elements().unwrap();
+    assert_eq!(unknown.names().collect::<Vec<_>>(), ["communication"]);
+
+    let note = practitioner.sign_data(&keys).unwrap();
+    let content: Value = serde_json::from_str(&note.get_content()).unwrap();
+    let expected: Value = serde_json::from_str(&fixture("v4", "practitioner")).unwrap();
+    assert_eq!(content, expected);
+}
+
+#[test]
+fn payloads_of_another_resource_type_are_rejected() {
+    let content = fixture("v2", "appointment");
+    let error = IgnisPayload::from_content(
+        &content,
+        FhirPractitioner::RESOURCE_TYPE,
+        FhirPractitioner::fhir_version(),
+    )
+    .unwrap_err();
+    assert_eq!(error, "Expected a Practitioner payload, found Appointment");
+}
+
+#[test]
+fn payloads_of_another_fhir_release_are_rejected() {
+    let content = fixture("v2", "practitioner").replace("5.0.0", "4.0.1");
+    let keys = UserKeys::generate();
+    let note = signed_note::<FhirPractitioner>(&keys, &content);
+    assert!(IgnisResource::<FhirPractitioner>::from_signed_note(&note, None).is_err());
+}
@@ -0,0 +1,145 @@
+use ignis_nostr::{
+    datatypes::{
+        FhirDateTime, FhirDuration, FhirMoney, FhirPeriod, FhirQuantity, FhirRange, FhirRatio,
+    },
+    valuesets::FhirQuantityComparator,
+};
+use serde_json::json;
+
+#[test]
+fn unset_bounds_and_quantity_members_are_left_out() {
+    let start = FhirDateTime::parse("2024-03-04").unwrap();
+    let period = FhirPeriod::starting(start);
+    assert_eq!(
+        serde_json::to_value(&period).unwrap(),
+        json!({"start": "2024-03-04"})
+    );
+
+    let range = FhirRange::new(Some(FhirQuantity::ucum(1.5, "mg").unwrap()), None).unwrap();
+    assert_eq!(
+        serde_json::to_value(&range).unwrap(),
+        json!({"low": {"value": 1.5, "unit": "mg", "system": "http://unitsofmeasure.org", "code": "mg"}})
+    );
+
+    let read: FhirPeriod = serde_json::from_value(json!({"end": null})).unwrap();
+    assert_eq!(read, FhirPeriod::new(None, None).unwrap());
+}
+
+fn date_time(text: &str) -> FhirDateTime {
+    FhirDateTime::parse(text).unwrap()
+}
+
+fn period(start: Option<&str>, end: Option<&str>) -> FhirPeriod {
+    FhirPeriod::new(start.map(date_time), end.map(date_time)).unwrap()
+}
+
+#[test]
+fn periods_end_after_they_start() {
+    assert!(FhirPeriod::new(Some(date_time("2024-03-05")), Some(date_time("2024-03-04"))).is_err());
+    // A partial end covers its whole month
+    assert!(FhirPeriod::new(Some(date_time("2024-03-31")), Some(date_time("2024-03"))).is_ok());
+}
+
+#[test]
+fn open_periods_contain_everything_on_their_open_side() {
+    let ongoing = period(Some("2024-03-04"), None);
+    assert!(ongoing.contains(&date_time("2999-01-01")));
+    assert!(!ongoing.contains(&date_time("2024-03-03")));
+    assert!(ongoing.is_active_now());
+    assert!(!ongoing.is_expired());
+
+    let until = period(None, Some("2024-03"));
+    assert!(until.contains(&date_time("1900")));
+    assert!(until.contains(&date_time("2024-03-31T23:59:59Z")));
+    assert!(!until.contains(&date_time("2024-04-01T00:00:00Z")));
+    assert!(until.is_expired());
+    assert_eq!(until.duration(), None);
+}
+
+#[test]
+fn periods_overlap_and_contain_other_periods() {
+    let march = period(Some("2024-03-01"), Some("2024-03-31"));
+    let week = period(Some("2024-03-04"), Some("2024-03-10"));
+    let april = period(Some("2024-04-01"), None);
+    assert!(march.overlaps(&week) && week.overlaps(&march));
+    assert!(!march.overlaps(&april));
+    assert!(april.overlaps(&period(None, Some("2024-04"))));
+    assert!(march.contains_period(&week));
+    assert!(!week.contains_period(&march));
+    assert!(!march.contains_period(&april));
+    assert!(period(None, None).contains_period(&april));
+    assert_eq!(week.duration(), Some(chrono::Duration::days(6)));
+}
+
+#[test]
+fn durations_use_ucum_time_units() {
+    let duration = FhirDuration::new(90.0, "min").unwrap();
+    assert_eq!(duration.seconds().unwrap(), 5400.0);
+    assert_eq!(duration.to_chrono().unwrap(), chrono::Duration::minutes(90));
+    assert!(FhirDuration::new(1.0, "kg").is_err());
+    assert!(FhirDuration::from_quantity(FhirQuantity::ucum(5.0, "mg").unwrap()).is_err());
+    assert_eq!(
+        FhirDuration::from_chrono(chrono::Duration::milliseconds(1500))
+            .unwrap()
+            .quantity()
+            .code
+            .as_deref(),
+        Some("ms")
+    );
+    assert_eq!(
+        serde_json::to_value(FhirDuration::from_chrono(chrono::Duration::hours(1)).unwrap())
+            .unwrap(),
+        json!({"value": 3600, "unit": "s", "system": "http://unitsofmeasure.org", "code": "s"})
+    );
+}
+
+#[test]
+fn quantities_need_a_system_for_their_code() {
+    assert!(FhirQuantity::ucum(1.0, "not a unit").is_err());
+    let quantity: FhirQuantity = serde_json::from_value(json!({"value": 1, "code": "mg"})).unwrap();
+    assert_eq!(
+        quantity.validate().unwrap_err(),
+        "Quantity code mg has no system"
+    );
+    let quantity = FhirQuantity::ucum(5.0, "mg")
+        .unwrap()
+        .with_comparator(FhirQuantityComparator::LessThan);
+    assert_eq!(serde_json::to_value(&quantity).unwrap()["comparator"], "<");
+}
+
+#[test]
+fn ranges_are_ordered_in_one_unit() {
+    let mg = |value| FhirQuantity::ucum(value, "mg").unwrap();
+    let range = FhirRange::new(Some(mg(1.0)), Some(mg(2.0))).unwrap();
+    assert!(range.contains(1.5) && !range.contains(2.5));
+    assert!(FhirRange::new(Some(mg(2.0)), Some(mg(1.0))).is_err());
+    assert!(FhirRange::new(Some(mg(1.0)), Some(FhirQuantity::ucum(2.0, "g").unwrap())).is_err());
+    let bounded = mg(1.0).with_comparator(FhirQuantityComparator::GreaterThan);
+    assert!(FhirRange::new(Some(bounded), None).is_err());
+}
+
+#[test]
+fn ratios_need_both_terms_and_a_nonzero_denominator() {
+    let ratio = FhirRatio::new(
+        FhirQuantity::ucum(5.0, "mg").unwrap(),
+        FhirQuantity::ucum(2.0, "mL").unwrap(),
+    )
+    .unwrap();
+    assert_eq!(ratio.value(), Some(2.5));
+    assert!(FhirRatio::new(
+        FhirQuantity::ucum(5.0, "mg").unwrap(),
+        FhirQuantity::ucum(0.0, "mL").unwrap()
+    )
+    .is_err());
+    let half: FhirRatio = serde_json::from_value(json!({"numerator": {"value": 1}})).unwrap();
+    assert!(half.validate().is_err());
+}
+
+#[test]
+fn money_uses_iso_4217_currencies() {
+    assert_eq!(
+        serde_json::to_value(FhirMoney::new(12.5, "VES").unwrap()).unwrap(),
+        json!({"value": 12.5, "currency": "VES"})
+    );
+    assert!(FhirMoney::new(12.5, "XYZ").is_err());
+}
@@ -0,0 +1,152 @@
+use ignis_nostr::{
+    datatypes::{FhirDateTime, FhirHumanName, FhirPeriod},
+    fhir_trait::{FhirIdentifiable, FhirIdentifier, FhirReference, IgnisResource},
+    resources::{organization::FhirOrganization, practitioner::FhirPractitioner},
+    valuesets::{FhirIdentifierType, FhirIdentifierUse},
+};
+use nostro2::userkeys::UserKeys;
+use serde_json::json;
+
+#[test]
+fn a_single_reference_identifier_is_written_as_an_object() {
+    let identifier = json!({"use": "official", "system": "urn:oid:1", "value": "V-12345678"});
+    let json = json!({"identifier": identifier, "display": "Centro Médico"});
+    let reference: FhirReference<FhirOrganization> = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&reference).unwrap(), json);
+
+    // Notes from earlier versions hold a list of identifiers
+    let legacy = json!({"reference": "", "identifier": [identifier], "display": "Centro Médico"});
+    let read: FhirReference<FhirOrganization> = serde_json::from_value(legacy).unwrap();
+    assert_eq!(read, reference);
+}
+
+#[test]
+fn identifiers_are_written_without_unset_members() {
+    let identifier = FhirIdentifier::new("urn:oid:1".to_string(), "1".to_string())
+        .with_use(FhirIdentifierUse::Old);
+    assert_eq!(
+        serde_json::to_value(&identifier).unwrap(),
+        json!({"use": "old", "system": "urn:oid:1", "value": "1"})
+    );
+    let reference = FhirReference::<FhirOrganization>::literal("Organization/1", "");
+    assert_eq!(
+        serde_json::to_value(&reference).unwrap(),
+        json!({"reference": "Organization/1"})
+    );
+}
+
+const LICENCES: &str = "https://example.org/licences/";
+
+fn licence(value: &str) -> FhirIdentifier {
+    FhirIdentifier::new(LICENCES.to_string(), value.to_string())
+        .with_type(FhirIdentifierType::MedicalLicense)
+}
+
+fn practitioner(identifiers: Vec<FhirIdentifier>) -> FhirPractitioner {
+    FhirPractitioner::new(
+        identifiers,
+        FhirHumanName::new_simple("Ana Pérez".to_string()),
+        vec![],
+    )
+    .unwrap()
+}
+
+fn period(start: &str, end: &str) -> FhirPeriod {
+    FhirPeriod::new(
+        Some(FhirDateTime::parse(start).unwrap()),
+        Some(FhirDateTime::parse(end).unwrap()),
+    )
+    .unwrap()
+}
+
+#[test]
+fn identifiers_are_read_with_every_member() {
+    let json = json!({
+        "use": "official",
+        "type": {"coding": [{"system": "http://terminology.hl7.org/CodeSystem/v2-0203", "code": "MD"}]},
+        "system": LICENCES,
+        "value": "MPPS-1234",
+        "period": {"start": "2019-01-01"},
+        "assigner": {"display": "Colegio de Médicos"}
+    });
+    let identifier: FhirIdentifier = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(identifier.get_use(), Some(FhirIdentifierUse::Official));
+    assert!(identifier.is_type(FhirIdentifierType::MedicalLicense));
+    assert!(!identifier.is_type(FhirIdentifierType::TaxId));
+    assert_eq!(identifier.get_system(), LICENCES);
+    assert_eq!(identifier.get_value(), "MPPS-1234");
+    assert_eq!(
+        identifier.get_period().unwrap().start.unwrap().to_string(),
+        "2019-01-01"
+    );
+    assert_eq!(
+        identifier.get_assigner().unwrap().get_display(),
+        "Colegio de Médicos"
+    );
+    assert_eq!(serde_json::to_value(&identifier).unwrap(), json);
+}
+
+#[test]
+fn values_are_matched_exactly_and_systems_loosely() {
+    let identifier = licence("MPPS-1234");
+    assert!(identifier.matches("https://example.org/licences", "MPPS-1234"));
+    assert!(!identifier.matches(LICENCES, "mpps-1234"));
+    assert!(!identifier.matches("https://example.org/other/", "MPPS-1234"));
+}
+
+#[test]
+fn old_and_expired_identifiers_are_not_active() {
+    assert!(licence("1").is_active());
+    assert!(!licence("1").with_use(FhirIdentifierUse::Old).is_active());
+    assert!(!licence("1")
+        .with_period(period("2010-01-01", "2015-12-31"))
+        .is_active());
+    assert!(licence("1")
+        .with_period(FhirPeriod::starting(FhirDateTime::parse("2016").unwrap()))
+        .is_active());
+}
+
+#[test]
+fn reissued_identifiers_prefer_the_active_one() {
+    let practitioner = practitioner(vec![
+        licence("OLD-1").with_use(FhirIdentifierUse::Old),
+        licence("NEW-2"),
+    ]);
+    assert_eq!(
+        practitioner.find_identifier(LICENCES).unwrap().get_value(),
+        "NEW-2"
+    );
+    assert_eq!(
+        practitioner
+            .find_identifier_by_type(FhirIdentifierType::MedicalLicense)
+            .unwrap()
+            .get_value(),
+        "NEW-2"
+    );
+    assert!(practitioner
+        .find_identifier_by_type(FhirIdentifierType::Passport)
+        .is_none());
+    // Old identifiers still identify the practitioner
+    assert!(practitioner.has_identifier(LICENCES, "OLD-1"));
+}
+
+#[test]
+fn practitioners_are_found_by_licence_within_a_store() {
+    let keys = UserKeys::generate();
+    let store: Vec<IgnisResource<FhirPractitioner>> = ["MPPS-1", "MPPS-2"]
+        .into_iter()
+        .map(|value| {
+            let mut resource = IgnisResource::new(practitioner(vec![licence(value)]), None, None);
+            resource.sign_data(&keys).unwrap();
+            resource
+        })
+        .collect();
+    let found = IgnisResource::find_by_identifier(&store, LICENCES, "MPPS-2").unwrap();
+    assert!(found.get_resource().has_identifier(LICENCES, "MPPS-2"));
+    assert!(IgnisResource::find_by_identifier(&store, LICENCES, "MPPS-3").is_none());
+    // The nostr identifier finds the resource too
+    let nostr = store[0].get_resource_identifier();
+    let found =
+        IgnisResource::find_by_identifier(&store, nostr.get_system(), nostr.get_value()).unwrap();
+    assert_eq!(found.get_resource_id(), store[0].get_resource_id());
+}
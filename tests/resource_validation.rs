@@ -0,0 +1,145 @@
+use ignis_nostr::{
+    datatypes::{FhirContactPoint, FhirHumanName, FhirInstant},
+    fhir_trait::{FhirReference, FhirResource, IgnisResource},
+    resources::{
+        appointment::FhirAppointment, appointment_response::FhirAppointmentResponse,
+        operation_outcome::FhirValidationMode, organization::FhirOrganization,
+        practitioner::FhirPractitioner,
+    },
+    valuesets::{FhirAppointmentStatus, FhirIssueType, FhirOrganizationType, FhirSpecialty},
+};
+use nostro2::userkeys::UserKeys;
+
+fn appointment(start: &str, end: &str) -> FhirAppointment {
+    FhirAppointment::new(
+        FhirAppointmentStatus::Booked,
+        vec![],
+        FhirSpecialty::Anesthetics,
+        FhirInstant::parse(start).unwrap(),
+        FhirInstant::parse(end).unwrap(),
+        None,
+        vec![],
+    )
+}
+
+fn backwards_appointment() -> FhirAppointment {
+    appointment("2024-03-04T14:30:00Z", "2024-03-04T13:30:00Z")
+}
+
+fn expressions<T: FhirResource>(resource: &T) -> Vec<String> {
+    resource
+        .validate()
+        .get_issues()
+        .iter()
+        .flat_map(|issue| issue.get_expression().to_vec())
+        .collect()
+}
+
+#[test]
+fn valid_resources_have_no_issues() {
+    let appointment = appointment("2024-03-04T13:30:00Z", "2024-03-04T14:30:00Z");
+    assert!(appointment.validate().get_issues().is_empty());
+    let practitioner = FhirPractitioner::new(
+        vec![],
+        FhirHumanName::parse("Ana Pérez", "es"),
+        vec![FhirContactPoint::email("ana@example.com").unwrap()],
+    )
+    .unwrap();
+    assert!(practitioner.validate().is_valid());
+}
+
+#[test]
+fn appointments_cannot_end_before_they_start() {
+    let outcome = backwards_appointment().validate();
+    let errors = outcome.errors();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].get_code(), FhirIssueType::Invariant);
+    assert_eq!(
+        errors[0].to_string(),
+        "error Appointment.end: app-3: end 2024-03-04T13:30:00Z is before start 2024-03-04T14:30:00Z"
+    );
+}
+
+#[test]
+fn responses_include_the_issues_of_their_appointment() {
+    let response = FhirAppointmentResponse::new(
+        backwards_appointment(),
+        FhirReference::<FhirPractitioner>::literal("", ""),
+        None,
+        vec![],
+    );
+    let mut issues = expressions(&response);
+    issues.sort();
+    assert_eq!(
+        issues,
+        vec![
+            "Appointment.end",
+            "AppointmentResponse.actor",
+            "AppointmentResponse.end"
+        ]
+    );
+}
+
+#[test]
+fn practitioners_and_organizations_need_a_name_and_valid_contacts() {
+    let practitioner = FhirPractitioner::new(
+        vec![],
+        FhirHumanName::new_simple(" ".to_string()),
+        vec![FhirContactPoint::new_phone(String::new())],
+    )
+    .unwrap();
+    assert_eq!(
+        expressions(&practitioner),
+        vec!["Practitioner.name", "Practitioner.telecom[0].value"]
+    );
+    let organization = FhirOrganization::new(
+        vec![],
+        String::new(),
+        FhirOrganizationType::Provider,
+        vec![],
+        String::new(),
+    )
+    .unwrap();
+    assert_eq!(expressions(&organization), vec!["Organization.name"]);
+}
+
+#[test]
+fn invalid_resources_are_not_signed_unless_validation_is_relaxed() {
+    let keys = UserKeys::generate();
+    let mut resource = IgnisResource::new(backwards_appointment(), None, None);
+    let error = resource.sign_data(&keys).unwrap_err();
+    assert!(error.contains("app-3"), "{}", error);
+    assert!(resource.get_signed_note().is_none());
+
+    let mut resource = resource.with_validation(FhirValidationMode::Warn);
+    resource.sign_data(&keys).unwrap();
+    assert!(!resource.get_validation_outcome().is_valid());
+
+    let mut resource = resource.with_validation(FhirValidationMode::Off);
+    resource.sign_data(&keys).unwrap();
+    assert!(resource.get_validation_outcome().get_issues().is_empty());
+}
+
+#[test]
+fn invalid_notes_are_rejected_when_decoded() {
+    let mut resource = IgnisResource::new(backwards_appointment(), None, None)
+        .with_validation(FhirValidationMode::Off);
+    let note = resource.sign_data(&UserKeys::generate()).unwrap();
+    assert!(IgnisResource::<FhirAppointment>::from_signed_note(&note, None).is_err());
+    let decoded = IgnisResource::<FhirAppointment>::from_signed_note_with(
+        &note,
+        None,
+        FhirValidationMode::Warn,
+    )
+    .unwrap();
+    assert_eq!(decoded.get_validation_outcome().errors().len(), 1);
+}
+
+#[test]
+fn outcomes_are_exported_as_fhir_operation_outcomes() {
+    let json = backwards_appointment().validate().to_fhir_json().unwrap();
+    assert_eq!(json["resourceType"], "OperationOutcome");
+    assert_eq!(json["issue"][0]["severity"], "error");
+    assert_eq!(json["issue"][0]["code"], "invariant");
+    assert_eq!(json["issue"][0]["expression"][0], "Appointment.end");
+}
@@ -0,0 +1,135 @@
+use ignis_nostr::{
+    datatypes::FhirInstant,
+    resources::appointment::FhirAppointment,
+    valuesets::{FhirAppointmentStatus, FhirSpecialty, FhirSpecialtyGroup},
+};
+
+#[test]
+fn single_letters_do_not_match_every_specialty() {
+    for query in ["q", "x", "z"] {
+        let found = FhirSpecialty::search(query);
+        assert!(found.len() < FhirSpecialty::ALL.len(), "{}", query);
+    }
+}
+
+#[test]
+fn short_words_must_match_exactly() {
+    let found = FhirSpecialty::search("ux");
+    assert!(found.is_empty(), "{:?}", found);
+}
+
+#[test]
+fn misspelled_words_still_match() {
+    assert_eq!(FhirSpecialty::search("cardiolgy")[0], FhirSpecialty::Cardiology);
+    assert!(FhirSpecialty::search("uroligy").contains(&FhirSpecialty::Urology));
+    assert!(FhirSpecialty::search("cardiologia").contains(&FhirSpecialty::Cardiology));
+}
+
+#[test]
+fn sub_specialties_have_a_parent() {
+    use FhirSpecialty::*;
+    assert_eq!(
+        SurgeryVascular.parent(),
+        Some(SurgicalSpecialtyOtherNotListed)
+    );
+    assert_eq!(MedicalOncology.parent(), Some(ClinicalOncology));
+    assert_eq!(GeneralPractice.parent(), None);
+    assert!(SurgicalSpecialtyOtherNotListed
+        .children()
+        .contains(&SurgeryNeurosurgery));
+    assert_eq!(
+        PediatricSurgeryBoneMarrowTransplantation.ancestors(),
+        vec![PediatricSurgery, PediatricGeneral]
+    );
+}
+
+#[test]
+fn local_codes_are_grouped_under_their_closest_specialty() {
+    use FhirSpecialty::*;
+    assert_eq!(SurgeryBariatric.parent(), Some(SurgeryGeneral));
+    assert_eq!(SurgeryEndoscopic.parent(), Some(SurgeryGeneral));
+    assert_eq!(PerinatalMedicine.parent(), Some(Obstetrics));
+    assert_eq!(GynecologyMastology.parent(), Some(Gynecology));
+    assert!(SurgeryBariatric.is_a(SurgicalSpecialtyOtherNotListed));
+}
+
+#[test]
+fn subsumption_includes_the_specialty_itself() {
+    use FhirSpecialty::*;
+    assert!(SurgeryGeneral.is_a(SurgeryGeneral));
+    assert!(SurgeryEndoscopic.is_a(SurgeryGeneral));
+    assert!(!SurgeryGeneral.is_a(SurgeryEndoscopic));
+    assert!(!Cardiology.is_a(SurgicalSpecialtyOtherNotListed));
+    assert!(SurgeryGeneral.descendants().contains(&SurgeryBariatric));
+    assert!(!SurgeryGeneral.descendants().contains(&SurgeryGeneral));
+}
+
+#[test]
+fn the_hierarchy_has_no_cycles() {
+    for specialty in FhirSpecialty::iter() {
+        assert!(
+            !specialty.ancestors().contains(&specialty),
+            "{:?}",
+            specialty
+        );
+    }
+}
+
+#[test]
+fn groups_cut_across_the_hierarchy() {
+    use FhirSpecialty::*;
+    let oncology = FhirSpecialtyGroup::Oncology.members();
+    for specialty in [
+        ClinicalOncology,
+        MedicalOncology,
+        SurgicalOncology,
+        UrologicalOncology,
+    ] {
+        assert!(oncology.contains(&specialty), "{:?}", specialty);
+    }
+    assert_eq!(
+        SurgicalOncology.groups(),
+        vec![FhirSpecialtyGroup::Surgery, FhirSpecialtyGroup::Oncology]
+    );
+    let surgery = FhirSpecialtyGroup::Surgery.members();
+    assert!(surgery.contains(&PediatricSurgery));
+    assert!(surgery.contains(&SurgeryDentalOralSurgery));
+    assert!(!surgery.contains(&Cardiology));
+}
+
+#[test]
+fn group_names_are_translated() {
+    assert_eq!(FhirSpecialtyGroup::Surgery.display_in("en"), "Surgery");
+    assert_ne!(FhirSpecialtyGroup::Surgery.display_in("es"), "Surgery");
+    assert_eq!(
+        FhirSpecialtyGroup::WomensHealth.display_in("fr"),
+        "Women's Health"
+    );
+}
+
+#[test]
+fn appointments_are_found_by_group_or_parent_specialty() {
+    let appointment = |specialty| {
+        FhirAppointment::new(
+            FhirAppointmentStatus::Booked,
+            vec![],
+            specialty,
+            FhirInstant::parse("2024-03-04T13:30:00Z").unwrap(),
+            FhirInstant::parse("2024-03-04T14:30:00Z").unwrap(),
+            None,
+            vec![],
+        )
+    };
+    let appointments = [
+        appointment(FhirSpecialty::SurgeryVascular),
+        appointment(FhirSpecialty::Cardiology),
+        appointment(FhirSpecialty::SurgeryBariatric),
+    ];
+    let surgical = appointments
+        .iter()
+        .filter(|appointment| appointment.in_specialty_group(FhirSpecialtyGroup::Surgery))
+        .count();
+    assert_eq!(surgical, 2);
+    assert!(appointments[2].has_specialty(FhirSpecialty::SurgeryGeneral));
+    assert!(!appointments[0].has_specialty(FhirSpecialty::SurgeryGeneral));
+}
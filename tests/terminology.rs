@@ -0,0 +1,298 @@
+use ignis_nostr::{
+    consts::SNOMED_CT_SYSTEM,
+    terminology::{FhirTerminology, FHIR_VALUESET_PRACTICE_CODES},
+    valuesets::{FhirAppointmentStatus, FhirCodedValue, FhirSpecialty},
+};
+use serde_json::json;
+
+const SYSTEM: &str = "http://example.org/CodeSystem/clinic-areas";
+const VALUE_SET: &str = "http://example.org/ValueSet/clinic-areas";
+
+// A local code system with a hierarchy and Spanish designations
+fn terminology() -> FhirTerminology {
+    let mut terminology = FhirTerminology::new();
+    terminology
+        .load_json(
+            &json!({
+                "resourceType": "Bundle",
+                "type": "collection",
+                "entry": [
+                    {"resource": {
+                        "resourceType": "CodeSystem",
+                        "url": SYSTEM,
+                        "name": "ClinicAreas",
+                        "version": "2",
+                        "status": "active",
+                        "content": "complete",
+                        "concept": [
+                            {"code": "surgery", "display": "Surgery",
+                             "designation": [{"language": "es", "value": "Cirugía"}],
+                             "concept": [
+                                {"code": "surgery-general", "display": "General surgery"},
+                                {"code": "surgery-vascular", "display": "Vascular surgery",
+                                 "designation": [{"language": "es", "value": "Cirugía vascular"}]}
+                             ]},
+                            {"code": "triage", "display": "Triage"}
+                        ]
+                    }},
+                    {"resource": {
+                        "resourceType": "ValueSet",
+                        "url": VALUE_SET,
+                        "status": "active",
+                        "compose": {
+                            "include": [{"system": SYSTEM, "filter": [
+                                {"property": "concept", "op": "is-a", "value": "surgery"}
+                            ]}],
+                            "exclude": [{"system": SYSTEM, "concept": [{"code": "surgery-general"}]}]
+                        }
+                    }}
+                ]
+            })
+            .to_string(),
+        )
+        .unwrap();
+    terminology
+}
+
+fn codes(terminology: &FhirTerminology, value_set: &str) -> Vec<String> {
+    terminology
+        .expand(value_set)
+        .unwrap()
+        .into_iter()
+        .filter_map(|contains| contains.code)
+        .collect()
+}
+
+#[test]
+fn code_systems_are_loaded_from_json() {
+    let terminology = terminology();
+    let code_system = terminology.get_code_system(SYSTEM).unwrap();
+    assert_eq!(code_system.all_concepts().len(), 4);
+    assert_eq!(
+        terminology.display(SYSTEM, "surgery-vascular", Some("es-VE")),
+        Some("Cirugía vascular".to_string())
+    );
+    // Concepts without a designation in the language keep their display
+    assert_eq!(
+        terminology.display(SYSTEM, "surgery-general", Some("es")),
+        Some("General surgery".to_string())
+    );
+    assert_eq!(terminology.display(SYSTEM, "radiology", None), None);
+}
+
+#[test]
+fn unknown_resources_are_rejected() {
+    let mut terminology = FhirTerminology::new();
+    assert_eq!(
+        terminology.load_json(r#"{"resourceType": "Patient"}"#),
+        Err("Unsupported terminology resource: Patient".to_string())
+    );
+    assert_eq!(
+        terminology.load_json("{}"),
+        Err("Missing resourceType".to_string())
+    );
+}
+
+#[test]
+fn lookup_returns_the_concept_and_its_designations() {
+    let terminology = terminology();
+    let result = terminology.lookup(SYSTEM, "surgery", Some("es")).unwrap();
+    assert_eq!(result.name, "ClinicAreas");
+    assert_eq!(result.version.as_deref(), Some("2"));
+    assert_eq!(result.display.as_deref(), Some("Cirugía"));
+    assert_eq!(result.designation.len(), 1);
+    assert_eq!(
+        terminology.lookup(SYSTEM, "radiology", None).unwrap_err(),
+        format!("Code radiology not found in {}", SYSTEM)
+    );
+    assert!(terminology
+        .lookup("http://example.org/unknown", "surgery", None)
+        .is_err());
+}
+
+#[test]
+fn codes_are_validated_against_their_code_system() {
+    let terminology = terminology();
+    assert!(
+        terminology
+            .validate_code_in_system(SYSTEM, "triage", None)
+            .result
+    );
+    assert!(
+        terminology
+            .validate_code_in_system(SYSTEM, "surgery", Some("Cirugía"))
+            .result
+    );
+    let result = terminology.validate_code_in_system(SYSTEM, "surgery", Some("Surgical"));
+    assert!(!result.result);
+    assert_eq!(result.display.as_deref(), Some("Surgery"));
+    assert_eq!(
+        result.message.as_deref(),
+        Some("Display \"Surgical\" does not match expected \"Surgery\"")
+    );
+    assert!(
+        !terminology
+            .validate_code_in_system(SYSTEM, "radiology", None)
+            .result
+    );
+}
+
+#[test]
+fn value_sets_are_expanded_with_filters_and_exclusions() {
+    let terminology = terminology();
+    assert_eq!(
+        codes(&terminology, VALUE_SET),
+        vec!["surgery", "surgery-vascular"]
+    );
+    let result = terminology.validate_code(VALUE_SET, SYSTEM, "surgery-general", None);
+    assert!(!result.result);
+    assert_eq!(
+        result.message.unwrap(),
+        format!(
+            "Code {}#surgery-general is not in value set {}",
+            SYSTEM, VALUE_SET
+        )
+    );
+    assert!(
+        terminology
+            .validate_code(
+                VALUE_SET,
+                SYSTEM,
+                "surgery-vascular",
+                Some("Cirugía vascular")
+            )
+            .result
+    );
+    assert!(terminology.expand("http://example.org/unknown").is_err());
+}
+
+#[test]
+fn filter_operations() {
+    let mut terminology = terminology();
+    for (op, value, expected) in [
+        (
+            "descendent-of",
+            "surgery",
+            vec!["surgery-general", "surgery-vascular"],
+        ),
+        ("is-not-a", "surgery", vec!["triage"]),
+        ("=", "triage", vec!["triage"]),
+        ("in", "triage, surgery", vec!["surgery", "triage"]),
+    ] {
+        let url = format!("http://example.org/ValueSet/{}", op);
+        terminology
+            .load_value(json!({
+                "resourceType": "ValueSet",
+                "url": url,
+                "status": "active",
+                "compose": {"include": [{"system": SYSTEM, "filter": [
+                    {"property": "concept", "op": op, "value": value}
+                ]}]}
+            }))
+            .unwrap();
+        assert_eq!(codes(&terminology, &url), expected, "{}", op);
+    }
+    terminology
+        .load_value(json!({
+            "resourceType": "ValueSet",
+            "url": "http://example.org/ValueSet/regex",
+            "status": "active",
+            "compose": {"include": [{"system": SYSTEM, "filter": [
+                {"property": "concept", "op": "regex", "value": "s.*"}
+            ]}]}
+        }))
+        .unwrap();
+    assert_eq!(
+        terminology
+            .expand("http://example.org/ValueSet/regex")
+            .unwrap_err(),
+        "Unsupported filter operation: regex"
+    );
+}
+
+#[test]
+fn value_sets_import_other_value_sets() {
+    let mut terminology = terminology();
+    terminology
+        .load_value(json!({
+            "resourceType": "ValueSet",
+            "url": "http://example.org/ValueSet/vascular",
+            "status": "active",
+            "compose": {"include": [{"valueSet": [VALUE_SET], "system": SYSTEM,
+                "concept": [{"code": "surgery-vascular"}, {"code": "triage"}]}]}
+        }))
+        .unwrap();
+    assert_eq!(
+        codes(&terminology, "http://example.org/ValueSet/vascular"),
+        vec!["surgery-vascular"]
+    );
+    terminology
+        .load_value(json!({
+            "resourceType": "ValueSet",
+            "url": "http://example.org/ValueSet/loop",
+            "status": "active",
+            "compose": {"include": [{"valueSet": ["http://example.org/ValueSet/loop"]}]}
+        }))
+        .unwrap();
+    assert!(terminology
+        .expand("http://example.org/ValueSet/loop")
+        .unwrap_err()
+        .contains("imports itself"));
+}
+
+#[test]
+fn defaults_cover_the_compiled_in_enums() {
+    let terminology = FhirTerminology::defaults();
+    let status = FhirAppointmentStatus::Booked;
+    assert_eq!(
+        codes(terminology, status.fhir_system()).len(),
+        FhirAppointmentStatus::all().len()
+    );
+    assert_eq!(
+        terminology.display(status.fhir_system(), &status.fhir_code(), Some("es")),
+        Some(status.display_in("es").to_string())
+    );
+    let practice_codes = terminology.expand(FHIR_VALUESET_PRACTICE_CODES).unwrap();
+    assert_eq!(practice_codes.len(), FhirSpecialty::all().len());
+    let specialty = FhirSpecialty::Anesthetics;
+    assert!(
+        terminology
+            .validate_code(
+                FHIR_VALUESET_PRACTICE_CODES,
+                SNOMED_CT_SYSTEM,
+                &specialty.fhir_code(),
+                Some(specialty.fhir_display())
+            )
+            .result
+    );
+}
+
+#[test]
+fn local_codes_are_added_without_a_release() {
+    let mut terminology = FhirTerminology::with_defaults();
+    terminology
+        .load_value(json!({
+            "resourceType": "ValueSet",
+            "url": "http://example.org/ValueSet/practice-codes",
+            "status": "active",
+            "compose": {"include": [
+                {"valueSet": [FHIR_VALUESET_PRACTICE_CODES]},
+                {"system": SYSTEM, "concept": [{"code": "triage", "display": "Triage"}]}
+            ]}
+        }))
+        .unwrap();
+    let expansion = terminology
+        .expand("http://example.org/ValueSet/practice-codes")
+        .unwrap();
+    assert_eq!(expansion.len(), FhirSpecialty::all().len() + 1);
+    assert!(
+        terminology
+            .validate_code(
+                "http://example.org/ValueSet/practice-codes",
+                SYSTEM,
+                "triage",
+                None
+            )
+            .result
+    );
+}
@@ -0,0 +1,53 @@
+use ignis_nostr::terminology::{
+    local_specialty_code_system, FhirCodeSystem, FhirConceptMap, FhirTerminology, FhirValueSet,
+};
+use serde_json::Value;
+
+fn assert_no_unset_members(json: &Value, path: &str) {
+    match json {
+        Value::Null => panic!("{} is null", path),
+        Value::Array(items) => {
+            assert!(!items.is_empty(), "{} is empty", path);
+            for (index, item) in items.iter().enumerate() {
+                assert_no_unset_members(item, &format!("{}[{}]", path, index));
+            }
+        }
+        Value::Object(map) => {
+            for (name, value) in map {
+                assert_no_unset_members(value, &format!("{}.{}", path, name));
+            }
+        }
+        _ => {}
+    }
+}
+
+#[test]
+fn published_terminology_has_no_unset_members() {
+    let code_system = local_specialty_code_system().unwrap();
+    let value_set = FhirValueSet::all_of_system(
+        "http://example.org/ValueSet/specialty".to_string(),
+        None,
+        code_system.url.clone(),
+    );
+    let concept_map = FhirConceptMap::specialty_to_snomed();
+    let published = [
+        code_system.to_fhir_json().unwrap(),
+        value_set.to_fhir_json().unwrap(),
+        concept_map.to_fhir_json().unwrap(),
+    ];
+    for json in &published {
+        assert_no_unset_members(json, json["resourceType"].as_str().unwrap());
+    }
+
+    let mut service = FhirTerminology::new();
+    for json in published {
+        service.load_value(json).unwrap();
+    }
+    let reloaded: &FhirCodeSystem = service.get_code_system(&code_system.url).unwrap();
+    assert_eq!(reloaded, &code_system);
+    assert_eq!(service.get_value_set(&value_set.url), Some(&value_set));
+    assert_eq!(
+        service.get_concept_map(&concept_map.url),
+        Some(&concept_map)
+    );
+}
@@ -0,0 +1,150 @@
+use ignis_nostr::{
+    datatypes::{FhirCodeableConcept, FhirCoding, FhirInstant},
+    fhir_trait::{FhirText, IgnisResource},
+    locale::{FhirLocaleCatalog, FhirLocalization},
+    resources::appointment::FhirAppointment,
+    valuesets::{FhirAppointmentStatus, FhirCodedValue, FhirSpecialty},
+};
+use nostro2::userkeys::UserKeys;
+use serde_json::{json, Value};
+
+fn signed_appointment() -> IgnisResource<FhirAppointment> {
+    let appointment = FhirAppointment::new(
+        FhirAppointmentStatus::Booked,
+        vec![],
+        FhirSpecialty::Anesthetics,
+        FhirInstant::parse("2024-03-04T13:30:00Z").unwrap(),
+        FhirInstant::parse("2024-03-04T14:30:00Z").unwrap(),
+        None,
+        vec![],
+    );
+    let mut resource = IgnisResource::new(appointment, None, None);
+    resource.sign_data(&UserKeys::generate()).unwrap();
+    resource
+}
+
+fn specialty(json: &Value) -> FhirCodeableConcept {
+    serde_json::from_value(json["specialty"][0].clone()).unwrap()
+}
+
+#[test]
+fn codings_carry_a_translation_per_language() {
+    let json = signed_appointment()
+        .get_fhir_json_in(&["es", "en", "pt"])
+        .unwrap();
+    assert_eq!(json["language"], "es");
+    let coding = &json["specialty"][0]["coding"][0];
+    assert_eq!(coding["display"], "Anesthetics");
+    assert_eq!(
+        coding["_display"]["extension"][0],
+        json!({
+            "url": "http://hl7.org/fhir/StructureDefinition/translation",
+            "extension": [
+                {"url": "lang", "valueCode": "es"},
+                {"url": "content", "valueString": "Anestesiología"}
+            ]
+        })
+    );
+    let specialty = specialty(&json);
+    assert_eq!(specialty.display_in("pt"), "Anestesiologia");
+    assert_eq!(specialty.display_in("es-VE"), "Anestesiología");
+    assert_eq!(specialty.display_in("fr"), "Anesthetics");
+}
+
+#[test]
+fn a_single_language_keeps_a_plain_narrative() {
+    let json = signed_appointment().get_fhir_json_in(&["pt"]).unwrap();
+    assert_eq!(json["language"], "pt");
+    let text: FhirText = serde_json::from_value(json["text"].clone()).unwrap();
+    assert!(text.get_languages().is_empty());
+    assert!(text.get_div().contains("<th>Especialidade</th>"));
+    assert_eq!(specialty(&json).display_in("pt"), "Anestesiologia");
+    assert_eq!(specialty(&json).display_in("es"), "Anesthetics");
+}
+
+#[test]
+fn no_languages_is_the_plain_export() {
+    let resource = signed_appointment();
+    assert_eq!(
+        resource.get_fhir_json_in(&[]).unwrap(),
+        resource.get_fhir_json().unwrap()
+    );
+}
+
+#[test]
+fn importers_pick_the_narrative_section_for_their_language() {
+    let json = signed_appointment()
+        .get_fhir_json_in(&["es", "en", "pt"])
+        .unwrap();
+    let text: FhirText = serde_json::from_value(json["text"].clone()).unwrap();
+    assert_eq!(text.get_languages(), vec!["es", "en", "pt"]);
+    assert!(text
+        .in_language("pt-BR")
+        .get_div()
+        .contains("<th>Especialidade</th>"));
+    assert!(text
+        .in_language("en")
+        .get_div()
+        .contains("<th>Specialty</th>"));
+    // Readers of a language without a section get the resource's language
+    assert!(text
+        .in_language("fr")
+        .get_div()
+        .contains("<th>Especialidad</th>"));
+    assert!(text.in_language("en").get_languages().is_empty());
+}
+
+#[test]
+fn narratives_round_trip_through_their_sections() {
+    let spanish = FhirText::new("Hola".to_string());
+    let english = FhirText::new("Hello".to_string());
+    let text =
+        FhirText::from_languages(&[("es", spanish.clone()), ("en", english.clone())]).unwrap();
+    assert_eq!(text.in_language("es"), spanish);
+    assert_eq!(text.in_language("en"), english);
+    assert_eq!(spanish.in_language("en"), spanish);
+}
+
+#[test]
+fn existing_translations_are_kept() {
+    let status = FhirAppointmentStatus::Booked;
+    let mut json = serde_json::to_value(
+        FhirCoding::new(
+            status.fhir_system().to_string(),
+            status.fhir_code(),
+            Some(status.fhir_display().to_string()),
+        )
+        .with_translation("es", "Confirmada"),
+    )
+    .unwrap();
+    FhirLocalization::defaults().add_translations(&mut json, &["es", "pt"]);
+    let coding: FhirCoding = serde_json::from_value(json).unwrap();
+    assert_eq!(coding.get_translation("es").unwrap(), "Confirmada");
+    assert_eq!(
+        coding.get_translation("pt").unwrap(),
+        status.display_in("pt")
+    );
+}
+
+#[test]
+fn regional_catalogs_are_used_for_their_language() {
+    let system = FhirSpecialty::Anesthetics.fhir_system();
+    let localization = FhirLocalization::with_defaults().with_catalog(
+        FhirLocaleCatalog::new("es-VE").with_display(system, "394577000", "Anestesia"),
+    );
+    let json = signed_appointment()
+        .get_fhir_json_in_with(&localization, &["es-VE", "es"])
+        .unwrap();
+    let specialty = specialty(&json);
+    assert_eq!(specialty.display_in("es-VE"), "Anestesia");
+    assert_eq!(specialty.display_in("es"), "Anestesiología");
+}
+
+#[test]
+fn quantities_are_not_translated() {
+    let mut json =
+        json!({"value": 1, "system": "http://unitsofmeasure.org", "code": "h", "unit": "h"});
+    let before = json.clone();
+    FhirLocalization::defaults().add_translations(&mut json, &["es"]);
+    assert_eq!(json, before);
+}
@@ -0,0 +1,113 @@
+// FHIR has no empty arrays or nulls, so unset members are left out of the
+// content and of the FHIR exports.
+use ignis_nostr::{
+    datatypes::{FhirHumanName, FhirInstant},
+    fhir_trait::{FhirReference, IgnisResource},
+    metadata::FhirExtendedContactDetail,
+    resources::{
+        appointment::FhirAppointment, appointment_response::FhirAppointmentResponse,
+        organization::FhirOrganization, practitioner::FhirPractitioner,
+    },
+    valuesets::{FhirAppointmentStatus, FhirOrganizationType, FhirSpecialty},
+};
+use nostro2::userkeys::UserKeys;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+fn empty_members(json: &Value, path: &str) -> Vec<String> {
+    match json {
+        Value::Null => vec![path.to_string()],
+        Value::Array(items) if items.is_empty() => vec![path.to_string()],
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .flat_map(|(index, item)| empty_members(item, &format!("{}[{}]", path, index)))
+            .collect(),
+        Value::Object(members) => members
+            .iter()
+            .flat_map(|(name, member)| empty_members(member, &format!("{}.{}", path, name)))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn assert_no_empty_members<T>(resource: T)
+where
+    T: ignis_nostr::fhir_trait::FhirResource + Serialize + DeserializeOwned + Clone,
+{
+    let content = serde_json::to_value(&resource).unwrap();
+    assert_eq!(empty_members(&content, "content"), Vec::<String>::new());
+    let mut resource = IgnisResource::new(resource, None, None);
+    resource.sign_data(&UserKeys::generate()).unwrap();
+    let json = resource.get_fhir_json().unwrap();
+    assert_eq!(empty_members(&json, "fhir"), Vec::<String>::new());
+}
+
+fn practitioner() -> FhirPractitioner {
+    FhirPractitioner::new(
+        vec![],
+        FhirHumanName::new_simple("Ana Pérez".to_string()),
+        vec![],
+    )
+    .unwrap()
+}
+
+fn appointment() -> FhirAppointment {
+    FhirAppointment::new(
+        FhirAppointmentStatus::Proposed,
+        vec![],
+        FhirSpecialty::Anesthetics,
+        FhirInstant::parse("2024-03-04T13:30:00Z").unwrap(),
+        FhirInstant::parse("2024-03-04T14:30:00Z").unwrap(),
+        None,
+        vec![],
+    )
+}
+
+#[test]
+fn practitioners_without_identifiers_telecom_or_extensions() {
+    assert_no_empty_members(practitioner());
+}
+
+#[test]
+fn organizations_without_identifiers_or_contacts() {
+    assert_no_empty_members(
+        FhirOrganization::new(
+            vec![],
+            "Clínica Ávila".to_string(),
+            FhirOrganizationType::Provider,
+            vec![],
+            "Clínica".to_string(),
+        )
+        .unwrap(),
+    );
+}
+
+#[test]
+fn contact_details_without_members() {
+    let serialized = serde_json::to_value(FhirExtendedContactDetail {
+        purpose: None,
+        name: None,
+        telecom: vec![],
+        address: None,
+        period: None,
+    })
+    .unwrap();
+    assert_eq!(serialized, serde_json::json!({}));
+}
+
+#[test]
+fn appointments_without_categories_description_or_extensions() {
+    assert_no_empty_members(appointment());
+}
+
+#[test]
+fn appointment_responses_without_a_comment() {
+    let actor = FhirReference::<FhirPractitioner>::literal("Practitioner/1", "Ana Pérez");
+    assert_no_empty_members(FhirAppointmentResponse::new(
+        appointment(),
+        actor,
+        None,
+        vec![],
+    ));
+}
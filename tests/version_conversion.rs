@@ -0,0 +1,106 @@
+use ignis_nostr::{
+    datatypes::{FhirContactPoint, FhirHumanName, FhirInstant},
+    fhir_trait::{FhirReference, IgnisResource},
+    resources::{
+        appointment::FhirAppointment, appointment_response::FhirAppointmentResponse,
+        from_fhir_version, operation_outcome::FhirOperationOutcome, practitioner::FhirPractitioner,
+        FhirVersion,
+    },
+    valuesets::{FhirAppointmentStatus, FhirIssueSeverity, FhirSpecialty},
+};
+use nostro2::userkeys::UserKeys;
+use serde_json::json;
+
+fn appointment() -> FhirAppointment {
+    FhirAppointment::new(
+        FhirAppointmentStatus::Booked,
+        vec![],
+        FhirSpecialty::Anesthetics,
+        FhirInstant::parse("2024-03-04T13:30:00Z").unwrap(),
+        FhirInstant::parse("2024-03-04T14:30:00Z").unwrap(),
+        Some("Pre-operative assessment".to_string()),
+        vec![],
+    )
+}
+
+fn practitioner() -> FhirPractitioner {
+    FhirPractitioner::new(
+        vec![],
+        FhirHumanName::parse("Ana Pérez", "es"),
+        vec![FhirContactPoint::email("ana@example.com").unwrap()],
+    )
+    .unwrap()
+}
+
+fn warnings(outcome: &FhirOperationOutcome) -> Vec<String> {
+    outcome
+        .get_issues()
+        .iter()
+        .filter(|issue| issue.get_severity() == FhirIssueSeverity::Warning)
+        .flat_map(|issue| issue.get_expression().to_vec())
+        .collect()
+}
+
+#[test]
+fn appointment_responses_are_valid_r4() {
+    let actor = FhirReference::new(practitioner(), "4f2b".to_string(), vec![]);
+    let response = FhirAppointmentResponse::new(appointment(), actor.clone(), None, vec![]);
+    let mut resource = IgnisResource::new(response, None, None);
+    resource.sign_data(&UserKeys::generate()).unwrap();
+
+    let conversion = resource.get_fhir_json_as(FhirVersion::R4).unwrap();
+    let r4 = conversion.get_value();
+    assert_eq!(r4["resourceType"], "AppointmentResponse");
+    assert_eq!(r4["participantStatus"], "accepted");
+    assert_eq!(r4["appointment"], json!({"reference": "#appointment"}));
+    let contained = &r4["contained"][0];
+    assert_eq!(contained["resourceType"], "Appointment");
+    assert_eq!(contained["id"], "appointment");
+    assert_eq!(contained["status"], "booked");
+    assert_eq!(
+        r4["actor"],
+        json!({
+            "reference": "Practitioner/4f2b",
+            "type": "Practitioner",
+            "display": "Ana Pérez"
+        })
+    );
+    assert!(r4.get("proposedNewTime").is_none());
+
+    // The embedded practitioner is lost, and reported
+    assert_eq!(
+        warnings(conversion.get_outcome()),
+        ["AppointmentResponse.actor.type"]
+    );
+    assert!(!conversion.is_lossless());
+
+    // Back in R5 the appointment is embedded again and the actor is a plain reference
+    let read = IgnisResource::<FhirAppointmentResponse<FhirPractitioner>>::resource_from_fhir_json(
+        r4.clone(),
+        FhirVersion::R4,
+    )
+    .unwrap();
+    assert!(read.get_outcome().get_issues().is_empty());
+    let actor = FhirReference::literal(actor.get_literal(), actor.get_display());
+    let expected = FhirAppointmentResponse::new(appointment(), actor, None, vec![]);
+    assert_eq!(read.get_value(), &expected);
+}
+
+#[test]
+fn r4_appointment_responses_for_other_appointments_are_reported() {
+    let r4 = json!({
+        "resourceType": "AppointmentResponse",
+        "appointment": {"reference": "Appointment/123"},
+        "actor": {"reference": "Practitioner/4f2b", "type": "Practitioner"},
+        "participantStatus": "accepted"
+    });
+    let conversion = from_fhir_version(r4, FhirVersion::R4);
+    assert_eq!(
+        warnings(conversion.get_outcome()),
+        ["AppointmentResponse.appointment"]
+    );
+    assert_eq!(
+        conversion.get_value()["actor"],
+        json!({"reference": "Practitioner/4f2b"})
+    );
+}